@@ -0,0 +1,19 @@
+use criterion::{ black_box, criterion_group, criterion_main, Criterion };
+use spool::{ ObjectPool, Pool };
+
+fn churn(c: &mut Criterion)
+{
+    c.bench_function("insert_delete_churn", |b| {
+        let mut pool = ObjectPool::new(10_000);
+        let mut keys: Vec<_> = (0..10_000u64).map(|i| pool.insert(i)).collect();
+
+        b.iter(|| {
+            // Free every other slot, then refill them.
+            for key in keys.iter().step_by(2) { pool.delete(key); }
+            for key in keys.iter_mut().step_by(2) { *key = pool.insert(black_box(1)); }
+        });
+    });
+}
+
+criterion_group!(benches, churn);
+criterion_main!(benches);
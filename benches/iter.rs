@@ -0,0 +1,20 @@
+use criterion::{ black_box, criterion_group, criterion_main, Criterion };
+use spool::{ ObjectPool, Pool };
+
+fn sparse_iter(c: &mut Criterion)
+{
+    // 1% occupancy: every hundredth slot of a 1M pool.
+    let mut pool = ObjectPool::new(1_000_000);
+    let keys: Vec<_> = (0..1_000_000u64).map(|i| pool.insert(i)).collect();
+    for (i, key) in keys.iter().enumerate()
+    {
+        if i % 100 != 0 { pool.delete(key); }
+    }
+
+    c.bench_function("sparse_iter", |b| {
+        b.iter(|| black_box(pool.iter().sum::<u64>()));
+    });
+}
+
+criterion_group!(benches, sparse_iter);
+criterion_main!(benches);
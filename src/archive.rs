@@ -0,0 +1,36 @@
+use rkyv::rancor::Fallible;
+use rkyv::with::{ ArchiveWith, DeserializeWith, SerializeWith };
+use rkyv::{ Archive, Archived, Place };
+
+/// Archives a `usize` as a `u64`, whatever rkyv's configured pointer width.
+///
+/// Free list links use `usize::MAX` as a sentinel, which wouldn't fit rkyv's default 32-bit archived `usize`.
+pub(crate) struct AsU64;
+
+impl ArchiveWith<usize> for AsU64
+{
+    type Archived = Archived<u64>;
+    type Resolver = ();
+
+    fn resolve_with(field: &usize, _: (), out: Place<Self::Archived>)
+    {
+        (*field as u64).resolve((), out);
+    }
+}
+
+impl<S: Fallible + ?Sized> SerializeWith<usize, S> for AsU64
+{
+    fn serialize_with(_: &usize, _: &mut S) -> Result<(), S::Error>
+    {
+        return Ok(());
+    }
+}
+
+impl<D: Fallible + ?Sized> DeserializeWith<Archived<u64>, usize, D> for AsU64
+{
+    fn deserialize_with(field: &Archived<u64>, _: &mut D) -> Result<usize, D::Error>
+    {
+        // Truncation maps the `u64::MAX` sentinel back to `usize::MAX` on narrower targets too.
+        return Ok(field.to_native() as usize);
+    }
+}
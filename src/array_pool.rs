@@ -0,0 +1,512 @@
+use crate::error::{ panic_full, PoolFull };
+use crate::pool::{ Pool, PoolKey, PoolEntry, FreeList, Iter, IterMut };
+use crate::pool_id::PoolId;
+
+/// A fixed-capacity pool backed by an inline array, so it never allocates.
+///
+/// Capacity is the const parameter `N`, known at compile time.
+/// Otherwise behaves exactly like a non-growable [`ObjectPool`] of the same capacity: slots are reused in the same order,
+/// and the same sequence of operations issues the same [`PoolKey`]s.
+///
+/// See [`Pool`] implementation for more information.
+///
+/// [`ObjectPool`]: struct.ObjectPool.html
+/// [`PoolKey`]: struct.PoolKey.html
+/// [`Pool`]: trait.Pool.html
+///
+/// ```rust
+/// use spool::{ ArrayPool, Pool };
+///
+/// let mut pool: ArrayPool<i32, 2> = ArrayPool::default();
+///
+/// let key1 = pool.insert(1);
+/// let key2 = pool.insert(2);
+///
+/// //Over capacity!
+/// assert!(pool.try_insert(404).is_err());
+///
+/// pool.delete(&key2);
+///
+/// //All is well.
+/// let key3 = pool.insert(3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ArrayPool<T, const N: usize>
+{
+    count: usize,
+    next: usize,
+    free: FreeList,
+    data: [PoolEntry<T>; N],
+    id: PoolId,
+}
+
+impl<T, const N: usize> ArrayPool<T, N>
+{
+    pub fn iter(&self) -> Iter<'_, T>
+    {
+        Iter::new(&self.data, None, self.count)
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T>
+    {
+        IterMut::new(&mut self.data, None, self.count)
+    }
+
+    // ====-====-====-====-====-==== //
+
+    /// Claims the next never-occupied slot since the last clear, skipping retired ones.
+    fn next_unused(&mut self) -> Option<usize>
+    {
+        while self.next < N
+        {
+            let index = self.next;
+            self.next += 1;
+
+            if !self.data[index].is_retired() { return Some(index); }
+        }
+
+        return None;
+    }
+}
+
+impl<T, const N: usize> Default for ArrayPool<T, N>
+{
+    /// Returns a new, empty pool.
+    fn default() -> Self
+    {
+        Self {
+            count: 0,
+            next: 0,
+            free: FreeList::new(),
+            data: core::array::from_fn(|_| PoolEntry::new()),
+            id: PoolId::unique(),
+        }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a ArrayPool<T, N>
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> { self.iter() }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut ArrayPool<T, N>
+{
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> { self.iter_mut() }
+}
+
+impl<T, const N: usize> Pool<T> for ArrayPool<T, N>
+{
+    /// Returns a new, empty pool.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the capacity is not `N`, as the capacity of an [`ArrayPool`] is fixed by its type.
+    /// See [`Default`] for a constructor taking no capacity.
+    ///
+    /// [`ArrayPool`]: struct.ArrayPool.html
+    /// [`Default`]: #impl-Default
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ArrayPool, Pool };
+    ///
+    /// let pool: ArrayPool<i32, 10> = ArrayPool::new(10);
+    /// assert_eq!(pool.capacity(), 10);
+    /// ```
+    fn new(capacity: usize) -> Self
+    {
+        assert_eq!(capacity, N, "ArrayPool capacity is fixed by its type");
+        return Self::default();
+    }
+
+    // ====-====-====-====-====-==== //
+
+    /// Returns `N`, the only capacity an [`ArrayPool`] of this type can have.
+    ///
+    /// [`ArrayPool`]: struct.ArrayPool.html
+    fn default_capacity() -> usize { N }
+
+    // ====-====-====-====-====-==== //
+
+    fn capacity(&self) -> usize { N }
+
+    fn len(&self) -> usize { self.count }
+
+    fn is_empty(&self) -> bool { self.len() == 0 }
+
+    // ====-====-====-====-====-==== //
+
+    /// Returns a [`PoolKey`] corresponding to the inserted item.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    ///
+    /// # Panics
+    ///
+    /// This function panics if pool is full. See [`try_insert`] for a non-panicking alternative.
+    ///
+    /// [`try_insert`]: #method.try_insert
+    fn insert(&mut self, value: T) -> PoolKey
+    {
+        match self.try_insert(value)
+        {
+            Ok(key) => key,
+            Err(_)  => panic_full("ArrayPool", N),
+        }
+    }
+
+    /// Returns a [`PoolKey`] corresponding to the inserted item, or [`PoolFull`] if the pool is full.
+    /// The rejected value is handed back inside the [`PoolFull`].
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    /// [`PoolFull`]: struct.PoolFull.html
+    fn try_insert(&mut self, value: T) -> Result<PoolKey, PoolFull<T>>
+    {
+        let index =
+            if let Some(index) = self.free.pop(&self.data)
+            {
+                index
+            }
+            else if let Some(index) = self.next_unused()
+            {
+                index
+            }
+            else
+            {
+                return Err(PoolFull(value));
+            };
+
+        let generation = self.data[index].set(value);
+
+        self.count += 1;
+
+        return Ok(PoolKey::tagged(index, generation, self.id));
+    }
+
+    fn get(&self, key: &PoolKey) -> Option<&T>
+    {
+        if !self.id.admits(key.pool_id) { return None; }
+
+        let entry = self.data.get(key.index)?;
+        if !entry.matches(key) { None } else { entry.get() }
+    }
+
+    fn get_mut(&mut self, key: &PoolKey) -> Option<&mut T>
+    {
+        if !self.id.admits(key.pool_id) { return None; }
+
+        let entry = self.data.get_mut(key.index)?;
+        if !entry.matches(key) { None } else { entry.get_mut() }
+    }
+
+    fn contains_key(&self, key: &PoolKey) -> bool
+    {
+        match self.data.get(key.index)
+        {
+            Some(entry) => self.id.admits(key.pool_id) && entry.matches(key) && !entry.is_empty(),
+            None        => false,
+        }
+    }
+
+    fn take(&mut self, key: &PoolKey) -> Option<T>
+    {
+        if !self.id.admits(key.pool_id) { return None; }
+
+        let entry = self.data.get(key.index)?;
+        if !entry.matches(key) || entry.is_empty() { return None; }
+
+        let data = self.free.release(&mut self.data, key.index);
+        self.count -= 1;
+
+        return data;
+    }
+
+    fn delete(&mut self, key: &PoolKey) -> bool
+    {
+        self.take(key).is_some()
+    }
+
+    fn clear(&mut self)
+    {
+        // Advance generations too, so no key issued before the clear can match a later insert.
+        for entry in self.data.iter_mut().filter(|e| !e.is_empty()) { entry.invalidate(); }
+
+        self.free.clear();
+        self.next = 0;
+        self.count = 0;
+    }
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    use crate::pool::{
+        Pool,
+        ObjectPool,
+    };
+
+    mod array_pool
+    {
+        mod new
+        {
+            use super::super::{
+                Pool,
+            };
+            use super::super::super::ArrayPool;
+
+            #[test]
+            fn correctly_initializes_a_pool()
+            {
+                let pool: ArrayPool<i32, 4> = ArrayPool::new(4);
+
+                assert_eq!(pool.capacity(), 4);
+                assert_eq!(pool.len(), 0);
+                assert_eq!(pool.free.len(), 0);
+                assert_eq!(pool.next, 0);
+            }
+
+            #[test]
+            #[should_panic]
+            fn panics_when_capacity_differs_from_n()
+            {
+                let _pool: ArrayPool<i32, 4> = ArrayPool::new(5);
+            }
+
+            #[test]
+            fn new_default_uses_n()
+            {
+                let pool: ArrayPool<i32, 4> = ArrayPool::new_default();
+
+                assert_eq!(ArrayPool::<i32, 4>::default_capacity(), 4);
+                assert_eq!(pool.capacity(), 4, "Expected the default capacity to be N, rather than panicking.");
+            }
+        }
+
+        mod insert
+        {
+            use super::super::{
+                Pool,
+            };
+            use super::super::super::ArrayPool;
+
+            #[test]
+            fn inserts_up_to_capacity()
+            {
+                let mut pool: ArrayPool<i32, 4> = ArrayPool::default();
+                let keys: Vec<_> = (0..4).map(|i| pool.insert(i)).collect();
+
+                for (i, key) in keys.iter().enumerate()
+                {
+                    assert_eq!(pool.get(key), Some(&(i as i32)), "Expected each key to resolve to its item.");
+                }
+
+                assert_eq!(pool.len(), 4);
+            }
+
+            #[test]
+            fn fails_when_full()
+            {
+                let mut pool: ArrayPool<i32, 4> = ArrayPool::default();
+                for i in 0..4 { pool.insert(i); }
+
+                let full = pool.try_insert(404);
+                assert_eq!(full.unwrap_err().into_inner(), 404, "Expected the rejected value to be handed back.");
+                assert_eq!(pool.len(), 4);
+            }
+
+            #[test]
+            #[should_panic(expected = "at capacity")]
+            fn panics_when_full()
+            {
+                let mut pool: ArrayPool<i32, 4> = ArrayPool::default();
+                for i in 0..5 { pool.insert(i); }
+            }
+        }
+
+        mod take
+        {
+            use super::super::{
+                Pool,
+            };
+            use super::super::super::ArrayPool;
+
+            #[test]
+            fn frees_slot_for_reuse_with_new_generation()
+            {
+                let mut pool: ArrayPool<i32, 4> = ArrayPool::default();
+                let keys: Vec<_> = (0..4).map(|i| pool.insert(i)).collect();
+
+                let key = keys[1];
+                assert_eq!(pool.take(&key), Some(1));
+                assert!(pool.get(&key).is_none(), "Expected taken key to be invalid.");
+                assert_eq!(pool.take(&key), None, "Expected a second take to fail.");
+
+                let new_key = pool.insert(100);
+                assert_eq!(new_key.index, key.index, "Expected the freed slot to be reused.");
+                assert_ne!(new_key, key, "Expected reused slot to issue a new generation.");
+                assert!(pool.get(&key).is_none());
+            }
+        }
+
+        mod delete
+        {
+            use super::super::{
+                Pool,
+            };
+            use super::super::super::ArrayPool;
+
+            #[test]
+            fn removes_item_and_ignores_stale_key()
+            {
+                let mut pool: ArrayPool<i32, 4> = ArrayPool::default();
+                let key1 = pool.insert(1);
+                let key2 = pool.insert(2);
+
+                assert!(pool.delete(&key1));
+                assert!(!pool.delete(&key1), "Expected a second delete to report nothing deleted.");
+
+                assert_eq!(pool.len(), 1);
+                assert_eq!(pool.free.len(), 1, "Expected stale delete not to push a duplicate free slot.");
+                assert!(pool.get(&key1).is_none());
+                assert_eq!(pool.get(&key2), Some(&2));
+            }
+        }
+
+        mod clear
+        {
+            use super::super::{
+                Pool,
+            };
+            use super::super::super::ArrayPool;
+
+            #[test]
+            fn invalidates_all_keys()
+            {
+                let mut pool: ArrayPool<i32, 4> = ArrayPool::default();
+                let keys: Vec<_> = (0..4).map(|i| pool.insert(i)).collect();
+
+                pool.clear();
+                assert!(pool.is_empty());
+
+                let new_keys: Vec<_> = (0..4).map(|i| pool.insert(i)).collect();
+                for key in keys
+                {
+                    assert!(pool.get(&key).is_none(), "Expected keys issued before the clear to be invalid.");
+                }
+                for key in new_keys
+                {
+                    assert!(pool.get(&key).is_some());
+                }
+            }
+        }
+
+        mod iter
+        {
+            use super::super::{
+                Pool,
+            };
+            use super::super::super::ArrayPool;
+
+            #[test]
+            fn is_exact_size_and_double_ended()
+            {
+                let mut pool: ArrayPool<i32, 6> = ArrayPool::default();
+                let keys: Vec<_> = (0..6).map(|i| pool.insert(i)).collect();
+                pool.delete(&keys[2]);
+                pool.delete(&keys[5]);
+
+                assert_eq!(pool.iter().len(), pool.len());
+                assert!(pool.iter().rev().eq(&[4, 3, 1, 0]));
+
+                let mut iter = pool.iter_mut();
+                assert_eq!(iter.next_back().map(|item| *item), Some(4));
+                assert_eq!(iter.len(), 3);
+            }
+        }
+
+        mod generation_overflow
+        {
+            use crate::pool::PoolEntry;
+            use super::super::{
+                Pool,
+            };
+            use super::super::super::ArrayPool;
+
+            #[test]
+            fn retires_slot_instead_of_wrapping()
+            {
+                let mut pool: ArrayPool<i32, 2> = ArrayPool::default();
+                pool.data[0] = PoolEntry::with_generation(u32::MAX - 2);
+
+                assert_eq!(pool.insert(1).generation.get(), u32::MAX - 1);
+                pool.insert(2);
+                pool.clear();
+
+                let key = pool.insert(3);
+                assert_eq!(key.index, 1, "Expected the retired slot to be skipped.");
+                assert!(pool.try_insert(4).is_err(), "Expected the retired slot not to be reused.");
+
+                pool.delete(&key);
+                assert_eq!(pool.insert(5).index, 1);
+            }
+        }
+
+        mod matches_object_pool
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+            use super::super::super::ArrayPool;
+
+            #[test]
+            fn issues_identical_keys_and_results()
+            {
+                let mut array: ArrayPool<i32, 4> = ArrayPool::default();
+                let mut object: ObjectPool<i32> = ObjectPool::new(4);
+
+                let mut array_keys = Vec::new();
+                let mut object_keys = Vec::new();
+                for i in 0..4
+                {
+                    array_keys.push(array.insert(i));
+                    object_keys.push(object.insert(i));
+                }
+
+                assert_eq!(array.try_insert(4).is_err(), object.try_insert(4).is_err());
+
+                assert_eq!(array.take(&array_keys[1]), object.take(&object_keys[1]));
+                array.delete(&array_keys[3]);
+                object.delete(&object_keys[3]);
+
+                for i in 10..12
+                {
+                    array_keys.push(array.insert(i));
+                    object_keys.push(object.insert(i));
+                }
+
+                for (array_key, object_key) in array_keys.iter().zip(&object_keys)
+                {
+                    assert_eq!(array_key.as_u64(), object_key.as_u64(), "Expected both pools to issue the same keys, reusing slots in the same order.");
+                    assert_eq!(array.get(array_key), object.get(object_key));
+                    assert_eq!(array.contains_key(array_key), object.contains_key(object_key));
+                }
+                assert_eq!(array.len(), object.len());
+                assert!(array.iter().eq(object.iter()));
+
+                array.clear();
+                object.clear();
+
+                assert_eq!(array.insert(20).as_u64(), object.insert(20).as_u64());
+                assert_eq!(array.len(), object.len());
+            }
+        }
+    }
+}
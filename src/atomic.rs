@@ -0,0 +1,491 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::num::NonZeroU32;
+use core::ops::{ Deref, DerefMut };
+use core::sync::atomic::{ AtomicU32, AtomicUsize, Ordering };
+use std::sync::{ Mutex, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard };
+
+use crate::error::{ panic_full, PoolError, PoolFull };
+use crate::index::PoolIndex;
+use crate::pool::PoolKey;
+
+/// The last generation a slot issues. Past it, the slot is retired, as [`ObjectPool`] does.
+///
+/// [`ObjectPool`]: struct.ObjectPool.html
+const LAST_GENERATION: u32 = u32::MAX - 2;
+
+/// A slot whose generation can be checked without locking.
+///
+/// Writers only change the generation while holding the item's write lock, bumping it after storing an item
+/// and before taking one out, so an occupied slot always carries an odd generation which matches its key.
+struct AtomicSlot<T>
+{
+    generation: AtomicU32,
+    item: RwLock<Option<T>>,
+}
+
+impl<T> AtomicSlot<T>
+{
+    fn new() -> Self
+    {
+        Self {
+            generation: AtomicU32::new(0),
+            item: RwLock::new(None),
+        }
+    }
+
+    fn matches<I>(&self, key: &PoolKey<I>) -> bool
+    {
+        return self.generation.load(Ordering::Acquire) == key.generation.get();
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, Option<T>>
+    {
+        self.item.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Option<T>>
+    {
+        self.item.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+// ===-===-===-===-===-===-===-===-===-===-===-===-=== //
+
+/// A fixed capacity pool for read-heavy sharing between threads, locking per slot rather than per pool.
+///
+/// Every slot carries an atomic generation, so stale keys are rejected, and [`contains_key`] answered, without taking any lock.
+/// Reading or writing a live item locks only its own slot, and the generation is checked again under that lock,
+/// so a reader racing a delete and reinsert of the same slot gets `None` rather than the new item.
+/// Readers of different slots never contend, and readers of the same slot share its lock.
+/// Only inserting and removing touch the shared free list, behind a [`Mutex`].
+///
+/// The pool never grows, since moving the slots would pull them out from under concurrent readers.
+/// For a growable pool behind a single lock, see [`ConcurrentPool`].
+///
+/// [`contains_key`]: #method.contains_key
+/// [`Mutex`]: https://doc.rust-lang.org/std/sync/struct.Mutex.html
+/// [`ConcurrentPool`]: struct.ConcurrentPool.html
+///
+/// # Examples
+///
+/// ```
+/// use spool::AtomicPool;
+///
+/// let pool = AtomicPool::new(10);
+/// let key = pool.insert(1);
+///
+/// std::thread::scope(|scope|
+/// {
+///     scope.spawn(|| assert_eq!(*pool.get(&key).unwrap(), 1));
+///     scope.spawn(|| assert_eq!(*pool.get(&key).unwrap(), 1));
+/// });
+///
+/// assert!(pool.delete(&key));
+/// assert!(!pool.contains_key(&key));
+/// ```
+pub struct AtomicPool<T, I = usize>
+{
+    slots: Box<[AtomicSlot<T>]>,
+    free: Mutex<Vec<usize>>,
+    count: AtomicUsize,
+    index: PhantomData<I>,
+}
+
+impl<T> AtomicPool<T>
+{
+    /// Returns a new, empty pool. Preallocated with specified capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::AtomicPool;
+    ///
+    /// let pool: AtomicPool<i32> = AtomicPool::new(10);
+    /// assert_eq!(pool.capacity(), 10);
+    /// ```
+    pub fn new(capacity: usize) -> Self
+    {
+        Self::try_new(capacity).expect("usize indices cover any capacity")
+    }
+}
+
+impl<T, I: PoolIndex> AtomicPool<T, I>
+{
+    /// Returns a new, empty pool. Preallocated with specified capacity.
+    /// Returns [`PoolError::CapacityOverflow`] if the capacity exceeds the range of the pool's index type.
+    ///
+    /// [`PoolError::CapacityOverflow`]: enum.PoolError.html#variant.CapacityOverflow
+    pub fn try_new(capacity: usize) -> Result<Self, PoolError>
+    {
+        if capacity > I::MAX_INDEX.saturating_add(1) { return Err(PoolError::CapacityOverflow); }
+
+        return Ok(Self {
+            slots: (0..capacity).map(|_| AtomicSlot::new()).collect(),
+            free: Mutex::new((0..capacity).rev().collect()),
+            count: AtomicUsize::new(0),
+            index: PhantomData,
+        });
+    }
+
+    pub fn capacity(&self) -> usize { self.slots.len() }
+
+    /// Returns the number of items in the pool. Only a snapshot, other threads may be inserting or removing meanwhile.
+    pub fn len(&self) -> usize { self.count.load(Ordering::Acquire) }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    // ====-====-====-====-====-==== //
+
+    /// Returns a [`PoolKey`] corresponding to the inserted item.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the pool is full. See [`try_insert`] for a non-panicking alternative.
+    ///
+    /// [`try_insert`]: #method.try_insert
+    pub fn insert(&self, value: T) -> PoolKey<I>
+    {
+        match self.try_insert(value)
+        {
+            Ok(key) => key,
+            Err(_)  => panic_full("AtomicPool", self.capacity()),
+        }
+    }
+
+    /// Returns a [`PoolKey`] corresponding to the inserted item, or [`PoolFull`] if the pool is full.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    /// [`PoolFull`]: struct.PoolFull.html
+    pub fn try_insert(&self, value: T) -> Result<PoolKey<I>, PoolFull<T>>
+    {
+        let mut value = Some(value);
+        match self.try_insert_with_key(|_| value.take().expect("called once"))
+        {
+            Some(key) => Ok(key),
+            None      => Err(PoolFull(value.expect("not called when full"))),
+        }
+    }
+
+    /// Inserts the item returned by `f`, which is handed the key the item will be stored under.
+    /// Returns that key.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the pool is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::AtomicPool;
+    ///
+    /// let pool = AtomicPool::new(10);
+    /// let key = pool.insert_with_key(|key| key.as_u64());
+    ///
+    /// assert_eq!(*pool.get(&key).unwrap(), key.as_u64());
+    /// ```
+    pub fn insert_with_key<F: FnOnce(PoolKey<I>) -> T>(&self, f: F) -> PoolKey<I>
+    {
+        match self.try_insert_with_key(f)
+        {
+            Some(key) => key,
+            None      => panic_full("AtomicPool", self.capacity()),
+        }
+    }
+
+    fn try_insert_with_key<F: FnOnce(PoolKey<I>) -> T>(&self, f: F) -> Option<PoolKey<I>>
+    {
+        let index = self.free.lock().unwrap_or_else(PoisonError::into_inner).pop()?;
+        let slot = &self.slots[index];
+
+        let mut item = slot.write();
+        let generation = slot.generation.load(Ordering::Relaxed) + 1;
+        let key = PoolKey::new(
+            I::from_usize(index).expect("capacity fits the index type"),
+            NonZeroU32::new(generation).expect("generation is non-zero after increment"),
+        );
+
+        *item = Some(f(key));
+        slot.generation.store(generation, Ordering::Release);
+        self.count.fetch_add(1, Ordering::AcqRel);
+
+        return Some(key);
+    }
+
+    // ====-====-====-====-====-==== //
+
+    /// Returns `true` if the key refers to an item in the pool. Takes no lock.
+    pub fn contains_key(&self, key: &PoolKey<I>) -> bool
+    {
+        match self.slots.get(key.index.into_usize())
+        {
+            // Only occupied slots carry an odd generation, a vacant one keeps the even generation it was vacated at.
+            Some(slot) => key.generation.get() % 2 == 1 && slot.matches(key),
+            None       => false,
+        }
+    }
+
+    /// Returns a guard dereferencing to the item, holding a shared lock on its slot alone.
+    /// Returns `None` if the key is out of range, stale, or points at an empty entry.
+    ///
+    /// Stale keys are rejected before locking.
+    pub fn get(&self, key: &PoolKey<I>) -> Option<AtomicItemRef<'_, T>>
+    {
+        let slot = self.slots.get(key.index.into_usize())?;
+        if !slot.matches(key) { return None; }
+
+        // The slot may have been emptied or reused since the check, confirm under the lock.
+        let guard = slot.read();
+        if !slot.matches(key) || guard.is_none() { return None; }
+
+        return Some(AtomicItemRef { guard });
+    }
+
+    /// Returns a guard mutably dereferencing to the item, holding an exclusive lock on its slot alone.
+    /// Returns `None` if the key is out of range, stale, or points at an empty entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::AtomicPool;
+    ///
+    /// let pool = AtomicPool::new(10);
+    /// let key = pool.insert(1);
+    ///
+    /// *pool.get_mut(&key).unwrap() += 1;
+    /// assert_eq!(*pool.get(&key).unwrap(), 2);
+    /// ```
+    pub fn get_mut(&self, key: &PoolKey<I>) -> Option<AtomicItemMut<'_, T>>
+    {
+        let slot = self.slots.get(key.index.into_usize())?;
+        if !slot.matches(key) { return None; }
+
+        let guard = slot.write();
+        if !slot.matches(key) || guard.is_none() { return None; }
+
+        return Some(AtomicItemMut { guard });
+    }
+
+    /// Removes the item from the pool and returns it. Returns `None` if the key is out of range, stale, or points at an empty entry.
+    pub fn take(&self, key: &PoolKey<I>) -> Option<T>
+    {
+        let index = key.index.into_usize();
+        let slot = self.slots.get(index)?;
+        if !slot.matches(key) { return None; }
+
+        let item = {
+            let mut guard = slot.write();
+            // A vacant slot keeps the generation its last item was taken at, so a key can match one with nothing in it.
+            if !slot.matches(key) || guard.is_none() { return None; }
+
+            slot.generation.store(key.generation.get() + 1, Ordering::Release);
+            guard.take()
+        };
+
+        self.count.fetch_sub(1, Ordering::AcqRel);
+        if key.generation.get() < LAST_GENERATION
+        {
+            self.free.lock().unwrap_or_else(PoisonError::into_inner).push(index);
+        }
+
+        return item;
+    }
+
+    /// Returns `true` if an item was deleted, `false` if the key is out of range, stale, or points at an empty entry.
+    pub fn delete(&self, key: &PoolKey<I>) -> bool
+    {
+        self.take(key).is_some()
+    }
+}
+
+impl<T, I> fmt::Debug for AtomicPool<T, I>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        f.debug_struct("AtomicPool")
+            .field("capacity", &self.slots.len())
+            .field("len", &self.count.load(Ordering::Acquire))
+            .finish_non_exhaustive()
+    }
+}
+
+// ===-===-===-===-===-===-===-===-===-===-===-===-=== //
+
+/// A shared borrow of an item in an [`AtomicPool`], holding a read lock on its slot until dropped.
+///
+/// Created by [`AtomicPool::get`].
+///
+/// [`AtomicPool`]: struct.AtomicPool.html
+/// [`AtomicPool::get`]: struct.AtomicPool.html#method.get
+pub struct AtomicItemRef<'a, T>
+{
+    guard: RwLockReadGuard<'a, Option<T>>,
+}
+
+impl<'a, T> Deref for AtomicItemRef<'a, T>
+{
+    type Target = T;
+
+    fn deref(&self) -> &T { self.guard.as_ref().expect("checked occupied when locked") }
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for AtomicItemRef<'a, T>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { fmt::Debug::fmt(&**self, f) }
+}
+
+// ===-===-===-===-===-===-===-===-===-===-===-===-=== //
+
+/// A mutable borrow of an item in an [`AtomicPool`], holding a write lock on its slot until dropped.
+///
+/// Created by [`AtomicPool::get_mut`].
+///
+/// [`AtomicPool`]: struct.AtomicPool.html
+/// [`AtomicPool::get_mut`]: struct.AtomicPool.html#method.get_mut
+pub struct AtomicItemMut<'a, T>
+{
+    guard: RwLockWriteGuard<'a, Option<T>>,
+}
+
+impl<'a, T> Deref for AtomicItemMut<'a, T>
+{
+    type Target = T;
+
+    fn deref(&self) -> &T { self.guard.as_ref().expect("checked occupied when locked") }
+}
+
+impl<'a, T> DerefMut for AtomicItemMut<'a, T>
+{
+    fn deref_mut(&mut self) -> &mut T { self.guard.as_mut().expect("checked occupied when locked") }
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for AtomicItemMut<'a, T>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { fmt::Debug::fmt(&**self, f) }
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    mod atomic_pool
+    {
+        use std::sync::atomic::{ AtomicBool, AtomicU64, Ordering };
+        use std::thread;
+
+        use crate::pool::PoolKey;
+        use super::super::AtomicPool;
+
+        #[test]
+        fn behaves_like_a_pool()
+        {
+            let pool = AtomicPool::new(2);
+
+            let key1 = pool.insert(1);
+            let key2 = pool.insert(2);
+            assert!(pool.try_insert(3).is_err(), "Expected a full pool to reject the insert.");
+
+            assert_eq!(pool.take(&key2), Some(2));
+            assert!(!pool.contains_key(&key2), "Expected taken key to be invalid.");
+            assert!(!pool.delete(&key2), "Expected a second removal to report nothing deleted.");
+
+            let key3 = pool.insert(3);
+            assert_eq!(key3.index, key2.index, "Expected the freed slot to be reused.");
+            assert!(pool.get(&key2).is_none(), "Expected the stale key to miss the reused slot.");
+            assert_eq!(*pool.get(&key3).unwrap(), 3);
+            assert_eq!(*pool.get(&key1).unwrap(), 1);
+            assert_eq!(pool.len(), 2);
+        }
+
+        #[test]
+        #[should_panic(expected = "spool: AtomicPool is at capacity (1)")]
+        fn panics_when_full()
+        {
+            let pool = AtomicPool::new(1);
+            pool.insert(1);
+            pool.insert_with_key(|_| 2);
+        }
+
+        #[test]
+        fn rejects_out_of_range_keys()
+        {
+            let pool: AtomicPool<i32> = AtomicPool::new(2);
+            let key = PoolKey::from_u64(1 << 32 | 10).unwrap();
+
+            assert!(pool.get(&key).is_none());
+            assert!(!pool.contains_key(&key));
+            assert!(!pool.delete(&key));
+        }
+
+        #[test]
+        fn rejects_keys_matching_a_vacant_slot()
+        {
+            let pool = AtomicPool::new(2);
+            let key = pool.insert(1);
+            assert_eq!(pool.take(&key), Some(1));
+
+            // Matches the generation the slot was vacated at.
+            let vacant = PoolKey::from_u64(2 << 32 | key.index as u64).unwrap();
+            assert_eq!(pool.take(&vacant), None);
+            assert!(!pool.delete(&vacant));
+            assert!(!pool.contains_key(&vacant));
+            assert_eq!(pool.len(), 0, "Expected the count to be untouched.");
+
+            let key1 = pool.insert(1);
+            let key2 = pool.insert(2);
+            assert_ne!(key1.index, key2.index, "Expected the slot not to be freed twice.");
+            assert_eq!(*pool.get(&key1).unwrap(), 1);
+            assert_eq!(*pool.get(&key2).unwrap(), 2);
+        }
+
+        #[test]
+        fn readers_never_see_a_reused_slot_through_a_stale_key()
+        {
+            const WRITES: usize = 20_000;
+
+            let pool = AtomicPool::new(2);
+            let first = pool.insert_with_key(|key| [key.as_u64(); 4]);
+            let published = AtomicU64::new(first.as_u64());
+            let done = AtomicBool::new(false);
+
+            thread::scope(|scope|
+            {
+                for _ in 0..4
+                {
+                    scope.spawn(||
+                    {
+                        let mut hits = 0;
+                        while !done.load(Ordering::Acquire) || hits == 0
+                        {
+                            let raw = published.load(Ordering::Acquire);
+                            let key = PoolKey::from_u64(raw).unwrap();
+
+                            if let Some(item) = pool.get(&key)
+                            {
+                                assert!(item.iter().all(|&value| value == raw), "Expected the item stored under the key, got {:?} for {:x}.", *item, raw);
+                                hits += 1;
+                            }
+                        }
+                    });
+                }
+
+                scope.spawn(||
+                {
+                    for _ in 0..WRITES
+                    {
+                        let old = PoolKey::from_u64(published.load(Ordering::Acquire)).unwrap();
+                        let new = pool.insert_with_key(|key| [key.as_u64(); 4]);
+
+                        published.store(new.as_u64(), Ordering::Release);
+                        assert!(pool.delete(&old), "Expected the previous key to still be live.");
+                    }
+                    done.store(true, Ordering::Release);
+                });
+            });
+
+            assert_eq!(pool.len(), 1, "Expected only the last insert to remain.");
+        }
+    }
+}
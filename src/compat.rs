@@ -0,0 +1,3 @@
+//! Shims mirroring the APIs of other crates, to ease migrating to spool a little at a time.
+
+pub mod slab;
@@ -0,0 +1,263 @@
+//! A stand-in for the [`slab`] crate's `Slab`, keyed by plain `usize` indices.
+//!
+//! [`slab`]: https://docs.rs/slab
+
+use core::ops::{ Index, IndexMut };
+
+use crate::pool::{ Pool, PoolKey, ObjectPool };
+
+/// A growable pool keyed by plain `usize` indices, ignoring generations, as the [`slab`] crate's `Slab` is.
+///
+/// Like `Slab`, a key is just the index of its item's slot, so once the item is removed and the slot reused,
+/// the old key silently resolves to the new item. Meant as a stepping stone: [`key`] and [`into_pool`] hand over
+/// generation-checked [`PoolKey`]s and the underlying [`ObjectPool`], for code ready to be rid of that hazard.
+///
+/// [`slab`]: https://docs.rs/slab
+/// [`key`]: #method.key
+/// [`into_pool`]: #method.into_pool
+/// [`PoolKey`]: ../../struct.PoolKey.html
+/// [`ObjectPool`]: ../../struct.ObjectPool.html
+///
+/// # Examples
+///
+/// ```
+/// use spool::compat::slab::Slab;
+///
+/// let mut slab = Slab::new();
+///
+/// let hello = slab.insert("hello");
+/// let world = slab.insert("world");
+///
+/// assert_eq!(slab[hello], "hello");
+/// assert_eq!(slab[world], "world");
+///
+/// slab[world] = "earth";
+/// assert_eq!(slab.remove(world), "earth");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Slab<T>
+{
+    pool: ObjectPool<T>,
+}
+
+impl<T> Slab<T>
+{
+    /// Returns a new, empty slab, which allocates nothing until its first insert.
+    pub fn new() -> Self
+    {
+        return Self::with_capacity(0);
+    }
+
+    /// Returns a new, empty slab. Preallocated with specified capacity.
+    pub fn with_capacity(capacity: usize) -> Self
+    {
+        return Self { pool: ObjectPool::new_growable(capacity) };
+    }
+
+    /// Consumes the slab, returning the underlying pool. Keys obtained through [`key`] stay valid against it.
+    ///
+    /// [`key`]: #method.key
+    pub fn into_pool(self) -> ObjectPool<T>
+    {
+        return self.pool;
+    }
+
+    /// Returns the underlying pool.
+    pub fn as_pool(&self) -> &ObjectPool<T>
+    {
+        return &self.pool;
+    }
+
+    // ====-====-====-====-====-==== //
+
+    pub fn capacity(&self) -> usize { self.pool.capacity() }
+
+    pub fn len(&self) -> usize { self.pool.len() }
+
+    pub fn is_empty(&self) -> bool { self.pool.is_empty() }
+
+    // ====-====-====-====-====-==== //
+
+    /// Inserts the item, returning its key. The slab grows as needed.
+    pub fn insert(&mut self, value: T) -> usize
+    {
+        return self.pool.insert(value).index;
+    }
+
+    /// Returns a reference to the item at `key`, or `None` if there isn't one.
+    pub fn get(&self, key: usize) -> Option<&T>
+    {
+        return self.pool.get_by_index(key);
+    }
+
+    /// Returns a mutable reference to the item at `key`, or `None` if there isn't one.
+    pub fn get_mut(&mut self, key: usize) -> Option<&mut T>
+    {
+        let key = self.pool.key_for_index(key)?;
+        return self.pool.get_mut(&key);
+    }
+
+    /// Returns `true` if there is an item at `key`.
+    pub fn contains(&self, key: usize) -> bool
+    {
+        return self.get(key).is_some();
+    }
+
+    /// Removes the item at `key` and returns it, or `None` if there isn't one.
+    pub fn try_remove(&mut self, key: usize) -> Option<T>
+    {
+        let key = self.pool.key_for_index(key)?;
+        return self.pool.take(&key);
+    }
+
+    /// Removes the item at `key` and returns it.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if there is no item at `key`.
+    #[track_caller]
+    pub fn remove(&mut self, key: usize) -> T
+    {
+        return self.try_remove(key).expect("invalid key");
+    }
+
+    /// Removes all items. Keys may be handed out again by later inserts.
+    pub fn clear(&mut self)
+    {
+        self.pool.clear();
+    }
+
+    /// Returns the generation-checked [`PoolKey`] of the item at `key`, or `None` if there isn't one.
+    /// Unlike the `usize` key, it stops resolving once the item is removed, even if the slot is reused.
+    ///
+    /// [`PoolKey`]: ../../struct.PoolKey.html
+    pub fn key(&self, key: usize) -> Option<PoolKey>
+    {
+        return self.pool.key_for_index(key);
+    }
+
+    /// Returns an iterator over the items, along with their keys, in ascending key order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &'_ T)>
+    {
+        self.pool.iter_with_keys().map(|(key, value)| (key.index, value))
+    }
+}
+
+// Implemented by hand, as the pool's own default is fixed at no capacity, which would leave the slab unable to insert.
+impl<T> Default for Slab<T>
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl<T> Index<usize> for Slab<T>
+{
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, key: usize) -> &T
+    {
+        self.get(key).expect("invalid key")
+    }
+}
+
+impl<T> IndexMut<usize> for Slab<T>
+{
+    #[track_caller]
+    fn index_mut(&mut self, key: usize) -> &mut T
+    {
+        self.get_mut(key).expect("invalid key")
+    }
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    mod slab
+    {
+        use crate::pool::Pool;
+        use super::super::Slab;
+
+        #[test]
+        fn inserts_gets_and_removes_by_index()
+        {
+            let mut slab = Slab::new();
+            let hello = slab.insert("hello");
+            let world = slab.insert("world");
+
+            assert_eq!(slab.len(), 2);
+            assert_eq!(slab.get(hello), Some(&"hello"));
+            assert_eq!(slab[world], "world");
+            assert!(slab.contains(hello));
+
+            assert_eq!(slab.remove(hello), "hello");
+            assert!(!slab.contains(hello));
+            assert_eq!(slab.get(hello), None);
+            assert_eq!(slab.try_remove(hello), None, "Expected a second remove to find nothing.");
+            assert_eq!(slab.len(), 1);
+        }
+
+        #[test]
+        fn mutates_through_get_mut_and_index_mut()
+        {
+            let mut slab = Slab::with_capacity(4);
+            let key = slab.insert(1);
+
+            *slab.get_mut(key).unwrap() += 1;
+            slab[key] *= 10;
+
+            assert_eq!(slab[key], 20);
+            assert_eq!(slab.get_mut(key + 1), None);
+        }
+
+        #[test]
+        fn reuses_removed_keys_like_slab()
+        {
+            let mut slab = Slab::new();
+            let first = slab.insert(1);
+            slab.insert(2);
+
+            slab.remove(first);
+            let reused = slab.insert(3);
+
+            assert_eq!(reused, first, "Expected the most recently removed key to be handed out next.");
+            assert_eq!(slab[first], 3, "Expected a plain key to resolve to whatever now occupies its slot.");
+        }
+
+        #[test]
+        fn grows_past_initial_capacity()
+        {
+            let mut slab = Slab::with_capacity(1);
+            let keys: Vec<_> = (0..10).map(|i| slab.insert(i)).collect();
+
+            assert_eq!(slab.len(), 10);
+            assert!(keys.iter().enumerate().all(|(i, &key)| slab[key] == i));
+            assert_eq!(slab.iter().map(|(key, _)| key).collect::<Vec<_>>(), keys);
+        }
+
+        #[test]
+        fn hands_over_generation_checked_keys()
+        {
+            let mut slab = Slab::new();
+            let index = slab.insert("Checked");
+            let key = slab.key(index).unwrap();
+
+            slab.remove(index);
+            slab.insert("Impostor");
+            let pool = slab.into_pool();
+
+            assert!(pool.get(&key).is_none(), "Expected the pool key to go stale where the plain key wouldn't.");
+        }
+
+        #[test]
+        #[should_panic(expected = "invalid key")]
+        fn panics_removing_a_vacant_key()
+        {
+            let mut slab: Slab<i32> = Slab::new();
+            slab.remove(0);
+        }
+    }
+}
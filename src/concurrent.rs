@@ -0,0 +1,355 @@
+use core::fmt;
+use core::ops::{ Deref, DerefMut };
+use std::sync::{ PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard };
+
+use crate::error::PoolFull;
+use crate::index::PoolIndex;
+use crate::pool::{ Pool, PoolKey, ObjectPool };
+
+/// An [`ObjectPool`] shareable between threads, behind a single [`RwLock`].
+///
+/// The lock covers the whole pool: any number of threads may read at once, while inserting, deleting,
+/// or mutably borrowing an item waits for exclusive access to the pool.
+/// Guards returned by [`get`] and [`get_mut`] hold the lock until they're dropped, so keep them short-lived.
+///
+/// Keys are plain [`PoolKey`]s, valid from any thread.
+///
+/// A panic while holding the lock doesn't poison the pool; the next caller carries on with it as is.
+///
+/// For read-heavy use with a fixed capacity, [`AtomicPool`] locks per slot instead.
+///
+/// [`ObjectPool`]: struct.ObjectPool.html
+/// [`AtomicPool`]: struct.AtomicPool.html
+/// [`RwLock`]: https://doc.rust-lang.org/std/sync/struct.RwLock.html
+/// [`get`]: #method.get
+/// [`get_mut`]: #method.get_mut
+/// [`PoolKey`]: struct.PoolKey.html
+///
+/// # Examples
+///
+/// ```
+/// use spool::ConcurrentPool;
+///
+/// let pool = ConcurrentPool::new_growable(10);
+///
+/// let keys: Vec<_> = std::thread::scope(|scope|
+/// {
+///     let handles: Vec<_> = (0..4).map(|i| scope.spawn({ let pool = &pool; move || pool.insert(i) })).collect();
+///     handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+/// });
+///
+/// assert_eq!(pool.len(), 4);
+/// assert_eq!(*pool.get(&keys[2]).unwrap(), 2);
+/// ```
+pub struct ConcurrentPool<T, I = usize>
+{
+    pool: RwLock<ObjectPool<T, I>>,
+}
+
+impl<T, I> Default for ConcurrentPool<T, I>
+{
+    fn default() -> Self
+    {
+        Self { pool: RwLock::default() }
+    }
+}
+
+impl<T> ConcurrentPool<T>
+{
+    /// Returns a new, empty pool. Preallocated with specified capacity.
+    ///
+    /// See [`ObjectPool::new`].
+    ///
+    /// [`ObjectPool::new`]: struct.ObjectPool.html#method.new
+    pub fn new(capacity: usize) -> Self
+    {
+        Self::from_pool(ObjectPool::new(capacity))
+    }
+
+    /// Returns a new, empty pool which grows when full. Preallocated with specified capacity.
+    ///
+    /// See [`ObjectPool::new_growable`].
+    ///
+    /// [`ObjectPool::new_growable`]: struct.ObjectPool.html#method.new_growable
+    pub fn new_growable(capacity: usize) -> Self
+    {
+        Self::from_pool(ObjectPool::new_growable(capacity))
+    }
+}
+
+impl<T, I: PoolIndex> ConcurrentPool<T, I>
+{
+    /// Wraps an existing pool. Keys issued by it stay valid.
+    pub fn from_pool(pool: ObjectPool<T, I>) -> Self
+    {
+        Self { pool: RwLock::new(pool) }
+    }
+
+    /// Consumes the wrapper, returning the underlying pool.
+    pub fn into_inner(self) -> ObjectPool<T, I>
+    {
+        self.pool.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the pool for shared access, for anything not covered by the methods here.
+    pub fn read(&self) -> RwLockReadGuard<'_, ObjectPool<T, I>>
+    {
+        self.pool.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the pool for exclusive access, for anything not covered by the methods here.
+    pub fn write(&self) -> RwLockWriteGuard<'_, ObjectPool<T, I>>
+    {
+        self.pool.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the underlying pool mutably. No locking is needed, since the borrow is exclusive.
+    pub fn get_pool_mut(&mut self) -> &mut ObjectPool<T, I>
+    {
+        self.pool.get_mut().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // ====-====-====-====-====-==== //
+
+    pub fn capacity(&self) -> usize { self.read().capacity() }
+
+    pub fn len(&self) -> usize { self.read().len() }
+
+    pub fn is_empty(&self) -> bool { self.read().is_empty() }
+
+    // ====-====-====-====-====-==== //
+
+    /// See [`Pool::insert`].
+    ///
+    /// [`Pool::insert`]: trait.Pool.html#tymethod.insert
+    pub fn insert(&self, value: T) -> PoolKey<I>
+    {
+        self.write().insert(value)
+    }
+
+    /// See [`Pool::try_insert`].
+    ///
+    /// [`Pool::try_insert`]: trait.Pool.html#tymethod.try_insert
+    pub fn try_insert(&self, value: T) -> Result<PoolKey<I>, PoolFull<T>>
+    {
+        self.write().try_insert(value)
+    }
+
+    /// Returns a guard dereferencing to the item, holding a shared lock on the pool.
+    /// Returns `None` if the key is out of range, stale, or points at an empty entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::ConcurrentPool;
+    ///
+    /// let pool = ConcurrentPool::new(10);
+    /// let key = pool.insert("Howdy!");
+    ///
+    /// assert_eq!(*pool.get(&key).unwrap(), "Howdy!");
+    /// ```
+    pub fn get(&self, key: &PoolKey<I>) -> Option<ItemRef<'_, T, I>>
+    {
+        let guard = self.read();
+        if !guard.contains_key(key) { return None; }
+
+        return Some(ItemRef { guard, key: *key });
+    }
+
+    /// Returns a guard mutably dereferencing to the item, holding an exclusive lock on the pool.
+    /// Returns `None` if the key is out of range, stale, or points at an empty entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::ConcurrentPool;
+    ///
+    /// let pool = ConcurrentPool::new(10);
+    /// let key = pool.insert(1);
+    ///
+    /// *pool.get_mut(&key).unwrap() += 1;
+    /// assert_eq!(*pool.get(&key).unwrap(), 2);
+    /// ```
+    pub fn get_mut(&self, key: &PoolKey<I>) -> Option<ItemMut<'_, T, I>>
+    {
+        let guard = self.write();
+        if !guard.contains_key(key) { return None; }
+
+        return Some(ItemMut { guard, key: *key });
+    }
+
+    pub fn contains_key(&self, key: &PoolKey<I>) -> bool
+    {
+        self.read().contains_key(key)
+    }
+
+    pub fn take(&self, key: &PoolKey<I>) -> Option<T>
+    {
+        self.write().take(key)
+    }
+
+    pub fn delete(&self, key: &PoolKey<I>) -> bool
+    {
+        self.write().delete(key)
+    }
+
+    pub fn clear(&self)
+    {
+        self.write().clear()
+    }
+}
+
+impl<T, I: PoolIndex> From<ObjectPool<T, I>> for ConcurrentPool<T, I>
+{
+    fn from(pool: ObjectPool<T, I>) -> Self { Self::from_pool(pool) }
+}
+
+impl<T: fmt::Debug, I: PoolIndex + fmt::Debug> fmt::Debug for ConcurrentPool<T, I>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        f.debug_tuple("ConcurrentPool").field(&*self.read()).finish()
+    }
+}
+
+// ===-===-===-===-===-===-===-===-===-===-===-===-=== //
+
+/// A shared borrow of an item in a [`ConcurrentPool`], holding a read lock on the pool until dropped.
+///
+/// Created by [`ConcurrentPool::get`].
+///
+/// [`ConcurrentPool`]: struct.ConcurrentPool.html
+/// [`ConcurrentPool::get`]: struct.ConcurrentPool.html#method.get
+pub struct ItemRef<'a, T, I: PoolIndex = usize>
+{
+    guard: RwLockReadGuard<'a, ObjectPool<T, I>>,
+    key: PoolKey<I>,
+}
+
+impl<'a, T, I: PoolIndex> ItemRef<'a, T, I>
+{
+    /// Returns the key of the item.
+    pub fn key(&self) -> PoolKey<I> { self.key }
+}
+
+impl<'a, T, I: PoolIndex> Deref for ItemRef<'a, T, I>
+{
+    type Target = T;
+
+    fn deref(&self) -> &T { &self.guard[self.key] }
+}
+
+impl<'a, T: fmt::Debug, I: PoolIndex> fmt::Debug for ItemRef<'a, T, I>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { fmt::Debug::fmt(&**self, f) }
+}
+
+// ===-===-===-===-===-===-===-===-===-===-===-===-=== //
+
+/// A mutable borrow of an item in a [`ConcurrentPool`], holding a write lock on the pool until dropped.
+///
+/// Created by [`ConcurrentPool::get_mut`].
+///
+/// [`ConcurrentPool`]: struct.ConcurrentPool.html
+/// [`ConcurrentPool::get_mut`]: struct.ConcurrentPool.html#method.get_mut
+pub struct ItemMut<'a, T, I: PoolIndex = usize>
+{
+    guard: RwLockWriteGuard<'a, ObjectPool<T, I>>,
+    key: PoolKey<I>,
+}
+
+impl<'a, T, I: PoolIndex> ItemMut<'a, T, I>
+{
+    /// Returns the key of the item.
+    pub fn key(&self) -> PoolKey<I> { self.key }
+}
+
+impl<'a, T, I: PoolIndex> Deref for ItemMut<'a, T, I>
+{
+    type Target = T;
+
+    fn deref(&self) -> &T { &self.guard[self.key] }
+}
+
+impl<'a, T, I: PoolIndex> DerefMut for ItemMut<'a, T, I>
+{
+    fn deref_mut(&mut self) -> &mut T { &mut self.guard[self.key] }
+}
+
+impl<'a, T: fmt::Debug, I: PoolIndex> fmt::Debug for ItemMut<'a, T, I>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { fmt::Debug::fmt(&**self, f) }
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    mod concurrent_pool
+    {
+        use std::thread;
+
+        use crate::pool::Pool;
+        use super::super::ConcurrentPool;
+
+        #[test]
+        fn inserts_from_many_threads_and_reads_back()
+        {
+            let pool = ConcurrentPool::new_growable(4);
+
+            let keys: Vec<_> = thread::scope(|scope|
+            {
+                let handles: Vec<_> = (0..8).map(|thread|
+                {
+                    let pool = &pool;
+                    scope.spawn(move || (0..100).map(|i| (pool.insert(thread * 100 + i), thread * 100 + i)).collect::<Vec<_>>())
+                }).collect();
+
+                handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect()
+            });
+
+            assert_eq!(pool.len(), 800, "Expected every insert to land.");
+
+            thread::scope(|scope|
+            {
+                for chunk in keys.chunks(100)
+                {
+                    let pool = &pool;
+                    scope.spawn(move ||
+                    {
+                        for (key, value) in chunk
+                        {
+                            assert_eq!(pool.get(key).map(|item| *item), Some(*value), "Expected each key to read back its own item.");
+                        }
+                    });
+                }
+            });
+        }
+
+        #[test]
+        fn deletes_and_mutates_through_shared_reference()
+        {
+            let pool = ConcurrentPool::new(10);
+            let key1 = pool.insert(1);
+            let key2 = pool.insert(2);
+
+            *pool.get_mut(&key1).unwrap() += 10;
+            assert_eq!(*pool.get(&key1).unwrap(), 11);
+
+            assert!(pool.delete(&key2));
+            assert!(!pool.delete(&key2), "Expected a second delete to report nothing deleted.");
+            assert!(pool.get(&key2).is_none(), "Expected a deleted key to be invalid.");
+            assert!(pool.get_mut(&key2).is_none(), "Expected a deleted key to be invalid.");
+
+            let pool = pool.into_inner();
+            assert_eq!(pool.len(), 1);
+        }
+
+        #[test]
+        fn is_send_and_sync()
+        {
+            fn assert_sync<S: Send + Sync>() {}
+            assert_sync::<ConcurrentPool<String>>();
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use core::num::NonZeroU32;
+
+use crate::generation::PoolGeneration;
+use crate::index::PoolIndex;
+use crate::pool::{ Pool, PoolKey, ObjectPool };
+
+/// A view into a single slot of an [`ObjectPool`], which is either occupied by the item of a key, or vacant.
+///
+/// Created by [`ObjectPool::entry`].
+///
+/// A key is vacant when it no longer corresponds to an item, i.e. it is stale or out of range.
+/// Inserting through a vacant entry never revives the old key: the item goes into whichever slot the pool hands out next,
+/// under a new [`PoolKey`], which is returned alongside the item.
+///
+/// [`ObjectPool`]: struct.ObjectPool.html
+/// [`ObjectPool::entry`]: struct.ObjectPool.html#method.entry
+/// [`PoolKey`]: struct.PoolKey.html
+#[derive(Debug)]
+pub enum Entry<'a, T, I: PoolIndex = usize, G: PoolGeneration = NonZeroU32>
+{
+    Occupied(OccupiedEntry<'a, T, I, G>),
+    Vacant(VacantEntry<'a, T, I, G>),
+}
+
+impl<'a, T, I: PoolIndex, G: PoolGeneration> Entry<'a, T, I, G>
+{
+    /// Returns the key of the item, along with a mutable reference to it. Inserts `value` first if the entry is vacant.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the entry is vacant and the pool is full, unless the pool is growable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// let key = pool.insert(1);
+    /// pool.delete(&key);
+    ///
+    /// let (new_key, item) = pool.entry(&key).or_insert(2);
+    /// *item += 1;
+    ///
+    /// assert_ne!(new_key, key);
+    /// assert_eq!(pool.get(&new_key), Some(&3));
+    /// ```
+    pub fn or_insert(self, value: T) -> (PoolKey<I, G>, &'a mut T)
+    {
+        self.or_insert_with(|| value)
+    }
+
+    /// Returns the key of the item, along with a mutable reference to it.
+    /// Inserts the result of `f` first if the entry is vacant.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the entry is vacant and the pool is full, unless the pool is growable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert(String::from("Existing"));
+    ///
+    /// let (same_key, item) = pool.entry(&key).or_insert_with(|| String::from("Never built"));
+    ///
+    /// assert_eq!(same_key, key);
+    /// assert_eq!(item, "Existing");
+    /// ```
+    pub fn or_insert_with<F: FnOnce() -> T>(self, f: F) -> (PoolKey<I, G>, &'a mut T)
+    {
+        match self
+        {
+            Entry::Occupied(entry) => (entry.key(), entry.into_mut()),
+            Entry::Vacant(entry)   => entry.insert(f()),
+        }
+    }
+
+    /// Returns the key of the item, along with a mutable reference to it.
+    /// Inserts `T::default()` first if the entry is vacant.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the entry is vacant and the pool is full, unless the pool is growable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool: ObjectPool<u32> = ObjectPool::new(10);
+    ///
+    /// let key = pool.insert(1);
+    /// pool.delete(&key);
+    ///
+    /// let (new_key, item) = pool.entry(&key).or_default();
+    ///
+    /// assert_eq!(*item, 0);
+    /// assert_ne!(new_key, key);
+    /// ```
+    pub fn or_default(self) -> (PoolKey<I, G>, &'a mut T) where T: Default
+    {
+        self.or_insert_with(T::default)
+    }
+
+    /// Calls `f` with the item if the entry is occupied, and returns the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert(1);
+    ///
+    /// pool.entry(&key).and_modify(|item| *item += 1).or_insert(100);
+    /// assert_eq!(pool[key], 2);
+    /// ```
+    pub fn and_modify<F: FnOnce(&mut T)>(mut self, f: F) -> Self
+    {
+        if let Entry::Occupied(ref mut entry) = self { f(entry.get_mut()); }
+        return self;
+    }
+}
+
+// ===-===-===-===-===-===-===-===-===-===-===-===-=== //
+
+/// A view into an occupied slot of an [`ObjectPool`]. Part of the [`Entry`] enum.
+///
+/// [`ObjectPool`]: struct.ObjectPool.html
+/// [`Entry`]: enum.Entry.html
+#[derive(Debug)]
+pub struct OccupiedEntry<'a, T, I: PoolIndex = usize, G: PoolGeneration = NonZeroU32>
+{
+    pub(crate) pool: &'a mut ObjectPool<T, I, G>,
+    pub(crate) key: PoolKey<I, G>,
+}
+
+impl<'a, T, I: PoolIndex, G: PoolGeneration> OccupiedEntry<'a, T, I, G>
+{
+    /// Returns the key of the item.
+    pub fn key(&self) -> PoolKey<I, G> { self.key }
+
+    /// Returns a reference to the item.
+    pub fn get(&self) -> &T
+    {
+        self.pool.get(&self.key).expect("occupied entry holds an item")
+    }
+
+    /// Returns a mutable reference to the item.
+    pub fn get_mut(&mut self) -> &mut T
+    {
+        self.pool.get_mut(&self.key).expect("occupied entry holds an item")
+    }
+
+    /// Returns a mutable reference to the item, bound to the lifetime of the pool borrow.
+    pub fn into_mut(self) -> &'a mut T
+    {
+        self.pool.get_mut(&self.key).expect("occupied entry holds an item")
+    }
+
+    /// Replaces the item, returning the previous one. The key remains valid.
+    pub fn insert(&mut self, value: T) -> T
+    {
+        core::mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes the item from the pool, returning it. The key is invalidated.
+    pub fn remove(self) -> T
+    {
+        self.pool.take(&self.key).expect("occupied entry holds an item")
+    }
+}
+
+/// A view into a vacant slot of an [`ObjectPool`]. Part of the [`Entry`] enum.
+///
+/// [`ObjectPool`]: struct.ObjectPool.html
+/// [`Entry`]: enum.Entry.html
+#[derive(Debug)]
+pub struct VacantEntry<'a, T, I: PoolIndex = usize, G: PoolGeneration = NonZeroU32>
+{
+    pub(crate) pool: &'a mut ObjectPool<T, I, G>,
+}
+
+impl<'a, T, I: PoolIndex, G: PoolGeneration> VacantEntry<'a, T, I, G>
+{
+    /// Inserts the item into the pool, returning its new key along with a mutable reference to it.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the pool is full, unless the pool is growable.
+    pub fn insert(self, value: T) -> (PoolKey<I, G>, &'a mut T)
+    {
+        let pool = self.pool;
+        let key = pool.insert(value);
+
+        return (key, pool.get_mut(&key).expect("item was just inserted"));
+    }
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    use crate::pool::{
+        Pool,
+        ObjectPool,
+    };
+
+    mod entry
+    {
+        use super::{
+            Pool,
+            ObjectPool,
+        };
+        use super::super::Entry;
+
+        #[test]
+        fn occupied_for_valid_key()
+        {
+            let mut pool = ObjectPool::new(10);
+            let key = pool.insert(1);
+
+            match pool.entry(&key)
+            {
+                Entry::Occupied(entry) => assert_eq!(entry.key(), key),
+                Entry::Vacant(_)       => panic!("Expected a valid key to be occupied."),
+            }
+
+            let (same_key, item) = pool.entry(&key).and_modify(|item| *item += 1).or_insert(100);
+            assert_eq!(same_key, key, "Expected the key to be unchanged.");
+            assert_eq!(*item, 2, "Expected the existing item, modified.");
+            assert_eq!(pool.len(), 1);
+        }
+
+        #[test]
+        fn vacant_inserts_into_a_fresh_slot()
+        {
+            let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+            let key = pool.insert(1);
+            let other_pool_key = {
+                let mut other = ObjectPool::new(10);
+                other.insert(0);
+                other.insert(0)
+            };
+
+            let (new_key, item) = pool.entry(&other_pool_key).or_insert_with(|| 2);
+            assert_eq!(*item, 2);
+            assert_eq!(pool.get(&new_key), Some(&2), "Expected the returned key to resolve.");
+            assert_eq!(pool.get(&key), Some(&1), "Expected existing items to be untouched.");
+            assert_eq!(pool.len(), 2);
+        }
+
+        #[test]
+        fn stale_key_is_vacant_and_stays_invalid()
+        {
+            let mut pool = ObjectPool::new(10);
+            let key = pool.insert(1);
+            pool.delete(&key);
+
+            let mut modified = false;
+            let (new_key, _) = pool.entry(&key).and_modify(|_| modified = true).or_insert(2);
+
+            assert!(!modified, "Expected and_modify to skip a vacant entry.");
+            assert_eq!(new_key.index, key.index, "Expected the freed slot to be reused.");
+            assert_ne!(new_key, key, "Expected a new generation.");
+            assert!(pool.get(&key).is_none(), "Expected the stale key to remain invalid.");
+            assert_eq!(pool.get(&new_key), Some(&2));
+        }
+
+        #[test]
+        fn occupied_entry_replaces_and_removes()
+        {
+            let mut pool = ObjectPool::new(10);
+            let key = pool.insert(1);
+
+            if let Entry::Occupied(mut entry) = pool.entry(&key)
+            {
+                assert_eq!(entry.insert(2), 1);
+                assert_eq!(*entry.get(), 2);
+                assert_eq!(entry.remove(), 2);
+            }
+
+            assert!(pool.is_empty());
+            assert!(pool.get(&key).is_none());
+        }
+    }
+}
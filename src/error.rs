@@ -0,0 +1,387 @@
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::error::Error;
+
+/// The errors which may occur when operating on a [`Pool`].
+///
+/// Returned by the fallible `try_*` methods, as an alternative to panicking.
+///
+/// [`Pool`]: trait.Pool.html
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PoolError
+{
+    /// The pool is at capacity, no more items can be inserted.
+    Full,
+    /// The requested capacity exceeds the range of the pool's index type.
+    CapacityOverflow,
+    /// The allocator could not provide the memory for the requested capacity.
+    AllocationFailed,
+}
+
+impl fmt::Display for PoolError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            PoolError::Full             => write!(f, "pool is at capacity"),
+            PoolError::CapacityOverflow => write!(f, "capacity exceeds the range of the pool's index type"),
+            PoolError::AllocationFailed => write!(f, "memory allocation failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for PoolError {}
+
+// ===-===-===-===-===-===-===-===-===-===-===-===-=== //
+
+/// The error returned by [`try_insert`] when the pool is at capacity.
+///
+/// Hands back the value that could not be inserted, so it isn't lost.
+/// Converts into [`PoolError::Full`] for use with `?`.
+///
+/// [`try_insert`]: trait.Pool.html#tymethod.try_insert
+/// [`PoolError::Full`]: enum.PoolError.html#variant.Full
+///
+/// ```rust
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use spool::{ ObjectPool, Pool, PoolError };
+///
+/// fn insert_both(pool: &mut ObjectPool<i32>) -> Result<(), PoolError>
+/// {
+///     pool.try_insert(1)?;
+///     pool.try_insert(2)?;
+///     Ok(())
+/// }
+///
+/// let mut pool = ObjectPool::new(1);
+/// assert_eq!(insert_both(&mut pool), Err(PoolError::Full));
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PoolFull<T>(pub T);
+
+impl<T> PoolFull<T>
+{
+    /// Returns the value that could not be inserted.
+    pub fn into_inner(self) -> T
+    {
+        return self.0;
+    }
+}
+
+impl<T> fmt::Display for PoolFull<T>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        fmt::Display::fmt(&PoolError::Full, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Debug> Error for PoolFull<T> {}
+
+impl<T> From<PoolFull<T>> for PoolError
+{
+    fn from(_: PoolFull<T>) -> Self
+    {
+        return PoolError::Full;
+    }
+}
+
+// ===-===-===-===-===-===-===-===-===-===-===-===-=== //
+
+/// The error returned by [`ObjectPool::insert_at`] when the item can't be placed at the key.
+///
+/// Each variant hands back the value that could not be inserted, so it isn't lost.
+///
+/// [`ObjectPool::insert_at`]: struct.ObjectPool.html#method.insert_at
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum InsertAtError<T>
+{
+    /// The key is null, or was issued by another pool.
+    Invalid(T),
+    /// The key's slot holds an item.
+    Occupied(T),
+    /// The key's slot has already been through the key's generation.
+    Stale(T),
+    /// The key's slot is beyond the pool's reach, and the pool could not grow to it.
+    OutOfRange(T),
+}
+
+impl<T> InsertAtError<T>
+{
+    /// Returns the value that could not be inserted.
+    pub fn into_inner(self) -> T
+    {
+        match self
+        {
+            InsertAtError::Invalid(value)    => value,
+            InsertAtError::Occupied(value)   => value,
+            InsertAtError::Stale(value)      => value,
+            InsertAtError::OutOfRange(value) => value,
+        }
+    }
+}
+
+impl<T> fmt::Display for InsertAtError<T>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            InsertAtError::Invalid(_)    => write!(f, "key is null or belongs to another pool"),
+            InsertAtError::Occupied(_)   => write!(f, "slot is occupied"),
+            InsertAtError::Stale(_)      => write!(f, "slot has already been through the key's generation"),
+            InsertAtError::OutOfRange(_) => write!(f, "slot is beyond the pool's reach"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Debug> Error for InsertAtError<T> {}
+
+// ===-===-===-===-===-===-===-===-===-===-===-===-=== //
+
+/// The reason [`ObjectPool::try_take`] or [`ObjectPool::try_delete`] removed nothing.
+///
+/// [`ObjectPool::try_take`]: struct.ObjectPool.html#method.try_take
+/// [`ObjectPool::try_delete`]: struct.ObjectPool.html#method.try_delete
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RemoveError
+{
+    /// The key's index is beyond the pool's capacity.
+    OutOfRange,
+    /// The key's slot has moved on to a later generation, i.e. it has been reused since the key's item was removed.
+    StaleGeneration,
+    /// The key's item has already been removed, and its slot not reused since.
+    AlreadyEmpty,
+    /// The key was issued by another pool. Only occurs with the `pool-id` feature.
+    ForeignKey,
+}
+
+impl fmt::Display for RemoveError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            RemoveError::OutOfRange      => write!(f, "key is out of range"),
+            RemoveError::StaleGeneration => write!(f, "key is stale, its slot has been reused"),
+            RemoveError::AlreadyEmpty    => write!(f, "key's item has already been removed"),
+            RemoveError::ForeignKey      => write!(f, "key belongs to another pool"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for RemoveError {}
+
+// ===-===-===-===-===-===-===-===-===-===-===-===-=== //
+
+/// The reason [`ObjectPool::try_get`] or [`ObjectPool::try_get_mut`] found nothing.
+///
+/// [`ObjectPool::try_get`]: struct.ObjectPool.html#method.try_get
+/// [`ObjectPool::try_get_mut`]: struct.ObjectPool.html#method.try_get_mut
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AccessError
+{
+    /// The key's index is beyond the pool's capacity.
+    OutOfRange,
+    /// The key's slot has moved on to a later generation, i.e. it has been reused since the key's item was removed.
+    StaleGeneration,
+    /// The key's item has been removed, and its slot not reused since.
+    Empty,
+    /// The key was issued by another pool. Only occurs with the `pool-id` feature.
+    ForeignKey,
+}
+
+impl fmt::Display for AccessError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            AccessError::OutOfRange      => write!(f, "key is out of range"),
+            AccessError::StaleGeneration => write!(f, "key is stale, its slot has been reused"),
+            AccessError::Empty           => write!(f, "key's item has been removed"),
+            AccessError::ForeignKey      => write!(f, "key belongs to another pool"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for AccessError {}
+
+// ===-===-===-===-===-===-===-===-===-===-===-===-=== //
+
+/// The first broken invariant found by [`ObjectPool::validate`].
+///
+/// [`ObjectPool::validate`]: struct.ObjectPool.html#method.validate
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Corruption
+{
+    /// The first never-occupied slot is beyond the pool's capacity.
+    NextOutOfRange { next: usize, capacity: usize },
+    /// The slot at `index` is occupied, though at or beyond the first never-occupied slot.
+    OccupiedBeyondNext { index: usize },
+    /// The slot at `index` is occupied at generation 0, which no key can carry.
+    ZeroGeneration { index: usize },
+    /// The occupancy bitmap disagrees with the slot at `index`, or marks an `index` beyond the pool's capacity.
+    OccupancyMismatch { index: usize },
+    /// The item count disagrees with the number of occupied slots.
+    CountMismatch { count: usize, occupied: usize },
+    /// The free list links to `index`, at or beyond the first never-occupied slot.
+    FreeIndexOutOfRange { index: usize },
+    /// The free list links to the occupied slot at `index`.
+    FreeSlotOccupied { index: usize },
+    /// The free list links to the retired slot at `index`, which must never be reused.
+    FreeSlotRetired { index: usize },
+    /// The free list links to `index` more than once.
+    DuplicateFreeIndex { index: usize },
+    /// The free list's recorded length or tail disagrees with the slots actually linked.
+    FreeListMismatch { len: usize, linked: usize },
+    /// The generation slots added by growing start at is exhausted, so they would be retired before their first use.
+    RetiredBaseGeneration { generation: u64 },
+    /// The recorded count of retired slots disagrees with the slots actually retired, overall or at or beyond the next slot.
+    RetiredCountMismatch { count: usize, retired: usize },
+}
+
+impl fmt::Display for Corruption
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match *self
+        {
+            Corruption::NextOutOfRange { next, capacity }       => write!(f, "next slot {} exceeds capacity {}", next, capacity),
+            Corruption::OccupiedBeyondNext { index }            => write!(f, "slot {} is occupied beyond the next slot", index),
+            Corruption::ZeroGeneration { index }                => write!(f, "slot {} is occupied at generation 0", index),
+            Corruption::OccupancyMismatch { index }             => write!(f, "occupancy bitmap disagrees with slot {}", index),
+            Corruption::CountMismatch { count, occupied }       => write!(f, "count {} doesn't match the {} occupied slots", count, occupied),
+            Corruption::FreeIndexOutOfRange { index }           => write!(f, "free slot {} is beyond the next slot", index),
+            Corruption::FreeSlotOccupied { index }              => write!(f, "free slot {} is occupied", index),
+            Corruption::FreeSlotRetired { index }               => write!(f, "free slot {} is retired", index),
+            Corruption::DuplicateFreeIndex { index }            => write!(f, "free slot {} is listed more than once", index),
+            Corruption::FreeListMismatch { len, linked }        => write!(f, "free list records {} slots, but links {}", len, linked),
+            Corruption::RetiredBaseGeneration { generation }    => write!(f, "base generation {} is exhausted", generation),
+            Corruption::RetiredCountMismatch { count, retired } => write!(f, "retired count {} doesn't match the {} retired slots", count, retired),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for Corruption {}
+
+
+/// Panics for an insert into a full pool, naming the pool type, e.g. `ObjectPool`, and its capacity.
+/// Kept out of line, as the cold path of every panicking insert.
+#[cold]
+#[track_caller]
+pub(crate) fn panic_full(pool: &str, capacity: usize) -> !
+{
+    panic!("spool: {} is at capacity ({}); use try_insert for fallible insertion", pool, capacity);
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    mod pool_error
+    {
+        use super::super::PoolError;
+
+        #[test]
+        fn displays_a_readable_message()
+        {
+            assert_eq!(PoolError::Full.to_string(), "pool is at capacity");
+            assert_eq!(PoolError::CapacityOverflow.to_string(), "capacity exceeds the range of the pool's index type");
+            assert_eq!(PoolError::AllocationFailed.to_string(), "memory allocation failed");
+        }
+    }
+
+    mod remove_error
+    {
+        use super::super::RemoveError;
+
+        #[test]
+        fn displays_a_distinct_message_per_reason()
+        {
+            let messages = [RemoveError::OutOfRange, RemoveError::StaleGeneration, RemoveError::AlreadyEmpty, RemoveError::ForeignKey]
+                .map(|error| error.to_string());
+
+            assert_eq!(messages[1], "key is stale, its slot has been reused");
+            for (i, message) in messages.iter().enumerate()
+            {
+                assert!(!messages[..i].contains(message), "Expected {:?} to be distinct.", message);
+            }
+        }
+    }
+
+    mod access_error
+    {
+        use super::super::AccessError;
+
+        #[test]
+        fn displays_a_distinct_message_per_reason()
+        {
+            let messages = [AccessError::OutOfRange, AccessError::StaleGeneration, AccessError::Empty, AccessError::ForeignKey]
+                .map(|error| error.to_string());
+
+            assert_eq!(messages[2], "key's item has been removed");
+            for (i, message) in messages.iter().enumerate()
+            {
+                assert!(!messages[..i].contains(message), "Expected {:?} to be distinct.", message);
+            }
+        }
+    }
+
+    mod corruption
+    {
+        use super::super::Corruption;
+
+        #[test]
+        fn displays_the_offending_slot()
+        {
+            assert_eq!(Corruption::DuplicateFreeIndex { index: 3 }.to_string(), "free slot 3 is listed more than once");
+            assert_eq!(Corruption::CountMismatch { count: 2, occupied: 1 }.to_string(), "count 2 doesn't match the 1 occupied slots");
+        }
+    }
+
+    mod pool_full
+    {
+        use super::super::{
+            PoolFull,
+            PoolError,
+        };
+
+        #[test]
+        fn hands_back_the_rejected_value()
+        {
+            let full = PoolFull(String::from("Rejected!"));
+            assert_eq!(full.into_inner(), "Rejected!");
+        }
+
+        #[test]
+        fn converts_into_pool_error()
+        {
+            let error: PoolError = PoolFull(100).into();
+            assert_eq!(error, PoolError::Full);
+        }
+
+        #[test]
+        fn displays_the_same_message_as_pool_error()
+        {
+            assert_eq!(PoolFull(100).to_string(), PoolError::Full.to_string());
+        }
+    }
+}
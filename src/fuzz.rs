@@ -0,0 +1,119 @@
+use alloc::vec::Vec;
+
+use arbitrary::{ Arbitrary, Unstructured };
+
+use crate::index::PoolIndex;
+use crate::pool::{ Pool, PoolKey, ObjectPool, SlotReuse };
+
+/// The most slots an arbitrary pool is created with, so fuzz inputs stay quick to build.
+const MAX_ARBITRARY_CAPACITY: usize = 64;
+
+/// Keys carry a 32-bit index at most, see `PoolKey::as_u64`.
+impl<'a, I: PoolIndex> Arbitrary<'a> for PoolKey<I>
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self>
+    {
+        let index = u.int_in_range(0..=I::MAX_INDEX.min(u32::MAX as usize))?;
+        let index = I::from_usize(index).expect("index is within MAX_INDEX");
+
+        return Ok(PoolKey::new(index, u.arbitrary()?));
+    }
+}
+
+/// Pools are built by replaying arbitrary inserts and deletes through the pool's own methods,
+/// so every arbitrary pool is one the crate could have produced, and internally consistent by construction.
+impl<'a, T: Arbitrary<'a>, I: PoolIndex> Arbitrary<'a> for ObjectPool<T, I>
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self>
+    {
+        let capacity = u.int_in_range(0..=MAX_ARBITRARY_CAPACITY.min(Self::max_capacity()))?;
+        let reuse = if u.arbitrary()? { SlotReuse::Fifo } else { SlotReuse::Lifo };
+
+        let mut pool = Self::try_new(capacity).expect("capacity is within max_capacity").with_slot_reuse(reuse);
+        let mut keys = Vec::new();
+
+        while !u.is_empty()
+        {
+            if !keys.is_empty() && u.arbitrary()?
+            {
+                let key = keys.swap_remove(u.choose_index(keys.len())?);
+                pool.delete(&key);
+            }
+            else if let Ok(key) = pool.try_insert(u.arbitrary()?)
+            {
+                keys.push(key);
+            }
+            else
+            {
+                break;
+            }
+        }
+
+        return Ok(pool);
+    }
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    mod arbitrary_pool
+    {
+        use arbitrary::{ Arbitrary, Unstructured };
+
+        use crate::pool::{ Pool, PoolKey, ObjectPool };
+
+        /// Fills `bytes` from a simple xorshift generator, standing in for fuzzer input.
+        fn fill(bytes: &mut [u8], seed: u64)
+        {
+            let mut state = seed | 1;
+            for byte in bytes.iter_mut()
+            {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                *byte = state as u8;
+            }
+        }
+
+        #[test]
+        fn pools_are_internally_consistent()
+        {
+            let mut bytes = [0u8; 512];
+
+            for seed in 0..500
+            {
+                fill(&mut bytes, seed);
+                let pool = ObjectPool::<u16, u8>::arbitrary(&mut Unstructured::new(&bytes)).expect("Expected a pool from any input.");
+
+                for (key, item) in pool.iter_with_keys()
+                {
+                    assert_eq!(pool.get(&key), Some(item), "Expected every live key to resolve.");
+                }
+
+                let len = pool.len();
+                let (data, free, next, count) = pool.into_raw_parts();
+
+                assert_eq!(count, len);
+                assert_eq!(count, data.iter().filter(|(_, item)| item.is_some()).count(), "Expected count to match the occupied slots.");
+                assert!(data.iter().all(|&(generation, ref item)| item.is_none() || generation >= 1), "Expected occupied slots to have a generation.");
+                assert!(next <= data.len());
+                assert!(free.iter().all(|&index| index < next && data[index].1.is_none()), "Expected free slots to be vacant and in range.");
+            }
+        }
+
+        #[test]
+        fn keys_are_representable()
+        {
+            let mut bytes = [0u8; 64];
+
+            for seed in 0..100
+            {
+                fill(&mut bytes, seed);
+                let key = PoolKey::<u16>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+
+                assert_eq!(PoolKey::from_u64(key.as_u64()), Some(key), "Expected the key to round trip.");
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use core::fmt::Debug;
+use core::hash::Hash;
+use core::num::{ NonZeroU16, NonZeroU32, NonZeroU64 };
+use core::ops::{ Add, Sub };
+
+mod sealed
+{
+    pub trait Sealed {}
+}
+
+/// A non-zero unsigned integer type usable as the generation of a [`PoolKey`].
+///
+/// Each slot counts its occupants in its generation, and retires once the count reaches [`RETIRED`], never to be reused.
+/// Narrower generation types make for smaller keys, at the cost of slots retiring sooner under churn.
+/// Implemented for `NonZeroU16`, `NonZeroU32`, the default, and `NonZeroU64`.
+///
+/// [`PoolKey`]: struct.PoolKey.html
+/// [`RETIRED`]: #associatedconstant.RETIRED
+pub trait PoolGeneration: sealed::Sealed + Copy + Debug + Eq + Ord + Hash
+{
+    /// The underlying integer, as each slot keeps it. `0` until the slot is first occupied.
+    type Raw: Copy + Send + Sync + Debug + Default + Eq + Ord + Hash + From<u8> + Into<u64> + Add<Output = Self::Raw> + Sub<Output = Self::Raw>;
+
+    /// The generation of the null key, the largest representable, which no slot ever issues.
+    const NULL: Self;
+
+    /// The generation a slot retires at, once vacated: the largest representable but for the null key's.
+    const RETIRED: Self::Raw;
+
+    /// Converts from the underlying integer, returning `None` if zero.
+    fn new(raw: Self::Raw) -> Option<Self>;
+
+    /// Converts into the underlying integer.
+    fn get(self) -> Self::Raw;
+}
+
+macro_rules! impl_pool_generation
+{
+    ($($t:ty => $raw:ty),*) => {
+        $(
+            impl sealed::Sealed for $t {}
+
+            impl PoolGeneration for $t
+            {
+                type Raw = $raw;
+
+                const NULL: Self = match <$t>::new(<$raw>::MAX) { Some(generation) => generation, None => unreachable!() };
+                const RETIRED: $raw = <$raw>::MAX - 1;
+
+                fn new(raw: $raw) -> Option<Self> { <$t>::new(raw) }
+                fn get(self) -> $raw { <$t>::get(self) }
+            }
+        )*
+    };
+}
+
+impl_pool_generation!(NonZeroU16 => u16, NonZeroU32 => u32, NonZeroU64 => u64);
+
+
+#[cfg(test)]
+mod tests
+{
+    mod pool_generation
+    {
+        use core::num::{ NonZeroU16, NonZeroU32, NonZeroU64 };
+
+        use super::super::PoolGeneration;
+
+        #[test]
+        fn retires_one_short_of_the_null_generation()
+        {
+            assert_eq!(<NonZeroU16 as PoolGeneration>::RETIRED, u16::MAX - 1);
+            assert_eq!(<NonZeroU32 as PoolGeneration>::RETIRED, u32::MAX - 1);
+            assert_eq!(<NonZeroU64 as PoolGeneration>::NULL.get(), u64::MAX);
+        }
+
+        #[test]
+        fn new_rejects_zero()
+        {
+            assert_eq!(<NonZeroU16 as PoolGeneration>::new(0), None);
+            assert_eq!(<NonZeroU16 as PoolGeneration>::new(1).map(PoolGeneration::get), Some(1));
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use core::convert::TryFrom;
+use core::fmt::Debug;
+use core::hash::Hash;
+
+mod sealed
+{
+    pub trait Sealed {}
+}
+
+/// An unsigned integer type usable as the index of a [`PoolKey`].
+///
+/// Narrower index types make for smaller keys, at the cost of limiting the capacity of the pool.
+/// Implemented for `u8`, `u16`, `u32`, `u64`, and `usize`, the default.
+///
+/// [`PoolKey`]: struct.PoolKey.html
+pub trait PoolIndex: sealed::Sealed + Copy + Debug + Eq + Ord + Hash
+{
+    /// The largest index representable, as a `usize`.
+    const MAX_INDEX: usize;
+
+    /// Converts from a `usize`, returning `None` if out of range.
+    fn from_usize(index: usize) -> Option<Self>;
+
+    /// Converts into a `usize`, saturating at `usize::MAX` if out of range.
+    fn into_usize(self) -> usize;
+}
+
+macro_rules! impl_pool_index
+{
+    ($($t:ty),*) => {
+        $(
+            impl sealed::Sealed for $t {}
+
+            impl PoolIndex for $t
+            {
+                const MAX_INDEX: usize =
+                    if (<$t>::MAX as u128) < (usize::MAX as u128) { <$t>::MAX as usize }
+                    else                                           { usize::MAX };
+
+                fn from_usize(index: usize) -> Option<Self> { <$t>::try_from(index).ok() }
+                fn into_usize(self) -> usize { usize::try_from(self).unwrap_or(usize::MAX) }
+            }
+        )*
+    };
+}
+
+impl_pool_index!(u8, u16, u32, u64, usize);
+
+
+#[cfg(test)]
+mod tests
+{
+    mod pool_index
+    {
+        use super::super::PoolIndex;
+
+        #[test]
+        fn max_index_matches_type_range()
+        {
+            assert_eq!(<u8 as PoolIndex>::MAX_INDEX, u8::MAX as usize);
+            assert_eq!(<u16 as PoolIndex>::MAX_INDEX, u16::MAX as usize);
+            assert_eq!(<usize as PoolIndex>::MAX_INDEX, usize::MAX);
+        }
+
+        #[test]
+        fn from_usize_rejects_out_of_range()
+        {
+            assert_eq!(u8::from_usize(255), Some(255));
+            assert_eq!(u8::from_usize(256), None);
+            assert_eq!(u16::from_usize(70_000), None);
+        }
+
+        #[test]
+        fn into_usize_round_trips()
+        {
+            assert_eq!(200u8.into_usize(), 200);
+            assert_eq!(u16::MAX.into_usize(), u16::MAX as usize);
+        }
+    }
+}
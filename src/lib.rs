@@ -1,2 +1,113 @@
+//! Without the default `std` feature the crate is `no_std`, and only depends on `alloc`.
+//!
+//! The `safe` feature replaces the unchecked indexing on hot paths with bounds-checked indexing, for auditing, or running under miri.
+//! The API is the same either way.
+//!
+//! The nightly-only `allocator_api` feature lets an [`ObjectPool`] keep its slots in a custom allocator, see [`ObjectPool::new_in`].
+//! It can't be combined with `serde` or `rkyv`.
+//!
+//! [`ObjectPool`]: struct.ObjectPool.html
+//! [`ObjectPool::new_in`]: struct.ObjectPool.html#method.new_in
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+#![allow(clippy::needless_return)]
+
+#[cfg(all(feature = "allocator_api", any(feature = "serde", feature = "rkyv")))]
+compile_error!("spool: the `allocator_api` feature can't be combined with `serde` or `rkyv`, which only handle pools in the global allocator");
+
+extern crate alloc;
+
+#[cfg(feature = "rkyv")]
+mod archive;
+mod array_pool;
+#[cfg(feature = "sync")]
+mod atomic;
+pub mod compat;
+#[cfg(feature = "sync")]
+mod concurrent;
+mod entry;
+mod error;
+#[cfg(feature = "arbitrary")]
+mod fuzz;
+mod generation;
+mod index;
+mod occupancy;
 mod pool;
-pub use pool::{ Pool, PoolKey, ObjectPool };
+mod pool_id;
+mod secondary;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+mod typed;
+mod weak;
+pub use array_pool::ArrayPool;
+#[cfg(feature = "sync")]
+pub use atomic::{ AtomicPool, AtomicItemRef, AtomicItemMut };
+#[cfg(feature = "sync")]
+pub use concurrent::{ ConcurrentPool, ItemRef, ItemMut };
+pub use entry::{ Entry, OccupiedEntry, VacantEntry };
+pub use error::{ AccessError, Corruption, InsertAtError, PoolError, PoolFull, RemoveError };
+pub use generation::PoolGeneration;
+pub use index::PoolIndex;
+pub use pool::{ Pool, PoolKey, ObjectPool, PoolBuilder, PoolStats, SlotReuse, Iter, IterMut, IntoIter, Drain, CursorMut };
+#[cfg(feature = "rkyv")]
+pub use pool::{ ArchivedObjectPool, ArchivedPoolKey, ArchivedSlotReuse };
+pub use secondary::SecondaryMap;
+pub use typed::{ TypedPool, TypedPoolKey };
+pub use weak::WeakPoolKey;
+
+/// Returns the default [`Pool`] implementation, an [`ObjectPool`], preallocated with specified capacity.
+///
+/// [`Pool`]: trait.Pool.html
+/// [`ObjectPool`]: struct.ObjectPool.html
+///
+/// # Examples
+///
+/// ```
+/// use spool::{ create_default_pool, ObjectPool, Pool };
+///
+/// let pool: ObjectPool<i32> = create_default_pool(10);
+/// assert_eq!(pool.capacity(), 10);
+/// ```
+pub fn create_default_pool<T>(capacity: usize) -> ObjectPool<T>
+{
+    return ObjectPool::new(capacity);
+}
+
+
+// Only built by `cargo test --no-default-features`, to prove the pools are usable through `core` and `alloc` alone.
+#[cfg(all(test, not(feature = "std")))]
+mod no_std
+{
+    use alloc::vec::Vec;
+    use core::fmt::Write;
+
+    use super::{
+        ArrayPool,
+        ObjectPool,
+        Pool,
+        PoolError,
+        SecondaryMap,
+    };
+
+    #[test]
+    fn pools_work_without_std()
+    {
+        let mut pool = ObjectPool::new(2);
+        let mut names = SecondaryMap::new();
+        let mut array: ArrayPool<i32, 2> = ArrayPool::default();
+
+        let keys: Vec<_> = (0..2).map(|i| pool.insert(i)).collect();
+        names.insert(&keys[0], "Zero");
+        array.insert(0);
+
+        assert_eq!(pool.get(&keys[1]), Some(&1));
+        assert_eq!(names.get(&keys[0]), Some(&"Zero"));
+        assert_eq!(array.len(), 1);
+
+        let error: PoolError = pool.try_insert(2).unwrap_err().into();
+        let mut message = alloc::string::String::new();
+        write!(message, "{}", error).unwrap();
+        assert_eq!(message, "pool is at capacity");
+    }
+}
@@ -0,0 +1,268 @@
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+use serde::{ Serialize, Deserialize };
+
+const BITS: usize = u64::BITS as usize;
+
+/// One bit per slot of a pool, set while the slot is occupied.
+///
+/// Lets iteration skip runs of vacant slots a word at a time, rather than visiting every slot.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub(crate) struct Occupancy
+{
+    words: Vec<u64>,
+}
+
+impl Occupancy
+{
+    pub(crate) fn new() -> Self
+    {
+        Self { words: Vec::new() }
+    }
+
+    pub(crate) fn contains(&self, index: usize) -> bool
+    {
+        match self.words.get(index / BITS)
+        {
+            Some(word) => word & (1 << (index % BITS)) != 0,
+            None       => false,
+        }
+    }
+
+    /// Marks the slot at `index` occupied, growing the set as needed.
+    // Inlined, since these are called from generic pool code monomorphized in other crates.
+    #[inline]
+    pub(crate) fn insert(&mut self, index: usize)
+    {
+        let word = index / BITS;
+        if word >= self.words.len() { self.words.resize(word + 1, 0); }
+
+        self.words[word] |= 1 << (index % BITS);
+    }
+
+    /// Marks the slot at `index` vacant.
+    #[inline]
+    pub(crate) fn remove(&mut self, index: usize)
+    {
+        if let Some(word) = self.words.get_mut(index / BITS) { *word &= !(1 << (index % BITS)); }
+    }
+
+    pub(crate) fn clear(&mut self)
+    {
+        for word in self.words.iter_mut() { *word = 0; }
+    }
+
+    /// Drops the bits of every slot at or beyond `len`, releasing excess memory.
+    pub(crate) fn truncate(&mut self, len: usize)
+    {
+        self.words.truncate(len.div_ceil(BITS));
+        if let Some(last) = self.words.last_mut()
+        {
+            if !len.is_multiple_of(BITS) { *last &= (1 << (len % BITS)) - 1; }
+        }
+        self.words.shrink_to_fit();
+    }
+
+    /// Drops trailing words with no slot occupied, releasing excess memory. Inserts grow the set again as needed.
+    pub(crate) fn shrink_to_fit(&mut self)
+    {
+        let len = self.words.iter().rposition(|&word| word != 0).map_or(0, |i| i + 1);
+        self.words.truncate(len);
+        self.words.shrink_to_fit();
+    }
+
+    #[cfg(test)]
+    pub(crate) fn capacity(&self) -> usize
+    {
+        self.words.capacity()
+    }
+
+    /// Returns an iterator over the occupied slots, in ascending order.
+    pub(crate) fn ones(&self) -> Ones<'_>
+    {
+        Ones {
+            words: self.words.iter(),
+            front: 0,
+            front_base: 0,
+            back: 0,
+            back_base: 0,
+            next_base: 0,
+        }
+    }
+}
+
+impl core::iter::FromIterator<usize> for Occupancy
+{
+    fn from_iter<It: IntoIterator<Item = usize>>(iter: It) -> Self
+    {
+        let mut occupancy = Self::new();
+        for index in iter { occupancy.insert(index); }
+
+        return occupancy;
+    }
+}
+
+/// An iterator over the set bits of an [`Occupancy`], from either end.
+///
+/// Each end holds the remaining bits of the word it last took, and falls back on the other end's word once `words` runs out.
+#[derive(Clone, Debug)]
+pub(crate) struct Ones<'a>
+{
+    words: core::slice::Iter<'a, u64>,
+    front: u64,
+    front_base: usize,
+    back: u64,
+    back_base: usize,
+    /// The slot of the first bit of the next word from the front.
+    next_base: usize,
+}
+
+impl<'a> Iterator for Ones<'a>
+{
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize>
+    {
+        while self.front == 0
+        {
+            match self.words.next()
+            {
+                Some(&word) =>
+                {
+                    self.front = word;
+                    self.front_base = self.next_base;
+                    self.next_base += BITS;
+                }
+                None =>
+                {
+                    if self.back == 0 { return None; }
+
+                    let bit = self.back.trailing_zeros() as usize;
+                    self.back &= self.back - 1;
+                    return Some(self.back_base + bit);
+                }
+            }
+        }
+
+        let bit = self.front.trailing_zeros() as usize;
+        self.front &= self.front - 1;
+
+        return Some(self.front_base + bit);
+    }
+}
+
+impl<'a> DoubleEndedIterator for Ones<'a>
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<usize>
+    {
+        while self.back == 0
+        {
+            let base = self.next_base + self.words.len().saturating_sub(1) * BITS;
+            match self.words.next_back()
+            {
+                Some(&word) =>
+                {
+                    self.back = word;
+                    self.back_base = base;
+                }
+                None =>
+                {
+                    if self.front == 0 { return None; }
+
+                    let bit = highest_bit(self.front);
+                    self.front &= !(1 << bit);
+                    return Some(self.front_base + bit);
+                }
+            }
+        }
+
+        let bit = highest_bit(self.back);
+        self.back &= !(1 << bit);
+
+        return Some(self.back_base + bit);
+    }
+}
+
+fn highest_bit(word: u64) -> usize
+{
+    return BITS - 1 - word.leading_zeros() as usize;
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    mod occupancy
+    {
+        use super::super::Occupancy;
+
+        #[test]
+        fn tracks_inserts_and_removes()
+        {
+            let mut occupancy = Occupancy::new();
+            occupancy.insert(3);
+            occupancy.insert(64);
+            occupancy.insert(200);
+            occupancy.remove(64);
+
+            assert!(occupancy.contains(3));
+            assert!(!occupancy.contains(64));
+            assert!(!occupancy.contains(1000), "Expected out of range slots to be vacant.");
+            assert!(occupancy.ones().eq(vec![3, 200]));
+        }
+
+        #[test]
+        fn shrink_to_fit_drops_trailing_empty_words()
+        {
+            let mut occupancy: Occupancy = vec![1, 130].into_iter().collect();
+            occupancy.remove(130);
+            occupancy.shrink_to_fit();
+
+            assert_eq!(occupancy.capacity(), 1, "Expected only the word holding slot 1 to remain.");
+            assert!(occupancy.ones().eq(vec![1]));
+
+            occupancy.insert(200);
+            assert!(occupancy.ones().eq(vec![1, 200]), "Expected the set to grow again.");
+        }
+
+        #[test]
+        fn truncate_drops_trailing_bits()
+        {
+            let mut occupancy: Occupancy = vec![1, 63, 64, 65, 130].into_iter().collect();
+            occupancy.truncate(65);
+
+            assert!(occupancy.ones().eq(vec![1, 63, 64]));
+        }
+
+        #[test]
+        fn ones_meet_in_the_middle()
+        {
+            let occupancy: Occupancy = vec![0, 5, 70, 130, 191].into_iter().collect();
+
+            assert!(occupancy.ones().rev().eq(vec![191, 130, 70, 5, 0]));
+
+            let mut ones = occupancy.ones();
+            assert_eq!(ones.next(), Some(0));
+            assert_eq!(ones.next_back(), Some(191));
+            assert_eq!(ones.next_back(), Some(130));
+            assert_eq!(ones.next(), Some(5));
+            assert_eq!(ones.next(), Some(70));
+            assert_eq!(ones.next(), None);
+            assert_eq!(ones.next_back(), None);
+        }
+
+        #[test]
+        fn clear_keeps_nothing()
+        {
+            let mut occupancy: Occupancy = (0..100).collect();
+            occupancy.clear();
+
+            assert_eq!(occupancy.ones().next(), None);
+        }
+    }
+}
@@ -1,76 +1,588 @@
-pub trait Pool<T>
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::{ Hash, Hasher };
+use core::iter::FromIterator;
+use core::marker::PhantomData;
+use core::num::NonZeroU32;
+use core::ops::{ DerefMut, Index, IndexMut };
+use core::pin::Pin;
+
+use crate::entry::{ Entry, OccupiedEntry, VacantEntry };
+use crate::error::{ panic_full, AccessError, Corruption, InsertAtError, PoolError, PoolFull, RemoveError };
+use crate::generation::PoolGeneration;
+use crate::index::PoolIndex;
+use crate::occupancy::{ Occupancy, Ones };
+use crate::pool_id::PoolId;
+use crate::weak::WeakPoolKey;
+
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use core::convert::TryFrom;
+#[cfg(feature = "serde")]
+use serde::{ Serialize, Deserialize };
+
+#[cfg(feature = "rkyv")]
+use crate::archive::AsU64;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+#[cfg(feature = "allocator_api")]
+use alloc::alloc::{ Allocator, Global };
+
+pub trait Pool<T, I: PoolIndex = usize, G: PoolGeneration = NonZeroU32>
 {
     fn new(capacity: usize) -> Self;
     fn capacity(&self) -> usize;
-    fn insert(&mut self, value: T) -> PoolKey;
-    fn get(&self, key: &PoolKey) -> Option<&T>;
-    fn get_mut(&mut self, key: &PoolKey) -> Option<&mut T>;
-    fn take(&mut self, key: &PoolKey) -> Option<T>;
-    fn delete(&mut self, key: &PoolKey);
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool;
+    fn insert(&mut self, value: T) -> PoolKey<I, G>;
+    fn try_insert(&mut self, value: T) -> Result<PoolKey<I, G>, PoolFull<T>>;
+    fn get(&self, key: &PoolKey<I, G>) -> Option<&T>;
+    fn get_mut(&mut self, key: &PoolKey<I, G>) -> Option<&mut T>;
+    fn contains_key(&self, key: &PoolKey<I, G>) -> bool;
+    fn take(&mut self, key: &PoolKey<I, G>) -> Option<T>;
+    fn delete(&mut self, key: &PoolKey<I, G>) -> bool;
     fn clear(&mut self);
+
+    // ====-====-====-====-====-==== //
+
+    /// Returns the capacity [`new_default`] preallocates. `16` unless the implementation says otherwise.
+    ///
+    /// [`new_default`]: #method.new_default
+    fn default_capacity() -> usize { 16 }
+
+    /// Returns a new, empty pool. Preallocated with the [`default_capacity`].
+    ///
+    /// [`default_capacity`]: #method.default_capacity
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let pool: ObjectPool<i32> = ObjectPool::new_default();
+    /// assert_eq!(pool.capacity(), ObjectPool::<i32>::default_capacity());
+    /// ```
+    fn new_default() -> Self where Self: Sized
+    {
+        Self::new(Self::default_capacity())
+    }
+}
+
+
+/// Identifies an item in a [`Pool`], by slot index and generation.
+///
+/// Generations are never zero, so an `Option<PoolKey>` is the same size as a `PoolKey`.
+/// The index type defaults to `usize`, see [`PoolIndex`] for narrower alternatives,
+/// and the generation type to `NonZeroU32`, see [`PoolGeneration`] for the alternatives.
+///
+/// [`Pool`]: trait.Pool.html
+/// [`PoolIndex`]: trait.PoolIndex.html
+/// [`PoolGeneration`]: trait.PoolGeneration.html
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct PoolKey<I = usize, G = NonZeroU32>
+{
+    pub(crate) index: I,
+    pub(crate) generation: G,
+    #[cfg_attr(all(feature = "serde", not(feature = "pool-id")), serde(skip))]
+    pub(crate) pool_id: PoolId,
+}
+
+impl<I, G> PoolKey<I, G>
+{
+    /// Returns a key not tagged with any pool id.
+    pub(crate) fn new(index: I, generation: G) -> Self
+    {
+        Self::tagged(index, generation, PoolId::NONE)
+    }
+
+    pub(crate) fn tagged(index: I, generation: G, pool_id: PoolId) -> Self
+    {
+        return PoolKey {
+            index,
+            generation,
+            pool_id,
+        };
+    }
+}
+
+impl<I: PoolIndex, G: PoolGeneration> PoolKey<I, G>
+{
+    /// Returns the null key, a placeholder which never corresponds to an item in any pool.
+    ///
+    /// [`get`], [`get_mut`], [`contains_key`], [`take`] and [`delete`] all treat it as absent.
+    /// Its generation is one no slot ever issues, so it can't be confused with a live key.
+    ///
+    /// [`get`]: trait.Pool.html#tymethod.get
+    /// [`get_mut`]: trait.Pool.html#tymethod.get_mut
+    /// [`contains_key`]: trait.Pool.html#tymethod.contains_key
+    /// [`take`]: trait.Pool.html#tymethod.take
+    /// [`delete`]: trait.Pool.html#tymethod.delete
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool, PoolKey };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// pool.insert("Howdy!");
+    ///
+    /// let key = PoolKey::null();
+    /// assert!(key.is_null());
+    /// assert!(pool.get(&key).is_none());
+    /// ```
+    pub fn null() -> PoolKey<I, G>
+    {
+        return PoolKey::new(I::from_usize(I::MAX_INDEX).expect("MAX_INDEX fits the index type"), G::NULL);
+    }
+
+    /// Returns `true` if this is the [`null`] key.
+    ///
+    /// [`null`]: #method.null
+    pub fn is_null(&self) -> bool
+    {
+        return self.generation == G::NULL;
+    }
+}
+
+impl<I: PoolIndex> PoolKey<I>
+{
+    /// Packs the key into a single `u64`, index in the low 32 bits and generation in the high 32 bits.
+    ///
+    /// The index is truncated to 32 bits. In debug builds, this function panics if it exceeds `u32::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool, PoolKey };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert("Howdy!");
+    ///
+    /// let raw = key.as_u64();
+    /// let unpacked = PoolKey::from_u64(raw).unwrap();
+    ///
+    /// assert_eq!(pool.get(&unpacked), Some(&"Howdy!"));
+    /// ```
+    pub fn as_u64(&self) -> u64
+    {
+        let index = self.index.into_usize();
+        debug_assert!(index <= u32::MAX as usize, "PoolKey index exceeds u32::MAX");
+
+        return (index as u32 as u64) | ((self.generation.get() as u64) << 32);
+    }
+
+    /// Unpacks a key previously packed by [`as_u64`].
+    /// Returns `None` if the generation bits are zero, as no key has a zero generation,
+    /// or if the index does not fit the index type.
+    ///
+    /// With the `pool-id` feature, the pool id is not packed, so unpacked keys are accepted by any pool.
+    ///
+    /// [`as_u64`]: #method.as_u64
+    pub fn from_u64(raw: u64) -> Option<PoolKey<I>>
+    {
+        let generation = NonZeroU32::new((raw >> 32) as u32)?;
+        let index = I::from_usize((raw & u32::MAX as u64) as usize)?;
+
+        return Some(PoolKey::new(index, generation));
+    }
+
+    /// Returns a [`WeakPoolKey`] to the same item, which must be upgraded against the pool before use.
+    ///
+    /// [`WeakPoolKey`]: struct.WeakPoolKey.html
+    pub fn downgrade(&self) -> WeakPoolKey<I>
+    {
+        return WeakPoolKey::new(*self);
+    }
+}
+
+impl<I: PoolIndex, G: PoolGeneration> Default for PoolKey<I, G>
+{
+    /// Returns the [`null`] key.
+    ///
+    /// [`null`]: #method.null
+    fn default() -> Self
+    {
+        Self::null()
+    }
+}
+
+/// Marks the end of a free list.
+const NO_SLOT: usize = usize::MAX;
+
+/// Marks a vacant slot claimed by [`ObjectPool::reserve_slot`], awaiting its item. Never in the free list.
+const RESERVED_SLOT: usize = usize::MAX - 1;
+
+/// The contents of a slot: an item, or the link to the next slot in the pool's free list.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub(crate) enum Slot<T>
+{
+    Occupied(T),
+    Vacant(#[cfg_attr(feature = "rkyv", rkyv(with = AsU64))] usize),
+}
+
+impl<T> Default for Slot<T>
+{
+    fn default() -> Self
+    {
+        Slot::Vacant(NO_SLOT)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub(crate) struct PoolEntry<T, G: PoolGeneration = NonZeroU32>
+{
+    generation: G::Raw,
+    slot: Slot<T>,
+}
+
+// Implemented by hand, as a vacant entry needn't require `T: Default`.
+impl<T, G: PoolGeneration> Default for PoolEntry<T, G>
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
 }
 
 
+impl<T, G: PoolGeneration> PoolEntry<T, G>
+{
+    pub(crate) fn new() -> Self
+    {
+        Self::with_generation(G::Raw::default())
+    }
+
+    pub(crate) fn with_generation(generation: G::Raw) -> Self
+    {
+        Self {
+            generation,
+            slot: Slot::default(),
+        }
+    }
+
+    // ====-====-====-====-====-==== //
+
+    pub(crate) fn set(&mut self, value: T) -> G
+    {
+        assert!(!self.is_retired(), "retired slots are never reused");
+
+        self.slot = Slot::Occupied(value);
+        return self.advance();
+    }
+
+    /// Returns the key for this entry at the given index, if occupied.
+    pub(crate) fn key<I: PoolIndex>(&self, index: usize, pool_id: PoolId) -> Option<PoolKey<I, G>>
+    {
+        if self.is_empty() { return None; }
+
+        let generation = G::new(self.generation)?;
+        let index = I::from_usize(index)?;
+        return Some(PoolKey::tagged(index, generation, pool_id));
+    }
+
+    /// Claims the entry for a key issued ahead of its item, which [`fill`] later supplies.
+    ///
+    /// [`fill`]: #method.fill
+    pub(crate) fn reserve(&mut self) -> G
+    {
+        assert!(!self.is_retired(), "retired slots are never reused");
+
+        self.slot = Slot::Vacant(RESERVED_SLOT);
+        return self.advance();
+    }
+
+    /// Supplies the item of a reserved entry, under the generation it was reserved at.
+    pub(crate) fn fill(&mut self, value: T)
+    {
+        debug_assert!(self.is_reserved(), "only reserved slots are filled");
+        self.slot = Slot::Occupied(value);
+    }
+
+    /// Releases the entry's reservation, if any, leaving it vacant and unlinked.
+    pub(crate) fn unreserve(&mut self)
+    {
+        if self.is_reserved() { self.slot = Slot::default(); }
+    }
+
+    pub(crate) fn is_reserved(&self) -> bool
+    {
+        return matches!(self.slot, Slot::Vacant(RESERVED_SLOT));
+    }
+
+    pub(crate) fn matches<I>(&self, key: &PoolKey<I, G>) -> bool
+    {
+        return self.generation == key.generation.get();
+    }
+
+    pub(crate) fn get(&self) -> Option<&T>
+    {
+        if let Slot::Occupied(ref data) = self.slot { Some(data) }
+        else                                        { None }
+    }
+
+    pub(crate) fn get_mut(&mut self) -> Option<&mut T>
+    {
+        if let Slot::Occupied(ref mut data) = self.slot { Some(data) }
+        else                                            { None }
+    }
+
+    pub(crate) fn invalidate(&mut self) -> Option<T>
+    {
+        if self.generation < G::NULL.get() { self.generation = self.generation + G::Raw::from(1); }
+        return self.take();
+    }
+
+    /// Returns `true` if the generation is exhausted, the next one being reserved for the null key.
+    /// Once vacated, such a slot is retired: it is never reused, since any further generation would alias an older key.
+    pub(crate) fn is_retired(&self) -> bool
+    {
+        return self.generation >= G::RETIRED;
+    }
+
+    pub(crate) fn is_empty(&self) -> bool
+    {
+        return matches!(self.slot, Slot::Vacant(_));
+    }
+
+    /// Takes the item, if any. The free list link of a vacant entry is left as is.
+    pub(crate) fn take(&mut self) -> Option<T>
+    {
+        if self.is_empty() { return None; }
+
+        match core::mem::take(&mut self.slot)
+        {
+            Slot::Occupied(data) => Some(data),
+            Slot::Vacant(_)      => None,
+        }
+    }
+
+    pub(crate) fn into_data(self) -> Option<T>
+    {
+        match self.slot
+        {
+            Slot::Occupied(data) => Some(data),
+            Slot::Vacant(_)      => None,
+        }
+    }
+
+    /// Returns the entry with its item transformed by `f`. Vacant entries stay vacant, free list link included.
+    pub(crate) fn map<U, F: FnOnce(T) -> U>(self, f: F) -> PoolEntry<U, G>
+    {
+        PoolEntry {
+            generation: self.generation,
+            slot: match self.slot
+            {
+                Slot::Occupied(data) => Slot::Occupied(f(data)),
+                Slot::Vacant(next)   => Slot::Vacant(next),
+            },
+        }
+    }
+
+    /// Advances the generation for a new occupant, returning it. Never called on a retired entry.
+    fn advance(&mut self) -> G
+    {
+        self.generation = self.generation + G::Raw::from(1);
+        return G::new(self.generation).expect("generation is non-zero after increment");
+    }
+
+    /// Returns the next slot in the free list, if vacant.
+    fn next_free(&self) -> Option<usize>
+    {
+        if let Slot::Vacant(next) = self.slot { Some(next) }
+        else                                  { None }
+    }
+}
+
+/// The order in which an [`ObjectPool`] hands out freed slots again.
+///
+/// [`ObjectPool`]: struct.ObjectPool.html
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
-pub struct PoolKey
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub enum SlotReuse
 {
-    index: usize,
-    generation: usize,
+    /// The most recently freed slot is reused first. Favours locality, but a few slots take the bulk of the churn.
+    #[default]
+    Lifo,
+    /// The least recently freed slot is reused first. Spreads the churn, so generations grow slower per slot.
+    Fifo,
 }
 
+/// A snapshot of an [`ObjectPool`]'s utilization, returned by [`stats`].
+///
+/// [`ObjectPool`]: struct.ObjectPool.html
+/// [`stats`]: struct.ObjectPool.html#method.stats
+#[non_exhaustive]
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
-struct PoolEntry<T>
+pub struct PoolStats
 {
-    generation: usize,
-    data: Option<T>,
+    /// The number of slots, occupied or not.
+    pub capacity: usize,
+    /// The number of items.
+    pub len: usize,
+    /// The number of freed slots waiting to be reused, see [`free_count`](struct.ObjectPool.html#method.free_count).
+    pub free_slots: usize,
+    /// The most slots ever in use at once, i.e. the highest [`occupied_upper_bound`](struct.ObjectPool.html#method.occupied_upper_bound) reached.
+    pub high_water_mark: usize,
+    /// The highest generation of any slot.
+    pub max_generation: u64,
 }
 
+/// A queue of vacant slots, linked through the slots themselves so it needs no allocation of its own.
+/// Slots are always popped from the head, and pushed at the head or the tail depending on the reuse order.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub(crate) struct FreeList
+{
+    #[cfg_attr(feature = "rkyv", rkyv(with = AsU64))]
+    head: usize,
+    #[cfg_attr(feature = "rkyv", rkyv(with = AsU64))]
+    tail: usize,
+    #[cfg_attr(feature = "rkyv", rkyv(with = AsU64))]
+    len: usize,
+    reuse: SlotReuse,
+}
+
+impl Default for FreeList
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
 
-impl<T> PoolEntry<T>
+impl FreeList
 {
-    fn new() -> Self
+    pub(crate) fn new() -> Self
     {
         Self {
-            generation: 0,
-            data: None,
+            head: NO_SLOT,
+            tail: NO_SLOT,
+            len: 0,
+            reuse: SlotReuse::Lifo,
         }
     }
 
+    pub(crate) fn len(&self) -> usize { self.len }
+
+    pub(crate) fn reuse(&self) -> SlotReuse { self.reuse }
+
+    /// Sets the order of future pushes. Slots already in the list keep their place.
+    pub(crate) fn set_reuse(&mut self, reuse: SlotReuse) { self.reuse = reuse; }
+
+    /// Returns the slot the next pop would return.
+    pub(crate) fn peek(&self) -> Option<usize>
+    {
+        if self.head == NO_SLOT { None } else { Some(self.head) }
+    }
+
     // ====-====-====-====-====-==== //
 
-    fn set(&mut self, value: T) -> usize
+    /// Pushes the vacant entry at `index`.
+    pub(crate) fn push<T, G: PoolGeneration>(&mut self, data: &mut [PoolEntry<T, G>], index: usize)
     {
-        self.data = Some(value);
-        self.generation += 1;
+        debug_assert!(data[index].is_empty(), "only vacant slots are freed");
+
+        match self.reuse
+        {
+            SlotReuse::Lifo =>
+            {
+                data[index].slot = Slot::Vacant(self.head);
+                if self.head == NO_SLOT { self.tail = index; }
+                self.head = index;
+            }
+            SlotReuse::Fifo =>
+            {
+                data[index].slot = Slot::Vacant(NO_SLOT);
+                if self.tail == NO_SLOT { self.head = index; }
+                else                    { data[self.tail].slot = Slot::Vacant(index); }
+                self.tail = index;
+            }
+        }
 
-        return self.generation;
+        self.len += 1;
     }
 
-    fn get(&self) -> Option<&T>
+    /// Vacates the entry at `index`, returning its item, and pushes it unless it is retired.
+    pub(crate) fn release<T, G: PoolGeneration>(&mut self, data: &mut [PoolEntry<T, G>], index: usize) -> Option<T>
     {
-        if let Some(ref data) = self.data { Some(data) }
-        else                              { None }
+        let entry = &mut data[index];
+        let value = entry.take();
+        if !entry.is_retired() { self.push(data, index); }
+
+        return value;
     }
 
-    fn get_mut(&mut self) -> Option<&mut T>
+    pub(crate) fn pop<T, G: PoolGeneration>(&mut self, data: &[PoolEntry<T, G>]) -> Option<usize>
     {
-        if let Some(ref mut data) = self.data { Some(data) }
-        else                                  { None }
+        let index = self.peek()?;
+
+        // Checked, as neither `from_raw_parts` nor an rkyv archive validates the free list it hands over.
+        self.head = data[index].next_free().expect("free list links only vacant slots");
+        if self.head == NO_SLOT { self.tail = NO_SLOT; }
+        self.len -= 1;
+
+        return Some(index);
     }
 
-    fn clear(&mut self)
+    pub(crate) fn clear(&mut self)
     {
-        self.data = None;
+        *self = Self {
+            reuse: self.reuse,
+            ..Self::new()
+        };
     }
 
-    fn is_empty(&self) -> bool
+    /// Returns an iterator over the slots in the list, in the order they would be popped.
+    pub(crate) fn iter<'a, T, G: PoolGeneration>(&self, data: &'a [PoolEntry<T, G>]) -> impl Iterator<Item = usize> + 'a
     {
-        return self.data.is_none();
+        let mut next = self.head;
+        core::iter::from_fn(move || {
+            if next == NO_SLOT { return None; }
+
+            let index = next;
+            next = data[index].next_free().expect("free list links only vacant slots");
+            return Some(index);
+        })
+        .take(self.len)
     }
 
-    fn take(&mut self) -> Option<T>
+    /// Unlinks the slots for which `f` returns `false`, keeping the order of the rest.
+    pub(crate) fn retain<T, G: PoolGeneration, F: FnMut(usize) -> bool>(&mut self, data: &mut [PoolEntry<T, G>], mut f: F)
     {
-        return self.data.take();
+        let mut prev = NO_SLOT;
+        let mut index = self.head;
+
+        while index != NO_SLOT
+        {
+            let next = data[index].next_free().expect("free list links only vacant slots");
+
+            if f(index)
+            {
+                prev = index;
+            }
+            else
+            {
+                if prev == NO_SLOT { self.head = next; }
+                else               { data[prev].slot = Slot::Vacant(next); }
+
+                if index == self.tail { self.tail = prev; }
+                self.len -= 1;
+            }
+
+            index = next;
+        }
     }
 }
 
@@ -78,14 +590,32 @@ impl<T> PoolEntry<T>
 
 /// The default ObjectPool implementation.
 ///
-/// Allocation of specified capacity happens completely upfront, and the pool cannot be resized.
+/// Allocation of specified capacity happens completely upfront, and the pool is only resized when asked to via [`reserve`].
+/// Alternatively, pools created via [`new_growable`] double in capacity whenever an insert would otherwise fail.
+/// Growing never moves existing items between slots, so keys remain valid.
 ///
 /// Items are eagerly dropped when [`deleted`], so destructors run asap.
+/// When the pool is cleared or dropped, the items left are dropped in ascending index order, the order iteration visits them,
+/// regardless of the order they were inserted in. The same goes for the items an [`IntoIter`] hasn't yielded yet.
+///
+/// Iteration order is part of the contract: [`iter`], [`iter_mut`], [`keys`], [`values`] and the other iterators over the pool
+/// yield the occupied slots in ascending index order, which is not insertion order once freed slots are reused.
+/// Each can also be reversed, for descending index order. No change to the pool's internals will alter this.
+///
+/// A slot is retired once its generation is exhausted, after `u32::MAX - 1` occupants, and is never handed out again.
+/// This keeps old keys from ever aliasing new items, at the cost of that slot.
 ///
 /// See [`Pool`] implementation for more information.
 ///
 /// [`deleted`]: struct.ObjectPool.delete
+/// [`IntoIter`]: struct.IntoIter.html
+/// [`iter`]: struct.ObjectPool.html#method.iter
+/// [`iter_mut`]: struct.ObjectPool.html#method.iter_mut
+/// [`keys`]: struct.ObjectPool.html#method.keys
+/// [`values`]: struct.ObjectPool.html#method.values
 /// [`Pool`]: trait.Pool.html
+/// [`reserve`]: struct.ObjectPool.html#method.reserve
+/// [`new_growable`]: struct.ObjectPool.html#method.new_growable
 ///
 /// ```rust
 /// # use std::error::Error;
@@ -111,33 +641,167 @@ impl<T> PoolEntry<T>
 /// #     Ok(())
 /// # }
 /// ```
+///
+/// The index type of issued keys defaults to `usize`. Narrower types, see [`PoolIndex`], limit capacity accordingly.
+///
+/// [`PoolIndex`]: trait.PoolIndex.html
+///
+/// ```rust
+/// use spool::{ ObjectPool, Pool, PoolKey };
+///
+/// let mut pool = ObjectPool::<i32, u16>::new(10);
+/// let key: PoolKey<u16> = pool.insert(1);
+///
+/// //Too large for u16 indices.
+/// assert!(ObjectPool::<i32, u16>::try_new(100_000).is_err());
+/// ```
+///
+/// The generation type defaults to `NonZeroU32`. `NonZeroU16`, see [`PoolGeneration`], shrinks keys further,
+/// though a slot then retires after some 65 thousand occupants rather than 4 billion, and `NonZeroU64` all but never retires one.
+///
+/// [`PoolGeneration`]: trait.PoolGeneration.html
+///
+/// ```rust
+/// use std::num::NonZeroU16;
+/// use spool::{ ObjectPool, Pool, PoolKey };
+///
+/// let mut pool: ObjectPool<i32, u16, NonZeroU16> = Pool::new(10);
+/// let key: PoolKey<u16, NonZeroU16> = pool.insert(1);
+/// assert_eq!(pool[key], 1);
+/// ```
+///
+/// Zero-sized items, such as `()`, take up no storage of their own, making the pool a plain slot allocator with keys as tokens.
+/// Each slot still needs its generation and free list link, which is all a pool of zero-sized items allocates.
+///
+/// ```rust
+/// use spool::{ ObjectPool, Pool };
+///
+/// let mut tokens = ObjectPool::new(10);
+///
+/// let token = tokens.insert(());
+/// tokens.delete(&token);
+///
+/// assert!(!tokens.contains_key(&token));
+/// ```
+///
+/// With the `serde` feature enabled, pools are serialized verbatim: generations and free list included.
+/// Keys obtained before serialization remain valid against the deserialized pool.
+/// Deserialization runs [`validate`], so malformed input is reported as an error rather than corrupting the pool.
+///
+/// With the `rkyv` feature enabled, pools can be archived too, generations and free list included,
+/// and read in place through [`ArchivedObjectPool`], against which keys obtained before archiving still resolve.
+/// As with any rkyv archive, the bytes must be suitably aligned: 8 bytes is enough, as rkyv's `AlignedVec` and memory maps both provide.
+///
+/// [`validate`]: struct.ObjectPool.html#method.validate
+/// [`ArchivedObjectPool`]: struct.ArchivedObjectPool.html
+///
+/// With the `pool-id` feature enabled, each pool tags the keys it issues with a unique id,
+/// and treats keys issued by any other pool as absent, even one of the same type.
+/// Clones share the id of the original, so keys remain valid against both.
+/// Ids count up from a seed drawn at random once per process, so a pool deserialized from an earlier run is unlikely
+/// to share its id with a fresh one. Without the `std` feature there's no randomness to draw on, and the seed is fixed.
+///
+/// With the nightly-only `allocator_api` feature enabled, pools take an allocator type parameter, `Global` by default,
+/// and [`new_in`] keeps the slots in the allocator given. [`Pool`] is implemented for pools in any allocator that implements `Default`,
+/// such as `Global`, or a zero-sized handle to an arena, which [`Pool::new`] creates the pool in.
+/// The rest of the API, bar [`iter`] and [`iter_mut`], is for pools in the global allocator.
+///
+/// [`new_in`]: struct.ObjectPool.html#method.new_in
+/// [`Pool::new`]: trait.Pool.html#tymethod.new
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(
+    try_from = "PoolFields<T, I, G>",
+    bound(serialize = "T: Serialize, G::Raw: Serialize", deserialize = "T: Deserialize<'de>, I: PoolIndex, G::Raw: Deserialize<'de>"),
+))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct ObjectPool<T, I = usize, G: PoolGeneration = NonZeroU32, #[cfg(feature = "allocator_api")] A: Allocator = Global>
+{
+    #[cfg_attr(feature = "rkyv", rkyv(with = AsU64))]
+    count: usize,
+    #[cfg_attr(feature = "rkyv", rkyv(with = AsU64))]
+    next: usize,
+    free: FreeList,
+    #[cfg(not(feature = "allocator_api"))]
+    data: Vec<PoolEntry<T, G>>,
+    #[cfg(feature = "allocator_api")]
+    data: Vec<PoolEntry<T, G>, A>,
+    occupied: Occupancy,
+    growable: bool,
+    base_generation: G::Raw,
+    /// The highest `next` reached before it last fell, by a clear, shrink or defragment.
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "rkyv", rkyv(with = AsU64))]
+    peak_next: usize,
+    /// The number of retired slots, and of those at or beyond `next`, which inserts skip over, kept so neither needs a scan.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "rkyv", rkyv(with = AsU64))]
+    retired: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "rkyv", rkyv(with = AsU64))]
+    retired_unused: usize,
+    #[cfg_attr(all(feature = "serde", not(feature = "pool-id")), serde(skip))]
+    id: PoolId,
+    index: PhantomData<I>,
+}
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
-pub struct ObjectPool<T>
+/// The fields of an [`ObjectPool`] as serialized, deserialized as is and then validated on conversion into the pool.
+///
+/// [`ObjectPool`]: struct.ObjectPool.html
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(rename = "ObjectPool", bound(deserialize = "T: Deserialize<'de>, G::Raw: Deserialize<'de>"))]
+struct PoolFields<T, I, G: PoolGeneration>
 {
     count: usize,
     next: usize,
-    free: Vec<usize>,
-    data: Vec<PoolEntry<T>>,
+    free: FreeList,
+    data: Vec<PoolEntry<T, G>>,
+    occupied: Occupancy,
+    growable: bool,
+    base_generation: G::Raw,
+    #[serde(default)]
+    peak_next: usize,
+    #[cfg_attr(not(feature = "pool-id"), serde(skip))]
+    id: PoolId,
+    index: PhantomData<I>,
 }
 
-impl<T> ObjectPool<T>
+#[cfg(feature = "serde")]
+impl<T, I: PoolIndex, G: PoolGeneration> TryFrom<PoolFields<T, I, G>> for ObjectPool<T, I, G>
 {
-    pub fn iter(&self) -> impl Iterator<Item = &'_ T>
-    {
-        self.data.iter().filter_map(|e| e.get())
-    }
+    type Error = Corruption;
 
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &'_ mut T>
+    fn try_from(fields: PoolFields<T, I, G>) -> Result<Self, Corruption>
     {
-        self.data.iter_mut().filter_map(|e| e.get_mut())
+        let mut pool = Self {
+            count: fields.count,
+            next: fields.next,
+            free: fields.free,
+            data: fields.data,
+            occupied: fields.occupied,
+            growable: fields.growable,
+            base_generation: fields.base_generation,
+            peak_next: fields.peak_next,
+            retired: 0,
+            retired_unused: 0,
+            id: fields.id,
+            index: fields.index,
+        };
+        pool.recount_retired();
+        pool.validate()?;
+
+        return Ok(pool);
     }
 }
 
-impl<T> Pool<T> for ObjectPool<T>
+impl<T> ObjectPool<T>
 {
     /// Returns a new, empty pool. Preallocated with specified capacity.
     ///
+    /// Equivalent to [`Pool::new`], but fixes the index type to the default `usize` so it needn't be annotated.
+    ///
+    /// [`Pool::new`]: trait.Pool.html#tymethod.new
+    ///
     /// # Examples
     ///
     /// ```
@@ -146,180 +810,360 @@ impl<T> Pool<T> for ObjectPool<T>
     /// let pool: ObjectPool<i32> = ObjectPool::new(10);
     /// assert_eq!(pool.capacity(), 10);
     /// ```
-    fn new(capacity: usize) -> Self
+    pub fn new(capacity: usize) -> Self
     {
-        Self {
-            count: 0,
-            next: 0,
-            free: Vec::new(),
-            data: {
-                let mut data = Vec::with_capacity(capacity);
-                data.resize_with(capacity, PoolEntry::new);
-                data
-            }
-        }
+        <Self as Pool<T>>::new(capacity)
     }
 
-    // ====-====-====-====-====-==== //
-
-    /// Returns the maximum capacity of the pool.
+    /// Returns a new, empty pool. Preallocated with specified capacity.
+    /// Unlike [`new`], the pool doubles its capacity whenever an insert would otherwise fail.
+    ///
+    /// [`new`]: #method.new
     ///
     /// # Examples
     ///
     /// ```
     /// use spool::{ ObjectPool, Pool };
     ///
-    /// let pool: ObjectPool<i32> = ObjectPool::new(10);
-    /// assert_eq!(pool.capacity(), 10);
+    /// let mut pool = ObjectPool::new_growable(1);
+    ///
+    /// let key1 = pool.insert(1);
+    /// let key2 = pool.insert(2);
+    ///
+    /// assert_eq!(pool.capacity(), 2);
+    /// assert_eq!(pool.get(&key1), Some(&1));
     /// ```
-    fn capacity(&self) -> usize { self.data.capacity() }
-
-    // ====-====-====-====-====-==== //
+    pub fn new_growable(capacity: usize) -> Self
+    {
+        let mut pool = Self::new(capacity);
+        pool.growable = true;
+        return pool;
+    }
 
-    /// Returns a [`PoolKey`] corresponding to the inserted item.
+    /// Returns a new pool with no capacity, which allocates nothing. Equivalent to `new(0)`, and to [`Default`].
     ///
-    /// [`PoolKey`]: struct.PoolKey.html
+    /// Such a pool is always empty: [`try_insert`] fails, [`insert`] panics, [`get`] returns `None` and iterators yield nothing.
+    /// For a pool which allocates nothing until its first insert, use [`new_growable`]`(0)` instead.
     ///
-    /// # Panics
+    /// [`Default`]: https://doc.rust-lang.org/core/default/trait.Default.html
+    /// [`try_insert`]: trait.Pool.html#tymethod.try_insert
+    /// [`insert`]: trait.Pool.html#tymethod.insert
+    /// [`get`]: trait.Pool.html#tymethod.get
+    /// [`new_growable`]: #method.new_growable
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new_empty();
+    ///
+    /// assert_eq!(pool.capacity(), 0);
+    /// assert!(pool.try_insert(1).is_err());
+    /// ```
+    pub fn new_empty() -> Self
+    {
+        return Self::new(0);
+    }
+
+    /// Returns a new, empty pool. Preallocated with specified capacity, with every slot starting at generation `start`.
+    /// Keys it issues carry generations above `start`, including those of slots added by growing,
+    /// so they never collide with keys persisted from a pool whose generations stayed at or below it, e.g. before a migration.
     ///
-    /// This function panics if pool is full.
+    /// Slots are retired once their generation is exhausted, so a `start` close to `u32::MAX` leaves fewer inserts per slot.
     ///
     /// # Examples
     ///
     /// ```
     /// use spool::{ ObjectPool, Pool };
     ///
-    /// let mut pool = ObjectPool::new(10);
-    /// let key = pool.insert("Howdy!");
+    /// let mut old = ObjectPool::new(10);
+    /// let old_key = old.insert("Persisted");
+    ///
+    /// let mut pool = ObjectPool::with_capacity_and_generation_start(10, 100);
+    /// let key = pool.insert("Migrated");
+    ///
+    /// assert_ne!(key, old_key);
+    /// assert!(pool.get(&old_key).is_none());
     /// ```
-    fn insert(&mut self, value: T) -> PoolKey
+    pub fn with_capacity_and_generation_start(capacity: usize, start: u32) -> Self
     {
-        let index =
-            if let Some(index) = self.free.pop()
-            {
-                index
-            }
-            else if self.next < self.data.capacity()
-            {
-                let index = self.next;
-                self.next += 1;
-                index
-            }
-            else
-            {
-                // TODO: Result with an error?
-                panic!();
-            };
+        let mut pool = Self::new(capacity);
+        pool.base_generation = start;
+        for entry in &mut pool.data { entry.generation = start; }
 
-        let generation = unsafe {
-            self.data.get_unchecked_mut(index).set(value)
-        };
+        return pool;
+    }
 
-        self.count += 1;
+    /// Returns a [`PoolBuilder`], for configuring several of the pool's options at once.
+    ///
+    /// [`PoolBuilder`]: struct.PoolBuilder.html
+    pub fn builder() -> PoolBuilder<T>
+    {
+        return PoolBuilder::new();
+    }
+}
 
-        return PoolKey {
-            index,
-            generation,
+// ===-===-===-===-===-===-===-===-===-===-===-===-=== //
+
+/// Configures and builds an [`ObjectPool`].
+///
+/// Gathers the options otherwise spread across [`new`], [`new_growable`], [`with_slot_reuse`] and
+/// [`with_capacity_and_generation_start`]. Options left unset take the same defaults as [`ObjectPool::new_default`].
+///
+/// [`ObjectPool`]: struct.ObjectPool.html
+/// [`new`]: struct.ObjectPool.html#method.new
+/// [`new_growable`]: struct.ObjectPool.html#method.new_growable
+/// [`with_slot_reuse`]: struct.ObjectPool.html#method.with_slot_reuse
+/// [`with_capacity_and_generation_start`]: struct.ObjectPool.html#method.with_capacity_and_generation_start
+/// [`ObjectPool::new_default`]: trait.Pool.html#method.new_default
+///
+/// # Examples
+///
+/// ```
+/// use spool::{ ObjectPool, Pool, SlotReuse };
+///
+/// let mut pool = ObjectPool::builder()
+///     .capacity(4)
+///     .growable(true)
+///     .free_policy(SlotReuse::Fifo)
+///     .build();
+///
+/// let key = pool.insert("Built");
+///
+/// assert_eq!(pool.capacity(), 4);
+/// assert!(pool.is_growable());
+/// assert_eq!(pool.slot_reuse(), SlotReuse::Fifo);
+/// assert_eq!(pool.get(&key), Some(&"Built"));
+/// ```
+pub struct PoolBuilder<T>
+{
+    capacity: usize,
+    growable: bool,
+    reuse: SlotReuse,
+    generation_start: u32,
+    pool_id: bool,
+    item: PhantomData<fn() -> T>,
+}
+
+impl<T> PoolBuilder<T>
+{
+    /// Returns a builder with every option at its default: a fixed capacity of [`Pool::default_capacity`],
+    /// LIFO slot reuse, generations starting at 0, and keys tagged with the pool's id.
+    ///
+    /// [`Pool::default_capacity`]: trait.Pool.html#method.default_capacity
+    pub fn new() -> Self
+    {
+        return Self {
+            capacity: <ObjectPool<T> as Pool<T>>::default_capacity(),
+            growable: false,
+            reuse: SlotReuse::Lifo,
+            generation_start: 0,
+            pool_id: true,
+            item: PhantomData,
         };
     }
 
-    /// Retrieves an Option<&T> corresponding to the [`PoolKey`] referenced.
+    /// Sets the number of slots to preallocate.
+    pub fn capacity(mut self, capacity: usize) -> Self
+    {
+        self.capacity = capacity;
+        return self;
+    }
+
+    /// Sets whether the pool doubles its capacity whenever an insert would otherwise fail. See [`ObjectPool::new_growable`].
     ///
-    /// [`PoolKey`]: struct.PoolKey.html
+    /// [`ObjectPool::new_growable`]: struct.ObjectPool.html#method.new_growable
+    pub fn growable(mut self, growable: bool) -> Self
+    {
+        self.growable = growable;
+        return self;
+    }
+
+    /// Sets the order in which freed slots are handed out again. See [`ObjectPool::with_slot_reuse`].
     ///
-    /// # Examples
+    /// [`ObjectPool::with_slot_reuse`]: struct.ObjectPool.html#method.with_slot_reuse
+    pub fn free_policy(mut self, reuse: SlotReuse) -> Self
+    {
+        self.reuse = reuse;
+        return self;
+    }
+
+    /// Sets the generation every slot starts at. See [`ObjectPool::with_capacity_and_generation_start`].
     ///
-    /// ```
-    /// use spool::{ ObjectPool, Pool };
+    /// [`ObjectPool::with_capacity_and_generation_start`]: struct.ObjectPool.html#method.with_capacity_and_generation_start
+    pub fn generation_start(mut self, start: u32) -> Self
+    {
+        self.generation_start = start;
+        return self;
+    }
+
+    /// Sets whether the pool tags its keys with its own id, and rejects keys issued by other pools.
     ///
-    /// let mut pool = ObjectPool::new(10);
+    /// Without it, the pool accepts keys from any pool of the same type, and its own keys are accepted by any pool,
+    /// as if the `pool-id` feature were disabled. Has no effect unless the feature is enabled.
+    pub fn with_pool_id(mut self, enabled: bool) -> Self
+    {
+        self.pool_id = enabled;
+        return self;
+    }
+
+    /// Returns a new, empty pool, configured as set.
+    pub fn build(self) -> ObjectPool<T>
+    {
+        let mut pool = ObjectPool::with_capacity_and_generation_start(self.capacity, self.generation_start)
+            .with_slot_reuse(self.reuse);
+        pool.growable = self.growable;
+        if !self.pool_id { pool.id = PoolId::NONE; }
+
+        return pool;
+    }
+}
+
+impl<T> Default for PoolBuilder<T>
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl<T> Clone for PoolBuilder<T>
+{
+    fn clone(&self) -> Self
+    {
+        Self { item: PhantomData, ..*self }
+    }
+}
+
+impl<T> fmt::Debug for PoolBuilder<T>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        f.debug_struct("PoolBuilder")
+            .field("capacity", &self.capacity)
+            .field("growable", &self.growable)
+            .field("free_policy", &self.reuse)
+            .field("generation_start", &self.generation_start)
+            .field("pool_id", &self.pool_id)
+            .finish()
+    }
+}
+
+// ===-===-===-===-===-===-===-===-===-===-===-===-=== //
+
+impl<T, I: PoolIndex, G: PoolGeneration> ObjectPool<T, I, G>
+{
+    /// The most slots [`insert_at`] grows the pool by to reach a key's slot, so a corrupt or hostile key can't exhaust memory.
+    /// Reserve ahead of keys further out, e.g. with [`try_reserve`].
     ///
-    /// let key1 = pool.insert("I am going to be removed!");
-    /// let key2 = pool.insert("I am going to remain!");
+    /// [`insert_at`]: #method.insert_at
+    /// [`try_reserve`]: #method.try_reserve
+    pub const INSERT_AT_MAX_GROWTH: usize = 1 << 16;
+
+    /// Returns the largest capacity supported by the pool's index type.
     ///
-    /// pool.delete(&key1);
+    /// # Examples
     ///
-    /// assert!(pool.get(&key1).is_none());
-    /// assert!(pool.get(&key2).is_some());
     /// ```
-    fn get(&self, key: &PoolKey) -> Option<&T>
+    /// use spool::ObjectPool;
+    ///
+    /// assert_eq!(ObjectPool::<i32, u8>::max_capacity(), 256);
+    /// ```
+    pub fn max_capacity() -> usize
     {
-        if key.index >= self.data.capacity() { return None; }
-        else
-        {
-            let entry = unsafe { self.data.get_unchecked(key.index) };
-            if entry.generation != key.generation { None } else { entry.get() }
-        }
+        return Self::index_capacity();
     }
 
-    /// Retrieves an Option<&T> corresponding to the [`PoolKey`] referenced.
+    /// Returns a new, empty pool. Preallocated with specified capacity.
+    /// Returns [`PoolError::CapacityOverflow`] if the capacity exceeds the range of the pool's index type.
     ///
-    /// [`PoolKey`]: struct.PoolKey.html
+    /// [`PoolError::CapacityOverflow`]: enum.PoolError.html#variant.CapacityOverflow
     ///
     /// # Examples
     ///
     /// ```
-    /// use spool::{ ObjectPool, Pool };
+    /// use spool::{ ObjectPool, Pool, PoolError };
     ///
-    /// let mut pool = ObjectPool::new(10);
+    /// let pool = ObjectPool::<i32, u8>::try_new(256);
+    /// assert!(pool.is_ok());
     ///
-    /// let key1 = pool.insert("I am going to be removed!");
-    /// let key2 = pool.insert("I am going to remain!");
+    /// let pool = ObjectPool::<i32, u8>::try_new(257);
+    /// assert_eq!(pool.unwrap_err(), PoolError::CapacityOverflow);
+    /// ```
+    pub fn try_new(capacity: usize) -> Result<Self, PoolError>
+    {
+        if capacity > Self::max_capacity() { return Err(PoolError::CapacityOverflow); }
+
+        return Ok(Self {
+            count: 0,
+            next: 0,
+            free: FreeList::new(),
+            data: {
+                let mut data = Vec::with_capacity(capacity);
+                data.resize_with(capacity, PoolEntry::new);
+                data
+            },
+            occupied: Occupancy::new(),
+            growable: false,
+            base_generation: G::Raw::default(),
+            peak_next: 0,
+            retired: 0,
+            retired_unused: 0,
+            id: PoolId::unique(),
+            index: PhantomData,
+        });
+    }
+
+    /// Returns a new, empty, growable pool. Preallocated with specified capacity.
+    /// Returns [`PoolError::CapacityOverflow`] if the capacity exceeds the range of the pool's index type.
     ///
-    /// pool.delete(&key1);
+    /// See [`new_growable`].
     ///
-    /// assert!(pool.get_mut(&key1).is_none());
-    /// assert!(pool.get_mut(&key2).is_some());
-    /// ```
-    fn get_mut(&mut self, key: &PoolKey) -> Option<&mut T>
+    /// [`PoolError::CapacityOverflow`]: enum.PoolError.html#variant.CapacityOverflow
+    /// [`new_growable`]: #method.new_growable
+    pub fn try_new_growable(capacity: usize) -> Result<Self, PoolError>
     {
-        if key.index >= self.data.capacity() { return None; }
-        else
-        {
-            let entry = unsafe { self.data.get_unchecked_mut(key.index) };
-            if entry.generation != key.generation { None } else { entry.get_mut() }
-        }
+        let mut pool = Self::try_new(capacity)?;
+        pool.growable = true;
+        return Ok(pool);
     }
 
-    /// Extracts an Option<T> corresponding to the [`PoolKey`] referenced.
-    /// When an entry is been [`taken`] it is removed from the pool.
+    /// Returns `true` if the pool grows automatically when full.
+    pub fn is_growable(&self) -> bool { self.growable }
+
+    /// Returns the pool, set to hand out freed slots in the given order. Pools default to [`SlotReuse::Lifo`].
     ///
-    /// [`PoolKey`]: struct.PoolKey.html
-    /// [`taken`]: #method.take
+    /// Under [`SlotReuse::Fifo`], churn is spread across every freed slot,
+    /// so each slot's generation grows slower and is retired later, at a small cost in locality.
+    /// Slots freed before the change keep their place in line.
+    ///
+    /// [`SlotReuse::Lifo`]: enum.SlotReuse.html#variant.Lifo
+    /// [`SlotReuse::Fifo`]: enum.SlotReuse.html#variant.Fifo
     ///
     /// # Examples
     ///
     /// ```
-    /// use spool::{ ObjectPool, Pool };
+    /// use spool::{ ObjectPool, Pool, SlotReuse };
     ///
-    /// let mut pool = ObjectPool::new(10);
+    /// let mut pool = ObjectPool::new(10).with_slot_reuse(SlotReuse::Fifo);
+    /// assert_eq!(pool.slot_reuse(), SlotReuse::Fifo);
     ///
-    /// let key = pool.insert("Take me!");
+    /// let key = pool.insert(1);
+    /// pool.delete(&key);
     ///
-    /// assert!(pool.take(&key).is_some());
     /// assert!(pool.get(&key).is_none());
     /// ```
-    fn take(&mut self, key: &PoolKey) -> Option<T>
+    pub fn with_slot_reuse(mut self, reuse: SlotReuse) -> Self
     {
-        if key.index >= self.data.capacity() { return None; }
-        else
-        {
-            let entry = unsafe { self.data.get_unchecked_mut(key.index) };
-            if entry.generation != key.generation || entry.is_empty() { return None; }
-
-            self.count -= 1;
-            self.free.push(key.index);
-
-            entry.take()
-        }
+        self.free.set_reuse(reuse);
+        return self;
     }
 
-    /// Deletes an entry corresponding to the [`PoolKey`] referenced.
-    /// When an entry is been [`deleted`] it is removed, however it will not be returned.
-    ///
-    /// [`PoolKey`]: struct.PoolKey.html
-    /// [`take`]: #method.take
-    /// [`deleted`]: #method.delete
+    /// Returns the order in which the pool hands out freed slots.
+    pub fn slot_reuse(&self) -> SlotReuse { self.free.reuse() }
+
+    /// Returns the number of freed slots waiting to be reused. Retired slots are not counted.
     ///
     /// # Examples
     ///
@@ -327,29 +1171,80 @@ impl<T> Pool<T> for ObjectPool<T>
     /// use spool::{ ObjectPool, Pool };
     ///
     /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert(1);
+    /// pool.insert(2);
     ///
-    /// let key = pool.insert("Delete me!");
+    /// pool.delete(&key);
+    /// assert_eq!(pool.free_count(), 1);
+    /// ```
+    pub fn free_count(&self) -> usize { self.free.len() }
+
+    /// Returns how many more items fit before the pool reaches its capacity: freed slots, plus those never used since the last clear.
+    /// Retired slots are not counted. A growable pool grows rather than failing once this reaches `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
     ///
+    /// let mut pool = ObjectPool::new(3);
+    /// let key = pool.insert(1);
+    /// pool.insert(2);
     /// pool.delete(&key);
     ///
-    /// assert!(pool.get(&key).is_none());
+    /// assert_eq!(pool.capacity_remaining(), 2);
     /// ```
-    fn delete(&mut self, key: &PoolKey)
+    pub fn capacity_remaining(&self) -> usize
     {
-        if key.index >= self.data.capacity() { return; }
-        else
-        {
-            let entry = unsafe { self.data.get_unchecked_mut(key.index) };
-            if entry.generation != key.generation || entry.is_empty() { return; }
+        return self.free.len() + (self.data.len() - self.next - self.retired_unused);
+    }
 
-            entry.clear();
-            self.count -= 1;
-            self.free.push(key.index);
-        }
+    /// Returns the number of slots retired for good, their generations exhausted. See [`ObjectPool`].
+    ///
+    /// Retired slots are never handed out again, and survive clearing and shrinking, so the count only ever rises.
+    /// Worth monitoring in long-running processes, as each one is a slot lost to the pool.
+    ///
+    /// [`ObjectPool`]: struct.ObjectPool.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::with_capacity_and_generation_start(2, u32::MAX - 2);
+    /// assert_eq!(pool.retired_slots(), 0);
+    ///
+    /// let key = pool.insert("Last of its slot");
+    /// pool.delete(&key);
+    ///
+    /// assert_eq!(pool.retired_slots(), 1);
+    /// assert_eq!(pool.capacity_remaining(), 1);
+    /// ```
+    pub fn retired_slots(&self) -> usize
+    {
+        return self.retired;
     }
 
-    /// Deletes all entries.
-    /// No entries will be returned.
+    /// Returns `true` if no more items fit without growing, i.e. [`try_insert`] would fail, unless the pool is growable.
+    ///
+    /// [`try_insert`]: trait.Pool.html#tymethod.try_insert
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(1);
+    /// assert!(!pool.is_full());
+    ///
+    /// pool.insert(1);
+    /// assert!(pool.is_full());
+    /// assert!(pool.try_insert(2).is_err());
+    /// ```
+    pub fn is_full(&self) -> bool { self.capacity_remaining() == 0 }
+
+    /// Returns an upper bound on the slots occupied since the pool was last cleared.
+    /// Slots at or beyond it have never held an item since then.
     ///
     /// # Examples
     ///
@@ -357,36 +1252,7609 @@ impl<T> Pool<T> for ObjectPool<T>
     /// use spool::{ ObjectPool, Pool };
     ///
     /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert(1);
+    /// pool.insert(2);
     ///
-    /// let key1 = pool.insert("Delete me!");
-    /// let key2 = pool.insert("Delete me too!");
-    /// let key3 = pool.insert("Delete me too!");
+    /// pool.delete(&key);
+    /// assert_eq!(pool.occupied_upper_bound(), 2);
+    /// ```
+    pub fn occupied_upper_bound(&self) -> usize { self.next }
+
+    /// Returns an iterator over the indices of the occupied slots, in ascending order.
     ///
-    /// pool.clear();
+    /// The positions of [`keys`], without their generations.
+    ///
+    /// [`keys`]: #method.keys
+    ///
+    /// # Examples
     ///
-    /// assert!(pool.get(&key1).is_none());
-    /// assert!(pool.get(&key2).is_none());
-    /// assert!(pool.get(&key2).is_none());
     /// ```
-    fn clear(&mut self)
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let keys: Vec<_> = (0..4).map(|i| pool.insert(i)).collect();
+    /// pool.delete(&keys[1]);
+    ///
+    /// assert!(pool.occupied_indices().eq([0, 2, 3]));
+    /// ```
+    pub fn occupied_indices(&self) -> impl Iterator<Item = usize> + '_
     {
-        for entry in self.data.iter_mut() { entry.clear(); }
-
-        self.free.clear();
-        self.next = 0;
-        self.count = 0;
+        self.occupied.ones()
     }
-}
 
+    /// Returns an iterator over the indices of the vacant slots, in the order inserts would claim them:
+    /// freed slots first, per the [`SlotReuse`] order, then the never used slots from [`occupied_upper_bound`] up.
+    ///
+    /// Retired slots are never claimed, so they are neither free nor occupied.
+    /// Short of those, the free and occupied indices together cover the whole capacity.
+    ///
+    /// [`SlotReuse`]: enum.SlotReuse.html
+    /// [`occupied_upper_bound`]: #method.occupied_upper_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(5);
+    /// let keys: Vec<_> = (0..3).map(|i| pool.insert(i)).collect();
+    /// pool.delete(&keys[1]);
+    ///
+    /// assert!(pool.free_indices().eq([1, 3, 4]));
+    /// ```
+    pub fn free_indices(&self) -> impl Iterator<Item = usize> + '_
+    {
+        let unused = (self.next..self.data.len()).filter(move |&index| !self.data[index].is_retired());
+        return self.free.iter(&self.data).chain(unused);
+    }
+
+    /// Returns the most slots ever in use at once, i.e. the highest [`occupied_upper_bound`] reached.
+    /// Deletes, clears and shrinks don't lower it.
+    ///
+    /// [`occupied_upper_bound`]: #method.occupied_upper_bound
+    pub fn high_water_mark(&self) -> usize { self.peak_next.max(self.next) }
+
+    /// Returns a snapshot of the pool's utilization, in one call.
+    ///
+    /// Walks every slot to find the highest generation, so costs time linear in the capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let keys: Vec<_> = (0..4).map(|i| pool.insert(i)).collect();
+    /// pool.delete(&keys[3]);
+    ///
+    /// let stats = pool.stats();
+    /// assert_eq!((stats.capacity, stats.len, stats.free_slots, stats.high_water_mark), (10, 3, 1, 4));
+    /// assert_eq!(stats.max_generation, 1);
+    /// ```
+    pub fn stats(&self) -> PoolStats
+    {
+        return PoolStats {
+            capacity: self.data.len(),
+            len: self.count,
+            free_slots: self.free.len(),
+            high_water_mark: self.high_water_mark(),
+            max_generation: self.data.iter().map(|entry| entry.generation).max().unwrap_or_default().into(),
+        };
+    }
+
+    /// Returns the generation of the slot at `index`, or `None` if the index is out of range.
+    ///
+    /// While the slot is occupied, this is the generation of its item's key. Once vacated, keys at or below it are stale.
+    /// Meant for diagnosing key reuse, no valid key is needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert(1);
+    /// pool.delete(&key);
+    /// pool.insert(2);
+    ///
+    /// assert_eq!(pool.slot_generation(0), Some(2));
+    /// assert_eq!(pool.slot_generation(10), None);
+    /// ```
+    pub fn slot_generation(&self, index: usize) -> Option<G::Raw>
+    {
+        return self.data.get(index).map(|entry| entry.generation);
+    }
+
+    /// Returns the key of the item in the slot at `index`, carrying its current generation,
+    /// or `None` if the slot is vacant or the index is out of range.
+    /// For systems which only store raw indices; the inverse of taking the index out of a key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert("Indexed");
+    ///
+    /// assert_eq!(pool.key_for_index(0), Some(key));
+    /// assert_eq!(pool.key_for_index(1), None);
+    /// ```
+    pub fn key_for_index(&self, index: usize) -> Option<PoolKey<I, G>>
+    {
+        return self.data.get(index)?.key(index, self.id);
+    }
+
+    /// Returns a reference to whichever item currently occupies the slot at `index`,
+    /// or `None` if the slot is vacant or the index is out of range.
+    ///
+    /// Bypasses the generation check entirely: the item may well not be the one a caller last saw at that index.
+    /// Meant for diagnostics and tooling which speak in raw indices, e.g. a memory visualizer; use [`get`] to look items up.
+    ///
+    /// [`get`]: trait.Pool.html#tymethod.get
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert("First");
+    /// pool.delete(&key);
+    /// pool.insert("Second");
+    ///
+    /// assert_eq!(pool.get(&key), None);
+    /// assert_eq!(pool.get_by_index(0), Some(&"Second"));
+    /// ```
+    pub fn get_by_index(&self, index: usize) -> Option<&T>
+    {
+        return self.data.get(index)?.get();
+    }
+
+    /// Returns `true` if the slot at `index` holds an item, or `None` if the index is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// pool.insert(1);
+    ///
+    /// assert_eq!(pool.is_slot_occupied(0), Some(true));
+    /// assert_eq!(pool.is_slot_occupied(1), Some(false));
+    /// assert_eq!(pool.is_slot_occupied(10), None);
+    /// ```
+    pub fn is_slot_occupied(&self, index: usize) -> Option<bool>
+    {
+        return self.data.get(index).map(|entry| !entry.is_empty());
+    }
+
+    /// Returns a [`Debug`] view of the pool's internal layout: every slot with its generation, the free list, and so on.
+    ///
+    /// The pool's own [`Debug`] output only shows the live items, which is usually what's wanted.
+    ///
+    /// [`Debug`]: https://doc.rust-lang.org/core/fmt/trait.Debug.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(2);
+    /// pool.insert(1);
+    ///
+    /// assert!(format!("{:?}", pool.debug_layout()).contains("generation: 1"));
+    /// ```
+    pub fn debug_layout(&self) -> impl fmt::Debug + '_
+    where
+        T: fmt::Debug,
+        I: fmt::Debug,
+    {
+        DebugLayout(self)
+    }
+
+    /// Returns `true` if both pools hold equal items under the same keys.
+    ///
+    /// Unlike `==`, which compares the pools verbatim, this ignores anything which doesn't affect the live items:
+    /// capacity, the order of the free list, the generations of vacant slots, and so on.
+    /// With the `pool-id` feature, pool ids are ignored too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert(1);
+    /// pool.insert(2);
+    ///
+    /// let mut other = pool.clone();
+    /// other.reserve(100);
+    /// other.delete(&key);
+    /// assert_ne!(pool, other);
+    ///
+    /// pool.delete(&key);
+    /// assert!(pool.content_eq(&other));
+    /// ```
+    pub fn content_eq(&self, other: &ObjectPool<T, I, G>) -> bool
+    where
+        T: PartialEq,
+    {
+        if self.count != other.count { return false; }
+
+        // Equal counts, so matching every live item of `self` leaves none unmatched in `other`.
+        return self.occupied.ones().all(|index|
+        {
+            let entry = &self.data[index];
+            match other.data.get(index)
+            {
+                Some(other_entry) => other_entry.generation == entry.generation && other_entry.get() == entry.get(),
+                None              => false,
+            }
+        });
+    }
+
+    /// Grows the pool, if necessary, so that at least `additional` more items can be inserted.
+    /// Existing items are not moved, so previously issued keys remain valid.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the new capacity exceeds the range of the pool's index type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(1);
+    /// let key = pool.insert(1);
+    ///
+    /// pool.reserve(2);
+    /// assert_eq!(pool.capacity(), 3);
+    ///
+    /// pool.insert(2);
+    /// pool.insert(3);
+    /// assert_eq!(pool.get(&key), Some(&1));
+    /// ```
+    pub fn reserve(&mut self, additional: usize)
+    {
+        if let Err(error) = self.try_reserve(additional) { panic!("{}", error); }
+    }
+
+    /// Grows the pool, if necessary, so that at least `additional` more items can be inserted.
+    /// Returns [`PoolError::CapacityOverflow`] if the new capacity exceeds the range of the pool's index type,
+    /// or [`PoolError::AllocationFailed`] if the allocator can't provide the memory. The pool is unchanged on error.
+    ///
+    /// See [`reserve`].
+    ///
+    /// [`PoolError::CapacityOverflow`]: enum.PoolError.html#variant.CapacityOverflow
+    /// [`PoolError::AllocationFailed`]: enum.PoolError.html#variant.AllocationFailed
+    /// [`reserve`]: #method.reserve
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool, PoolError };
+    ///
+    /// let mut pool = ObjectPool::<i32, u8>::try_new(10).unwrap();
+    ///
+    /// assert_eq!(pool.try_reserve(100), Ok(()));
+    /// assert_eq!(pool.try_reserve(1000), Err(PoolError::CapacityOverflow));
+    /// assert_eq!(pool.capacity(), 100);
+    /// ```
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), PoolError>
+    {
+        let available = self.capacity_remaining();
+        if available < additional
+        {
+            let capacity = self.data.len().checked_add(additional - available)
+                .filter(|&capacity| capacity <= Self::max_capacity())
+                .ok_or(PoolError::CapacityOverflow)?;
+            self.try_push_slots(capacity - self.data.len())?;
+        }
+
+        return Ok(());
+    }
+
+    /// Checks the pool's internal invariants, returning the first one found broken.
+    /// Intended for pools built from outside data, e.g. by [`from_raw_parts`] or deserialization, before they're used.
+    ///
+    /// Checks that the counts match the occupied and retired slots, and that the free list only links distinct, vacant slots in range.
+    /// Runs in time linear in the capacity.
+    ///
+    /// [`from_raw_parts`]: #method.from_raw_parts
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert(1);
+    /// pool.insert(2);
+    /// pool.delete(&key);
+    ///
+    /// assert_eq!(pool.validate(), Ok(()));
+    /// ```
+    pub fn validate(&self) -> Result<(), Corruption>
+    {
+        let capacity = self.data.len();
+        if self.next > capacity { return Err(Corruption::NextOutOfRange { next: self.next, capacity }); }
+        if let Some(index) = self.occupied.ones().next_back().filter(|&index| index >= capacity)
+        {
+            return Err(Corruption::OccupancyMismatch { index });
+        }
+        if self.base_generation >= G::RETIRED
+        {
+            return Err(Corruption::RetiredBaseGeneration { generation: self.base_generation.into() });
+        }
+
+        let mut occupied = 0;
+        for (index, entry) in self.data.iter().enumerate()
+        {
+            let is_occupied = !entry.is_empty();
+            if is_occupied != self.occupied.contains(index) { return Err(Corruption::OccupancyMismatch { index }); }
+            if !is_occupied { continue; }
+
+            if index >= self.next     { return Err(Corruption::OccupiedBeyondNext { index }); }
+            if G::new(entry.generation).is_none() { return Err(Corruption::ZeroGeneration { index }); }
+            occupied += 1;
+        }
+        if occupied != self.count { return Err(Corruption::CountMismatch { count: self.count, occupied }); }
+
+        let (retired, retired_unused) = self.count_retired();
+        if (retired, retired_unused) != (self.retired, self.retired_unused)
+        {
+            return Err(Corruption::RetiredCountMismatch { count: self.retired, retired });
+        }
+
+        // Walked by hand rather than with `FreeList::iter`, as a corrupt list may link an occupied slot, or loop.
+        let mut listed = Vec::new();
+        listed.resize(self.next, false);
+        let (mut linked, mut tail) = (0, NO_SLOT);
+        let mut index = self.free.head;
+        while index != NO_SLOT
+        {
+            if index >= self.next { return Err(Corruption::FreeIndexOutOfRange { index }); }
+            if listed[index]      { return Err(Corruption::DuplicateFreeIndex { index }); }
+            listed[index] = true;
+
+            let entry = &self.data[index];
+            if entry.is_retired() { return Err(Corruption::FreeSlotRetired { index }); }
+
+            tail = index;
+            linked += 1;
+            index = entry.next_free().ok_or(Corruption::FreeSlotOccupied { index })?;
+        }
+        if linked != self.free.len || tail != self.free.tail { return Err(Corruption::FreeListMismatch { len: self.free.len, linked }); }
+
+        return Ok(());
+    }
+
+    /// Releases memory the pool's bookkeeping holds beyond what its items need, e.g. after deleting a large burst of them.
+    /// Unlike [`shrink_to_fit`], the slots themselves are left alone, so the capacity is unchanged.
+    ///
+    /// The free list is threaded through the vacant slots, so it has no memory of its own to shrink;
+    /// what's released is the occupancy bitmap iteration uses to skip vacant slots, down to the last occupied one.
+    ///
+    /// [`shrink_to_fit`]: #method.shrink_to_fit
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10_000);
+    /// let keys: Vec<_> = (0..10_000).map(|i| pool.insert(i)).collect();
+    /// for key in &keys[1..] { pool.delete(key); }
+    ///
+    /// pool.shrink_free_list();
+    ///
+    /// assert_eq!(pool.capacity(), 10_000);
+    /// assert!(pool.iter().eq(&[0]));
+    /// ```
+    pub fn shrink_free_list(&mut self)
+    {
+        self.occupied.shrink_to_fit();
+    }
+
+    /// Shrinks the capacity of the pool, dropping unused slots beyond the last occupied one.
+    /// Occupied slots are never moved, so previously issued keys remain valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// let key1 = pool.insert(1);
+    /// let key2 = pool.insert(2);
+    /// let key3 = pool.insert(3);
+    ///
+    /// pool.delete(&key3);
+    /// pool.shrink_to_fit();
+    ///
+    /// assert_eq!(pool.capacity(), 2);
+    /// assert_eq!(pool.get(&key2), Some(&2));
+    /// ```
+    pub fn shrink_to_fit(&mut self)
+    {
+        // Retired slots are kept too, lest their index be reissued by a later grow, and reserved ones, awaiting their item.
+        let next = self.next;
+        let len = self.data.iter().enumerate()
+            .rposition(|(index, e)| !e.is_empty() || e.is_retired() || (index < next && e.is_reserved()))
+            .map_or(0, |i| i + 1);
+
+        // Slots created by a later grow must not reissue generations of the slots being dropped.
+        let max_generation = self.data[len..].iter().map(|e| e.generation).max();
+        if let Some(generation) = max_generation
+        {
+            self.base_generation = self.base_generation.max(generation);
+        }
+
+        self.free.retain(&mut self.data, |index| index < len);
+        self.data.truncate(len);
+        self.data.shrink_to_fit();
+        self.occupied.truncate(len);
+        self.peak_next = self.high_water_mark();
+        self.next = self.next.min(len);
+        self.recount_retired();
+    }
+
+    /// Removes all items and releases the pool's memory, shrinking its capacity to zero.
+    /// Unlike [`clear`], which keeps the allocation for reuse.
+    ///
+    /// Previously issued keys are invalidated, even against slots created by a later grow.
+    /// Retired slots are kept, lest their index be reissued, so the capacity only drops as far as the last of those.
+    ///
+    /// [`clear`]: trait.Pool.html#tymethod.clear
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new_growable(1000);
+    /// let key = pool.insert(1);
+    ///
+    /// pool.clear_and_shrink();
+    /// assert_eq!(pool.capacity(), 0);
+    ///
+    /// pool.insert(2);
+    /// assert!(pool.get(&key).is_none());
+    /// ```
+    pub fn clear_and_shrink(&mut self)
+    {
+        self.clear();
+        self.shrink_to_fit();
+    }
+
+    /// Returns a reference to the item, or an [`AccessError`] saying why there was nothing to return.
+    /// Like [`get`], but tells a stale key from one whose item is already gone, e.g. to diagnose a dangling key.
+    ///
+    /// [`AccessError`]: enum.AccessError.html
+    /// [`get`]: trait.Pool.html#tymethod.get
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ AccessError, ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert(1);
+    /// assert_eq!(pool.try_get(&key), Ok(&1));
+    ///
+    /// pool.delete(&key);
+    /// assert_eq!(pool.try_get(&key), Err(AccessError::Empty));
+    ///
+    /// pool.insert(2);
+    /// assert_eq!(pool.try_get(&key), Err(AccessError::StaleGeneration));
+    /// ```
+    pub fn try_get(&self, key: &PoolKey<I, G>) -> Result<&T, AccessError>
+    {
+        let entry = self.data.get(key.index.into_usize()).ok_or(AccessError::OutOfRange)?;
+
+        if !self.id.admits(key.pool_id) { return Err(AccessError::ForeignKey); }
+        if !entry.matches(key)          { return Err(AccessError::StaleGeneration); }
+
+        return entry.get().ok_or(AccessError::Empty);
+    }
+
+    /// Returns a mutable reference to the item, or an [`AccessError`] saying why there was nothing to return. See [`try_get`].
+    ///
+    /// [`AccessError`]: enum.AccessError.html
+    /// [`try_get`]: #method.try_get
+    pub fn try_get_mut(&mut self, key: &PoolKey<I, G>) -> Result<&mut T, AccessError>
+    {
+        self.try_get(key)?;
+        return Ok(self.get_mut(key).expect("key was checked to be valid"));
+    }
+
+    /// Removes the item from the pool and returns it, or a [`RemoveError`] saying why there was nothing to remove.
+    /// Like [`take`], but tells a stale key from one whose item is already gone, e.g. to diagnose a use after free.
+    ///
+    /// [`RemoveError`]: enum.RemoveError.html
+    /// [`take`]: trait.Pool.html#tymethod.take
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool, RemoveError };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert(1);
+    ///
+    /// assert_eq!(pool.try_take(&key), Ok(1));
+    /// assert_eq!(pool.try_take(&key), Err(RemoveError::AlreadyEmpty));
+    ///
+    /// pool.insert(2);
+    /// assert_eq!(pool.try_take(&key), Err(RemoveError::StaleGeneration));
+    /// ```
+    pub fn try_take(&mut self, key: &PoolKey<I, G>) -> Result<T, RemoveError>
+    {
+        let entry = self.data.get(key.index.into_usize()).ok_or(RemoveError::OutOfRange)?;
+
+        if !self.id.admits(key.pool_id) { return Err(RemoveError::ForeignKey); }
+        if !entry.matches(key)          { return Err(RemoveError::StaleGeneration); }
+        if entry.is_empty()             { return Err(RemoveError::AlreadyEmpty); }
+
+        return Ok(self.take(key).expect("key was checked to be valid"));
+    }
+
+    /// Deletes the item, or returns a [`RemoveError`] saying why there was nothing to delete. See [`try_take`].
+    ///
+    /// [`RemoveError`]: enum.RemoveError.html
+    /// [`try_take`]: #method.try_take
+    pub fn try_delete(&mut self, key: &PoolKey<I, G>) -> Result<(), RemoveError>
+    {
+        self.try_take(key).map(drop)
+    }
+
+    /// Returns an iterator over the items in the pool, along with their [`PoolKey`]s, in ascending index order.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// pool.insert("Howdy!");
+    ///
+    /// for (key, item) in pool.iter_with_keys()
+    /// {
+    ///     assert_eq!(pool.get(&key), Some(item));
+    /// }
+    /// ```
+    pub fn iter_with_keys(&self) -> impl DoubleEndedIterator<Item = (PoolKey<I, G>, &'_ T)>
+    {
+        self.occupied.ones().filter_map(move |index| {
+            let entry = &self.data[index];
+            entry.key(index, self.id).zip(entry.get())
+        })
+    }
+
+    /// Returns an iterator over mutable references to the items in the pool, along with their [`PoolKey`]s, in ascending index order.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// pool.insert(1);
+    /// pool.insert(2);
+    ///
+    /// let mut odd = Vec::new();
+    /// for (key, item) in pool.iter_mut_with_keys()
+    /// {
+    ///     *item += 1;
+    ///     if *item % 2 == 1 { odd.push(key); }
+    /// }
+    ///
+    /// for key in odd { pool.delete(&key); }
+    /// assert_eq!(pool.len(), 1);
+    /// ```
+    pub fn iter_mut_with_keys(&mut self) -> impl DoubleEndedIterator<Item = (PoolKey<I, G>, &'_ mut T)>
+    {
+        return IterMutWithKeys {
+            inner: IterMut::new(&mut self.data, Some(self.occupied.ones()), self.count),
+            id: self.id,
+            index: PhantomData,
+        };
+    }
+
+    /// Inserts the item returned by `f`, which is passed the [`PoolKey`] the item will be inserted under.
+    /// Returns that same key. Useful for items which need to know their own key.
+    ///
+    /// If `f` panics, nothing is inserted, and the slot claimed for the item is handed back to the pool,
+    /// under a later generation, so the key passed to `f` never resolves.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    ///
+    /// # Panics
+    ///
+    /// This function panics if pool is full, unless the pool is growable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool, PoolKey };
+    ///
+    /// struct Node
+    /// {
+    ///     this: PoolKey,
+    /// }
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert_with_key(|this| Node { this });
+    ///
+    /// assert_eq!(pool[key].this, key);
+    /// ```
+    pub fn insert_with_key<F: FnOnce(PoolKey<I, G>) -> T>(&mut self, f: F) -> PoolKey<I, G>
+    {
+        let index = match self.claim_slot()
+        {
+            Some(index) => index,
+            None        => panic_full("ObjectPool", self.capacity()),
+        };
+
+        // The generation the slot will have once set, which is what the key must carry.
+        let generation = G::new(self.data[index].generation + G::Raw::from(1)).expect("generation is non-zero after increment");
+        let key = PoolKey::tagged(
+            I::from_usize(index).expect("slot index exceeds the range of the pool's index type"),
+            generation,
+            self.id,
+        );
+
+        // Should `f` panic, the guard hands the slot back, so it isn't lost to the pool.
+        let guard = ReleaseOnUnwind { pool: self, index };
+        let value = f(key);
+
+        let pool = &mut *guard.pool;
+        pool.data[index].set(value);
+        pool.occupied.insert(index);
+        pool.count += 1;
+        core::mem::forget(guard);
+
+        return key;
+    }
+
+    /// Inserts the item at the exact slot and generation of `key`, so that the key resolves to it.
+    /// Intended for rebuilding a pool from a record of the keys it issued, e.g. when replaying a log.
+    /// The pool grows to reach the key's slot if needed, even if it is not growable, by at most [`INSERT_AT_MAX_GROWTH`] slots.
+    ///
+    /// Returns an [`InsertAtError`], handing back the item, if the key is null or was issued by another pool,
+    /// if the slot is occupied, or if the slot has already been through the key's generation.
+    /// Generations only move forward, so keys issued before the slot was last vacated stay invalid.
+    /// Returns [`InsertAtError::OutOfRange`] if the slot is beyond the pool's reach: past [`max_capacity`],
+    /// more than [`INSERT_AT_MAX_GROWTH`] slots past the capacity, or unable to be allocated.
+    ///
+    /// Claiming a slot below the highest claimed so far takes it out of the free list, in time proportional to its depth in the list.
+    /// A slot freed by the replayed record sits near the head, so replaying keys in the order they were issued stays fast.
+    ///
+    /// [`InsertAtError`]: enum.InsertAtError.html
+    /// [`InsertAtError::OutOfRange`]: enum.InsertAtError.html#variant.OutOfRange
+    /// [`INSERT_AT_MAX_GROWTH`]: #associatedconstant.INSERT_AT_MAX_GROWTH
+    /// [`max_capacity`]: #method.max_capacity
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool, PoolKey };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let raw = pool.insert("Saved").as_u64();
+    ///
+    /// let mut restored = ObjectPool::new(0);
+    /// let key = PoolKey::from_u64(raw).unwrap();
+    ///
+    /// assert!(restored.insert_at(key, "Saved").is_ok());
+    /// assert_eq!(restored.get(&key), Some(&"Saved"));
+    /// assert!(restored.insert_at(key, "Again").is_err());
+    /// ```
+    pub fn insert_at(&mut self, key: PoolKey<I, G>, value: T) -> Result<(), InsertAtError<T>>
+    {
+        if key.is_null() || !self.id.admits(key.pool_id) { return Err(InsertAtError::Invalid(value)); }
+
+        let index = key.index.into_usize();
+        let generation = match self.data.get(index)
+        {
+            Some(entry) if !entry.is_empty() => return Err(InsertAtError::Occupied(value)),
+            Some(entry)                      => entry.generation,
+            None                             => self.base_generation,
+        };
+        if key.generation.get() <= generation { return Err(InsertAtError::Stale(value)); }
+
+        if index >= self.data.len()
+        {
+            let additional = index + 1 - self.data.len();
+            if index >= Self::max_capacity() || additional > Self::INSERT_AT_MAX_GROWTH { return Err(InsertAtError::OutOfRange(value)); }
+            if self.try_push_slots(additional).is_err() { return Err(InsertAtError::OutOfRange(value)); }
+        }
+
+        self.claim_index(index);
+
+        let entry = &mut self.data[index];
+        entry.generation = key.generation.get() - G::Raw::from(1);
+        entry.set(value);
+
+        self.occupied.insert(index);
+        self.count += 1;
+
+        return Ok(());
+    }
+
+    /// Claims a slot and returns its key ahead of the item, for two-phase construction: store the key elsewhere, then [`fill`] it.
+    /// Until filled, the key resolves to nothing, e.g. [`get`] returns `None`, and the slot is not handed out by inserts.
+    ///
+    /// The slot stays claimed until filled, or the pool is cleared. Compacting the pool, e.g. by [`defragment`], releases it,
+    /// and so does encoding it with [`to_bytes`], after which [`fill`] fails.
+    ///
+    /// [`fill`]: #method.fill
+    /// [`get`]: trait.Pool.html#tymethod.get
+    /// [`defragment`]: #method.defragment
+    /// [`to_bytes`]: #method.to_bytes
+    ///
+    /// # Panics
+    ///
+    /// This function panics if pool is full, unless the pool is growable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// let key = pool.reserve_slot();
+    /// assert!(pool.get(&key).is_none());
+    ///
+    /// pool.fill(&key, "Filled in later").unwrap();
+    /// assert_eq!(pool.get(&key), Some(&"Filled in later"));
+    /// ```
+    pub fn reserve_slot(&mut self) -> PoolKey<I, G>
+    {
+        let index = match self.claim_slot()
+        {
+            Some(index) => index,
+            None        => panic_full("ObjectPool", self.capacity()),
+        };
+
+        let generation = self.data[index].reserve();
+        return PoolKey::tagged(
+            I::from_usize(index).expect("slot index exceeds the range of the pool's index type"),
+            generation,
+            self.id,
+        );
+    }
+
+    /// Supplies the item of a key returned by [`reserve_slot`], after which the key resolves to it.
+    ///
+    /// Returns an [`InsertAtError`], handing back the item, if the key is null, out of range, or was issued by another pool,
+    /// if the slot has already been filled, or if the key isn't a reservation still awaiting its item.
+    ///
+    /// [`reserve_slot`]: #method.reserve_slot
+    /// [`InsertAtError`]: enum.InsertAtError.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool, InsertAtError };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.reserve_slot();
+    ///
+    /// assert_eq!(pool.fill(&key, 1), Ok(()));
+    /// assert_eq!(pool.fill(&key, 2), Err(InsertAtError::Occupied(2)));
+    /// ```
+    pub fn fill(&mut self, key: &PoolKey<I, G>, value: T) -> Result<(), InsertAtError<T>>
+    {
+        let index = key.index.into_usize();
+        if key.is_null() || index >= self.data.len() || !self.id.admits(key.pool_id) { return Err(InsertAtError::Invalid(value)); }
+
+        let entry = &mut self.data[index];
+        if !entry.matches(key) { return Err(InsertAtError::Stale(value)); }
+        if !entry.is_empty()   { return Err(InsertAtError::Occupied(value)); }
+
+        // Slots at or beyond `next` are unclaimed, even if a compaction left their reservation mark behind.
+        if !entry.is_reserved() || index >= self.next { return Err(InsertAtError::Stale(value)); }
+
+        entry.fill(value);
+        self.occupied.insert(index);
+        self.count += 1;
+
+        return Ok(());
+    }
+
+    /// Puts an item back in the slot `key`'s item was removed from, as if it were never gone, returning the slot's new key.
+    /// Intended for transactional updates: take an item, transform it, and put it back in place.
+    ///
+    /// The old key stays invalid, as after any removal; the new key is the slot's next generation.
+    ///
+    /// Returns an [`InsertAtError`], handing back the item, if the key is null or was issued by another pool,
+    /// if the key's item is still in the pool, or if the slot has been reused, cleared, or retired since.
+    ///
+    /// [`InsertAtError`]: enum.InsertAtError.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert(String::from("Draft"));
+    ///
+    /// let mut value = pool.take(&key).unwrap();
+    /// value.push_str(", revised");
+    ///
+    /// let new_key = pool.reinsert(key, value).unwrap();
+    ///
+    /// assert_eq!(pool.get(&new_key).map(String::as_str), Some("Draft, revised"));
+    /// assert!(pool.get(&key).is_none());
+    /// ```
+    pub fn reinsert(&mut self, key: PoolKey<I, G>, value: T) -> Result<PoolKey<I, G>, InsertAtError<T>>
+    {
+        if key.is_null() || !self.id.admits(key.pool_id) { return Err(InsertAtError::Invalid(value)); }
+
+        let index = key.index.into_usize();
+        match self.data.get(index)
+        {
+            Some(entry) if !entry.matches(&key) || entry.is_retired() => return Err(InsertAtError::Stale(value)),
+            Some(entry) if !entry.is_empty()                          => return Err(InsertAtError::Occupied(value)),
+            Some(_)                                                   => {},
+            None                                                      => return Err(InsertAtError::Stale(value)),
+        }
+
+        self.claim_index(index);
+
+        let generation = self.data[index].set(value);
+        self.occupied.insert(index);
+        self.count += 1;
+
+        return Ok(PoolKey::tagged(key.index, generation, self.id));
+    }
+
+    /// Inserts the items of the iterator, in order, returning their [`PoolKey`]s.
+    /// A growable pool grows once upfront for the iterator's lower size hint.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    ///
+    /// # Panics
+    ///
+    /// This function panics if pool becomes full, unless the pool is growable. Items inserted before then remain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let keys = pool.insert_many(vec!["One", "Two", "Three"]);
+    ///
+    /// assert_eq!(pool[keys[1]], "Two");
+    /// ```
+    pub fn insert_many<It: IntoIterator<Item = T>>(&mut self, items: It) -> Vec<PoolKey<I, G>>
+    {
+        let items = items.into_iter();
+        let mut keys = Vec::with_capacity(items.size_hint().0);
+
+        self.grow_for(items.size_hint().0);
+
+        for value in items { keys.push(self.insert(value)); }
+
+        return keys;
+    }
+
+    /// Deletes the items corresponding to the [`PoolKey`]s referenced.
+    /// Keys which are out of range, stale, or point at empty entries are ignored.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let keys = pool.insert_many(0..5);
+    ///
+    /// pool.delete_many(&keys[..3]);
+    ///
+    /// assert_eq!(pool.len(), 2);
+    /// ```
+    pub fn delete_many(&mut self, keys: &[PoolKey<I, G>])
+    {
+        for key in keys { self.delete(key); }
+    }
+
+    /// Replaces the item corresponding to the [`PoolKey`] referenced, returning the previous item.
+    /// The generation is unchanged, so the key remains valid.
+    /// Returns `None`, leaving the pool untouched, if the key is out of range, stale, or points at an empty entry.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert("Old");
+    ///
+    /// assert_eq!(pool.replace(&key, "New"), Some("Old"));
+    /// assert_eq!(pool[key], "New");
+    /// ```
+    pub fn replace(&mut self, key: &PoolKey<I, G>, value: T) -> Option<T>
+    {
+        let item = self.get_mut(key)?;
+        return Some(core::mem::replace(item, value));
+    }
+
+    /// Alias for [`take`], named after the standard collections' `remove`.
+    ///
+    /// [`take`]: trait.Pool.html#tymethod.take
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert(1);
+    ///
+    /// assert_eq!(pool.remove(&key), Some(1));
+    /// assert_eq!(pool.remove(&key), None);
+    /// ```
+    pub fn remove(&mut self, key: &PoolKey<I, G>) -> Option<T>
+    {
+        self.take(key)
+    }
+
+    /// Alias for [`contains_key`], named after slab's `contains`.
+    ///
+    /// [`contains_key`]: trait.Pool.html#tymethod.contains_key
+    pub fn contains(&self, key: &PoolKey<I, G>) -> bool
+    {
+        self.contains_key(key)
+    }
+
+    /// Returns the [`Entry`] for the key, for in-place manipulation or get-or-insert semantics.
+    ///
+    /// [`Entry`]: enum.Entry.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert(1);
+    ///
+    /// let (key, item) = pool.entry(&key).or_insert(100);
+    /// *item += 1;
+    ///
+    /// assert_eq!(pool[key], 2);
+    /// ```
+    pub fn entry(&mut self, key: &PoolKey<I, G>) -> Entry<'_, T, I, G>
+    {
+        if self.contains_key(key) { Entry::Occupied(OccupiedEntry { pool: self, key: *key }) }
+        else                      { Entry::Vacant(VacantEntry { pool: self }) }
+    }
+
+    /// Returns the key of the item along with a mutable reference to it, if the key is valid.
+    /// Otherwise inserts the result of `f` into a fresh slot, and returns the new key along with the new item.
+    ///
+    /// Shorthand for `pool.entry(key).or_insert_with(f)`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the key is not valid and the pool is full, unless the pool is growable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// let key = pool.insert(1);
+    /// pool.delete(&key);
+    ///
+    /// let (new_key, item) = pool.get_or_insert_with(&key, || 2);
+    /// *item += 1;
+    ///
+    /// assert_ne!(new_key, key);
+    /// assert_eq!(pool[new_key], 3);
+    /// ```
+    pub fn get_or_insert_with<F: FnOnce() -> T>(&mut self, key: &PoolKey<I, G>, f: F) -> (PoolKey<I, G>, &mut T)
+    {
+        return self.entry(key).or_insert_with(f);
+    }
+
+    /// Returns the key of the item corresponding to the [`PoolKey`] referenced, along with a mutable reference to it.
+    /// If the key is out of range, stale, or points at an empty entry, inserts `T::default()` first, under a new key.
+    /// Shorthand for `entry(key).or_default()`.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    ///
+    /// # Panics
+    ///
+    /// This function panics if an insert is needed and the pool is full, unless the pool is growable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool, PoolKey };
+    ///
+    /// let mut counts: ObjectPool<u32> = ObjectPool::new(10);
+    ///
+    /// let (key, count) = counts.get_or_default(&PoolKey::null());
+    /// *count += 1;
+    ///
+    /// let (same_key, count) = counts.get_or_default(&key);
+    /// *count += 1;
+    ///
+    /// assert_eq!(same_key, key);
+    /// assert_eq!(counts[key], 2);
+    /// ```
+    pub fn get_or_default(&mut self, key: &PoolKey<I, G>) -> (PoolKey<I, G>, &mut T) where T: Default
+    {
+        return self.entry(key).or_default();
+    }
+
+    /// Retrieves a mutable reference to the item corresponding to the [`PoolKey`] referenced, like [`get_mut`],
+    /// but panics with the supplied message rather than returning `None`. Mirrors [`Option::expect`].
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    /// [`get_mut`]: trait.Pool.html#tymethod.get_mut
+    /// [`Option::expect`]: https://doc.rust-lang.org/core/option/enum.Option.html#method.expect
+    ///
+    /// # Panics
+    ///
+    /// This function panics with `msg` if the key is out of range, stale, or points at an empty entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert(1);
+    ///
+    /// *pool.get_mut_expect(&key, "missing entity") += 1;
+    /// assert_eq!(pool[key], 2);
+    /// ```
+    #[track_caller]
+    pub fn get_mut_expect(&mut self, key: &PoolKey<I, G>, msg: &str) -> &mut T
+    {
+        self.get_mut(key).expect(msg)
+    }
+
+    /// Returns a mutable reference to the item, along with its current key, as [`key_for_index`] would rebuild it.
+    /// Returns `None` if the key is out of range, stale, or points at an empty entry.
+    ///
+    /// For a key which resolves, the returned key is equal to it, save that an untagged key, e.g. one unpacked by
+    /// [`PoolKey::from_u64`], comes back tagged with the pool's id under the `pool-id` feature.
+    ///
+    /// [`key_for_index`]: #method.key_for_index
+    /// [`PoolKey::from_u64`]: struct.PoolKey.html#method.from_u64
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert(1);
+    ///
+    /// let (current, item) = pool.get_mut_with_key(&key).unwrap();
+    /// *item += 1;
+    ///
+    /// assert_eq!(current, key);
+    /// assert_eq!(pool[key], 2);
+    /// ```
+    pub fn get_mut_with_key(&mut self, key: &PoolKey<I, G>) -> Option<(PoolKey<I, G>, &mut T)>
+    {
+        if !self.contains_key(key) { return None; }
+
+        let current = self.key_for_index(key.index.into_usize())?;
+        return self.get_mut(key).map(|item| (current, item));
+    }
+
+    /// Retrieves mutable references to several items at once.
+    /// Returns `None` if any key is out of range, stale, or points at an empty entry, or if two keys refer to the same slot.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// let key1 = pool.insert(1);
+    /// let key2 = pool.insert(2);
+    ///
+    /// if let Some([a, b]) = pool.get_disjoint_mut([key1, key2])
+    /// {
+    ///     std::mem::swap(a, b);
+    /// }
+    ///
+    /// assert_eq!(pool[key1], 2);
+    /// assert_eq!(pool[key2], 1);
+    /// assert!(pool.get_disjoint_mut([key1, key1]).is_none());
+    /// ```
+    pub fn get_disjoint_mut<const N: usize>(&mut self, keys: [PoolKey<I, G>; N]) -> Option<[&mut T; N]>
+    {
+        if !keys.iter().all(|key| self.contains_key(key)) { return None; }
+
+        // Rejects aliasing keys, as the same slot can't be borrowed mutably twice.
+        let entries = self.data.get_disjoint_mut(keys.map(|key| key.index.into_usize())).ok()?;
+
+        return Some(entries.map(|e| e.get_mut().expect("entry is occupied")));
+    }
+
+    /// Retrieves mutable references to several items at once, without checking the keys.
+    /// For hot paths whose keys are already known to be valid and distinct; otherwise use [`get_disjoint_mut`].
+    ///
+    /// With the `safe` feature the keys are checked anyway, panicking on the conditions below rather than causing undefined behavior.
+    ///
+    /// [`get_disjoint_mut`]: #method.get_disjoint_mut
+    ///
+    /// # Safety
+    ///
+    /// Calling this method is undefined behavior if:
+    ///
+    /// * any key is out of range, stale, points at an empty entry, or was issued by another pool,
+    ///   i.e. if [`contains_key`] would return `false` for it, or
+    /// * any two keys refer to the same slot, as that would hand out aliasing mutable references.
+    ///
+    /// [`contains_key`]: trait.Pool.html#tymethod.contains_key
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// let key1 = pool.insert(1);
+    /// let key2 = pool.insert(2);
+    ///
+    /// // Safety: both keys were just issued by this pool, and differ.
+    /// let [a, b] = unsafe { pool.get_many_unchecked_mut([key1, key2]) };
+    /// std::mem::swap(a, b);
+    ///
+    /// assert_eq!(pool[key1], 2);
+    /// assert_eq!(pool[key2], 1);
+    /// ```
+    pub unsafe fn get_many_unchecked_mut<const N: usize>(&mut self, keys: [PoolKey<I, G>; N]) -> [&mut T; N]
+    {
+        debug_assert!(keys.iter().all(|key| self.contains_key(key)), "get_many_unchecked_mut called with an invalid key");
+
+        #[cfg(feature = "safe")]
+        return self.get_disjoint_mut(keys).expect("get_many_unchecked_mut called with invalid or aliasing keys");
+
+        #[cfg(not(feature = "safe"))]
+        {
+            // Distinctness is checked in debug builds by the slice method itself.
+            let entries = unsafe { self.data.get_disjoint_unchecked_mut(keys.map(|key| key.index.into_usize())) };
+
+            return entries.map(|entry| match entry.get_mut()
+            {
+                Some(data) => data,
+                None       => unsafe { core::hint::unreachable_unchecked() },
+            });
+        }
+    }
+
+    /// Retrieves mutable references to two items at once. Shorthand for the common case of [`get_disjoint_mut`].
+    /// Returns `None` if either key is out of range, stale, or points at an empty entry, or if both keys refer to the same slot.
+    ///
+    /// [`get_disjoint_mut`]: #method.get_disjoint_mut
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// let key1 = pool.insert(1);
+    /// let key2 = pool.insert(2);
+    ///
+    /// if let Some((a, b)) = pool.get2_mut(&key1, &key2)
+    /// {
+    ///     *a += *b;
+    /// }
+    ///
+    /// assert_eq!(pool[key1], 3);
+    /// assert!(pool.get2_mut(&key1, &key1).is_none());
+    /// ```
+    pub fn get2_mut(&mut self, a: &PoolKey<I, G>, b: &PoolKey<I, G>) -> Option<(&mut T, &mut T)>
+    {
+        if !self.contains_key(a) || !self.contains_key(b) { return None; }
+
+        let (a, b) = (a.index.into_usize(), b.index.into_usize());
+        if a == b { return None; }
+
+        // Split between the two slots, so each half can be borrowed mutably on its own.
+        let (low, high) = self.data.split_at_mut(a.max(b));
+        let (low, high) = (low[a.min(b)].get_mut()?, high[0].get_mut()?);
+
+        return if a < b { Some((low, high)) } else { Some((high, low)) };
+    }
+
+    /// Swaps the items of two keys in place, leaving both keys valid.
+    /// Returns `false`, swapping nothing, unless both keys are valid. Swapping an item with itself does nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// let key1 = pool.insert("One");
+    /// let key2 = pool.insert("Two");
+    ///
+    /// assert!(pool.swap(&key1, &key2));
+    /// assert_eq!(pool[key1], "Two");
+    /// assert_eq!(pool[key2], "One");
+    /// ```
+    pub fn swap(&mut self, a: &PoolKey<I, G>, b: &PoolKey<I, G>) -> bool
+    {
+        if a.index == b.index { return self.contains_key(a) && self.contains_key(b); }
+
+        match self.get2_mut(a, b)
+        {
+            Some((a, b)) => { core::mem::swap(a, b); true }
+            None         => false,
+        }
+    }
+
+    /// Returns an iterator over the [`PoolKey`]s of the items in the pool, in ascending index order.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// pool.insert(1);
+    /// pool.insert(2);
+    ///
+    /// let keys: Vec<_> = pool.keys().collect();
+    /// for key in keys { pool.delete(&key); }
+    ///
+    /// assert!(pool.is_empty());
+    /// ```
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = PoolKey<I, G>> + '_
+    {
+        self.occupied.ones().filter_map(move |index| self.data[index].key(index, self.id))
+    }
+
+    /// Returns an iterator over references to the items in the pool, in ascending index order. Equivalent to [`iter`].
+    ///
+    /// [`iter`]: #method.iter
+    pub fn values(&self) -> Iter<'_, T, G>
+    {
+        self.iter()
+    }
+
+    /// Returns an iterator over mutable references to the items in the pool, in ascending index order. Equivalent to [`iter_mut`].
+    ///
+    /// [`iter_mut`]: #method.iter_mut
+    pub fn values_mut(&mut self) -> IterMut<'_, T, G>
+    {
+        self.iter_mut()
+    }
+
+    /// Returns an iterator which removes the items from the pool, yielding them by value.
+    ///
+    /// Once the iterator is dropped, even if it was not fully consumed, the pool is left empty as if [`cleared`].
+    /// Every previously issued [`PoolKey`] is invalidated.
+    ///
+    /// [`cleared`]: trait.Pool.html#tymethod.clear
+    /// [`PoolKey`]: struct.PoolKey.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// let key = pool.insert("Drain");
+    /// pool.insert("me!");
+    ///
+    /// let items: Vec<_> = pool.drain().collect();
+    ///
+    /// assert_eq!(items, ["Drain", "me!"]);
+    /// assert!(pool.is_empty());
+    /// assert!(pool.get(&key).is_none());
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_, T, I, G>
+    {
+        Drain { pool: self, index: 0 }
+    }
+
+    /// Moves the items into `out`, appended in index order, leaving the pool empty as if [`cleared`].
+    /// Every previously issued [`PoolKey`] is invalidated.
+    ///
+    /// Lets a caller recycle the items, and the buffer holding them, from one clear to the next, rather than dropping them.
+    ///
+    /// [`cleared`]: trait.Pool.html#tymethod.clear
+    /// [`PoolKey`]: struct.PoolKey.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let mut scratch = Vec::new();
+    ///
+    /// let key = pool.insert(String::from("Recycle"));
+    /// pool.insert(String::from("me!"));
+    ///
+    /// pool.clear_into(&mut scratch);
+    ///
+    /// assert_eq!(scratch, ["Recycle", "me!"]);
+    /// assert!(pool.is_empty());
+    /// assert!(pool.get(&key).is_none());
+    /// ```
+    pub fn clear_into(&mut self, out: &mut Vec<T>)
+    {
+        out.extend(self.drain());
+    }
+
+    /// Returns a cursor over the items, in index order, starting at the first.
+    /// Unlike the iterators, the cursor can remove the item it's on and carry on from there.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// pool.extend(1..=5);
+    ///
+    /// let mut cursor = pool.cursor_mut();
+    /// while let Some(item) = cursor.current()
+    /// {
+    ///     if *item % 2 == 0 { cursor.remove_current(); }
+    ///     else              { *item *= 10; cursor.move_next(); }
+    /// }
+    ///
+    /// assert!(pool.iter().eq(&[10, 30, 50]));
+    /// ```
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T, I, G>
+    {
+        CursorMut::new(self)
+    }
+
+    /// Retains only the items specified by the predicate, deleting the rest.
+    /// The predicate is passed the [`PoolKey`] of each item, along with a mutable reference to it.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// let key1 = pool.insert(1);
+    /// let key2 = pool.insert(2);
+    ///
+    /// pool.retain(|_, item| *item % 2 == 0);
+    ///
+    /// assert!(pool.get(&key1).is_none());
+    /// assert!(pool.get(&key2).is_some());
+    /// ```
+    pub fn retain<F: FnMut(PoolKey<I, G>, &mut T) -> bool>(&mut self, mut f: F)
+    {
+        // Walks a snapshot of the occupied slots, as removing an item clears its bit.
+        let occupied = self.occupied.clone();
+        for index in occupied.ones()
+        {
+            let entry = &mut self.data[index];
+            let (key, data) = match entry.key(index, self.id).zip(entry.get_mut())
+            {
+                Some(pair) => pair,
+                None       => continue,
+            };
+
+            if !f(key, data)
+            {
+                self.release(index);
+                self.occupied.remove(index);
+                self.count -= 1;
+            }
+        }
+    }
+
+    /// Retains only the items whose [`PoolKey`] the predicate keeps, deleting the rest.
+    /// Like [`retain`], but for when the decision is made elsewhere, e.g. against a set of keys to keep.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    /// [`retain`]: #method.retain
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// let key1 = pool.insert("Visible");
+    /// let key2 = pool.insert("Hidden");
+    ///
+    /// let visible: HashSet<_> = [key1].iter().copied().collect();
+    /// pool.retain_keys(|key| visible.contains(key));
+    ///
+    /// assert!(pool.get(&key1).is_some());
+    /// assert!(pool.get(&key2).is_none());
+    /// ```
+    pub fn retain_keys<F: FnMut(&PoolKey<I, G>) -> bool>(&mut self, mut keep: F)
+    {
+        self.retain(|key, _| keep(&key));
+    }
+
+    /// Removes the items matching the predicate, returning them in a new pool with capacity for exactly that many.
+    /// The new pool is growable if this one is.
+    ///
+    /// Extracted items get fresh keys from the new pool, in index order; their keys into this pool are invalidated.
+    /// Keys of the items left behind still resolve.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// let key1 = pool.insert(1);
+    /// let key2 = pool.insert(2);
+    ///
+    /// let evens = pool.extract_if(|item| *item % 2 == 0);
+    ///
+    /// assert!(evens.iter().eq(&[2]));
+    /// assert_eq!(pool.get(&key1), Some(&1));
+    /// assert!(pool.get(&key2).is_none());
+    /// ```
+    pub fn extract_if<F: FnMut(&T) -> bool>(&mut self, mut f: F) -> Self
+    {
+        let mut values = Vec::new();
+        let occupied = self.occupied.clone();
+        for index in occupied.ones()
+        {
+            if !self.data[index].get().is_some_and(&mut f) { continue; }
+
+            values.extend(self.release(index));
+            self.occupied.remove(index);
+            self.count -= 1;
+        }
+
+        let mut extracted: Self = values.into_iter().collect();
+        extracted.growable = self.growable;
+        return extracted;
+    }
+
+    /// Moves every item of `other` into this pool, leaving `other` empty as if [`cleared`].
+    /// This pool grows as needed to fit them, even if it isn't growable, as by [`reserve`].
+    ///
+    /// The items are placed in new slots, so they get new keys; returns `(old_key, new_key)` pairs, in `other`'s index order,
+    /// for remapping any keys held elsewhere. Keys into `other` are invalidated.
+    ///
+    /// [`cleared`]: trait.Pool.html#tymethod.clear
+    /// [`reserve`]: #method.reserve
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the new capacity exceeds the range of the pool's index type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(1);
+    /// let mut other = ObjectPool::new(10);
+    ///
+    /// pool.insert("Master");
+    /// let key = other.insert("Worker");
+    ///
+    /// let remap = pool.append(&mut other);
+    ///
+    /// assert_eq!(remap[0].0, key);
+    /// assert_eq!(pool.get(&remap[0].1), Some(&"Worker"));
+    /// assert!(other.is_empty());
+    /// ```
+    pub fn append(&mut self, other: &mut Self) -> Vec<(PoolKey<I, G>, PoolKey<I, G>)>
+    {
+        let old_keys: Vec<_> = other.keys().collect();
+        self.reserve(old_keys.len());
+
+        return old_keys.into_iter()
+            .zip(other.drain())
+            .map(|(old_key, value)| (old_key, self.insert(value)))
+            .collect();
+    }
+
+    /// Consumes the pool, returning a pool of the items transformed by `f`, in index order.
+    /// Slots, generations and the free list are carried over, so previously issued keys resolve to the transformed items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert("42");
+    ///
+    /// let pool = pool.map_into(|raw| raw.parse::<i32>().unwrap());
+    /// assert_eq!(pool.get(&key), Some(&42));
+    /// ```
+    pub fn map_into<U, F: FnMut(T) -> U>(self, mut f: F) -> ObjectPool<U, I, G>
+    {
+        ObjectPool {
+            count: self.count,
+            next: self.next,
+            free: self.free,
+            data: self.data.into_iter().map(|e| e.map(&mut f)).collect(),
+            occupied: self.occupied,
+            growable: self.growable,
+            base_generation: self.base_generation,
+            peak_next: self.peak_next,
+            retired: self.retired,
+            retired_unused: self.retired_unused,
+            id: self.id,
+            index: PhantomData,
+        }
+    }
+
+    /// Consumes the pool, returning its raw parts: the generation and item of every slot, the free slots in the order
+    /// they would be reused, the number of slots occupied since the last clear, and the number of items.
+    ///
+    /// Intended for custom serialization without the `serde` feature. See [`from_raw_parts`].
+    ///
+    /// [`from_raw_parts`]: #method.from_raw_parts
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(3);
+    /// let key = pool.insert('a');
+    /// pool.insert('b');
+    /// pool.delete(&key);
+    ///
+    /// let (data, free, next, count) = pool.into_raw_parts();
+    ///
+    /// assert_eq!(data, vec![(1, None), (1, Some('b')), (0, None)]);
+    /// assert_eq!(free, vec![0]);
+    /// assert_eq!((next, count), (2, 1));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn into_raw_parts(self) -> (Vec<(G::Raw, Option<T>)>, Vec<usize>, usize, usize)
+    {
+        let free = self.free.iter(&self.data).collect();
+        let data = self.data.into_iter().map(|e| (e.generation, e.into_data())).collect();
+
+        return (data, free, self.next, self.count);
+    }
+
+    /// Reconstructs a pool from the raw parts returned by [`into_raw_parts`].
+    /// Keys issued by the original pool resolve against the new one, unless the `pool-id` feature is enabled.
+    /// The new pool is not growable, and reuses slots in LIFO order.
+    ///
+    /// In debug builds, this function panics if the parts are inconsistent, e.g. the count doesn't match the occupied slots,
+    /// or a free slot is out of range or occupied. In release builds, inconsistent parts cause unspecified, though memory safe, behaviour.
+    ///
+    /// [`into_raw_parts`]: #method.into_raw_parts
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the number of slots exceeds the range of the pool's index type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool, PoolKey };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = PoolKey::from_u64(pool.insert("Persisted").as_u64()).unwrap();
+    ///
+    /// let (data, free, next, count) = pool.into_raw_parts();
+    /// let pool = ObjectPool::<_>::from_raw_parts(data, free, next, count);
+    ///
+    /// assert_eq!(pool.get(&key), Some(&"Persisted"));
+    /// ```
+    pub fn from_raw_parts(data: Vec<(G::Raw, Option<T>)>, free: Vec<usize>, next: usize, count: usize) -> Self
+    {
+        if data.len() > Self::max_capacity() { panic!("{}", PoolError::CapacityOverflow); }
+
+        debug_assert!(next <= data.len(), "next exceeds the number of slots");
+        debug_assert_eq!(count, data.iter().filter(|(_, item)| item.is_some()).count(), "count doesn't match the occupied slots");
+        debug_assert!(data[next..].iter().all(|(_, item)| item.is_none()), "slots at or beyond next are occupied");
+        debug_assert!(data.iter().all(|&(generation, ref item)| item.is_none() || G::new(generation).is_some()), "occupied slot with generation 0");
+        debug_assert!(
+            free.iter().enumerate().all(|(i, &index)| index < next && data[index].1.is_none() && !free[..i].contains(&index)),
+            "free slots must be distinct, vacant, and below next",
+        );
+
+        let mut pool = <Self as Pool<T, I, G>>::new(0);
+
+        // Slots created by a later grow must not reissue generations of slots the original pool may have dropped.
+        pool.base_generation = data.iter().map(|&(generation, _)| generation).max().unwrap_or_default();
+        pool.data = data.into_iter()
+            .enumerate()
+            .map(|(index, (generation, item))| {
+                let mut entry = PoolEntry::with_generation(generation);
+                if let Some(item) = item
+                {
+                    entry.slot = Slot::Occupied(item);
+                    pool.occupied.insert(index);
+                }
+                entry
+            })
+            .collect();
+
+        // Pushed in reverse, so the slots pop in the order given.
+        for &index in free.iter().rev() { pool.free.push(&mut pool.data, index); }
+
+        pool.next = next;
+        pool.count = count;
+        pool.recount_retired();
+
+        return pool;
+    }
+
+    /// Returns references to the items, in index order, for code expecting a slice-like snapshot.
+    /// Nothing is cloned; see [`into_values`] to take the items themselves.
+    ///
+    /// [`into_values`]: #method.into_values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// let key = pool.insert(1);
+    /// pool.insert(2);
+    /// pool.insert(3);
+    /// pool.delete(&key);
+    ///
+    /// assert_eq!(pool.as_vec(), [&2, &3]);
+    /// ```
+    pub fn as_vec(&self) -> Vec<&T>
+    {
+        let mut values = Vec::with_capacity(self.count);
+        values.extend(self.iter());
+
+        return values;
+    }
+
+    /// Consumes the pool, returning its items in index order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// let key = pool.insert(1);
+    /// pool.insert(2);
+    /// pool.delete(&key);
+    ///
+    /// assert_eq!(pool.into_values(), vec![2]);
+    /// ```
+    pub fn into_values(self) -> Vec<T>
+    {
+        let mut values = Vec::with_capacity(self.count);
+        values.extend(self);
+
+        return values;
+    }
+
+    // ====-====-====-====-====-==== //
+
+    /// Grows a growable pool once upfront for a batch of inserts, rather than doubling repeatedly.
+    fn grow_for(&mut self, additional: usize)
+    {
+        if self.growable
+        {
+            self.reserve(additional.min(Self::max_capacity() - self.data.len()));
+        }
+    }
+
+    /// Claims a specific vacant slot, so neither the free list nor `next` hands it out again.
+    fn claim_index(&mut self, index: usize)
+    {
+        if index < self.next
+        {
+            self.free.retain(&mut self.data, |free| free != index);
+        }
+        else
+        {
+            for skipped in self.next..index
+            {
+                if !self.data[skipped].is_retired() { self.free.push(&mut self.data, skipped); }
+                else                                { self.retired_unused -= 1; }
+            }
+            self.next = index + 1;
+        }
+    }
+}
+
+/// Returns a claimed but unfilled slot to the pool when dropped. Forgotten once the slot is filled.
+struct ReleaseOnUnwind<'a, T, I: PoolIndex, G: PoolGeneration>
+{
+    pool: &'a mut ObjectPool<T, I, G>,
+    index: usize,
+}
+
+impl<'a, T, I: PoolIndex, G: PoolGeneration> Drop for ReleaseOnUnwind<'a, T, I, G>
+{
+    fn drop(&mut self)
+    {
+        // Past the generation of the key handed to `f`, lest it resolve to the slot's next occupant.
+        self.pool.data[self.index].advance();
+        self.pool.release(self.index);
+    }
+}
+
+/// Implements for pools in any allocator with the `allocator_api` feature, and in the global one without.
+/// [`Pool`] needs a `Default` allocator, to create pools in.
+///
+/// [`Pool`]: trait.Pool.html
+macro_rules! impl_in_any_allocator
+{
+    (impl Pool<T, I> for ObjectPool $body:tt) => {
+        #[cfg(not(feature = "allocator_api"))]
+        impl<T, I: PoolIndex, G: PoolGeneration> Pool<T, I, G> for ObjectPool<T, I, G> $body
+
+        #[cfg(feature = "allocator_api")]
+        impl<T, I: PoolIndex, G: PoolGeneration, A: Allocator + Default> Pool<T, I, G> for ObjectPool<T, I, G, A> $body
+    };
+    (impl ObjectPool $body:tt) => {
+        #[cfg(not(feature = "allocator_api"))]
+        impl<T, I: PoolIndex, G: PoolGeneration> ObjectPool<T, I, G> $body
+
+        #[cfg(feature = "allocator_api")]
+        impl<T, I: PoolIndex, G: PoolGeneration, A: Allocator> ObjectPool<T, I, G, A> $body
+    };
+}
+
+impl_in_any_allocator!(impl ObjectPool
+{
+    /// Returns an iterator over references to the items in the pool, in ascending index order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert(1);
+    /// pool.insert(2);
+    /// pool.delete(&key);
+    /// pool.insert(3);
+    ///
+    /// // The reused slot comes first, as it has the lower index.
+    /// assert!(pool.iter().eq(&[3, 2]));
+    /// assert!(pool.iter().rev().eq(&[2, 3]));
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T, G>
+    {
+        Iter::new(&self.data, Some(self.occupied.ones()), self.count)
+    }
+
+    /// Returns an iterator over mutable references to the items in the pool, in ascending index order.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, G>
+    {
+        IterMut::new(&mut self.data, Some(self.occupied.ones()), self.count)
+    }
+
+    // ====-====-====-====-====-==== //
+
+    /// Returns the largest capacity supported by the pool's index type. See [`max_capacity`].
+    ///
+    /// [`max_capacity`]: #method.max_capacity
+    fn index_capacity() -> usize
+    {
+        return I::MAX_INDEX.saturating_add(1);
+    }
+
+    /// Appends `additional` vacant slots at the base generation. The pool is unchanged if the allocator can't provide them.
+    fn try_push_slots(&mut self, additional: usize) -> Result<(), PoolError>
+    {
+        self.data.try_reserve_exact(additional).map_err(|_| PoolError::AllocationFailed)?;
+
+        let generation = self.base_generation;
+        self.data.resize_with(self.data.len() + additional, || PoolEntry::with_generation(generation));
+
+        return Ok(());
+    }
+
+    /// Claims a slot for the next insert, growing the pool if allowed. Returns `None` if the pool is full.
+    fn claim_slot(&mut self) -> Option<usize>
+    {
+        let index = if let Some(index) = self.free.pop(&self.data)
+        {
+            index
+        }
+        else if let Some(index) = self.next_unused()
+        {
+            index
+        }
+        else if self.growable && self.data.len() < Self::index_capacity()
+        {
+            let additional = self.data.len().max(1).min(Self::index_capacity() - self.data.len());
+            if let Err(error) = self.try_push_slots(additional) { panic!("{}", error); }
+
+            self.next_unused().expect("reserved slots are unused")
+        }
+        else
+        {
+            return None;
+        };
+
+        // Catches a free list or `next` out of sync with the slots in debug builds, before an item is written over.
+        debug_assert!(
+            index < self.next && self.data[index].is_empty() && !self.data[index].is_reserved() && !self.occupied.contains(index),
+            "spool: claimed slot {} is not free; the free list or next is corrupt",
+            index,
+        );
+
+        return Some(index);
+    }
+
+    /// Claims the next never-occupied slot since the last clear, skipping retired ones.
+    fn next_unused(&mut self) -> Option<usize>
+    {
+        while self.next < self.data.len()
+        {
+            let index = self.next;
+            self.next += 1;
+
+            if !self.data[index].is_retired() { return Some(index); }
+            self.retired_unused -= 1;
+        }
+
+        return None;
+    }
+
+    /// Vacates the slot at `index`, returning its item, and frees it, or counts it as retired if its generations are exhausted.
+    fn release(&mut self, index: usize) -> Option<T>
+    {
+        let value = self.free.release(&mut self.data, index);
+        if self.data[index].is_retired() { self.retired += 1; }
+
+        return value;
+    }
+
+    /// Returns the number of retired slots, and of those at or beyond `next`, counted afresh.
+    /// Reserved slots below `next` are still awaiting their item, so don't count until released.
+    fn count_retired(&self) -> (usize, usize)
+    {
+        let unused = self.data[self.next..].iter().filter(|e| e.is_empty() && e.is_retired()).count();
+        let used = self.data[..self.next].iter().filter(|e| e.is_empty() && e.is_retired() && !e.is_reserved()).count();
+
+        return (used + unused, unused);
+    }
+
+    fn recount_retired(&mut self)
+    {
+        let (retired, unused) = self.count_retired();
+        self.retired = retired;
+        self.retired_unused = unused;
+    }
+
+    /// Returns the entry at an index the caller has already bounds checked.
+    /// Indexes unchecked, unless the `safe` feature is enabled.
+    #[inline]
+    fn entry_at(&self, index: usize) -> &PoolEntry<T, G>
+    {
+        debug_assert!(index < self.data.len());
+
+        #[cfg(feature = "safe")]
+        return &self.data[index];
+
+        #[cfg(not(feature = "safe"))]
+        return unsafe { self.data.get_unchecked(index) };
+    }
+
+    /// Returns the entry at an index the caller has already bounds checked, mutably.
+    /// Indexes unchecked, unless the `safe` feature is enabled.
+    #[inline]
+    fn entry_at_mut(&mut self, index: usize) -> &mut PoolEntry<T, G>
+    {
+        debug_assert!(index < self.data.len());
+
+        #[cfg(feature = "safe")]
+        return &mut self.data[index];
+
+        #[cfg(not(feature = "safe"))]
+        return unsafe { self.data.get_unchecked_mut(index) };
+    }
+});
+
+#[cfg(feature = "std")]
+impl<T, I: PoolIndex, G: PoolGeneration> ObjectPool<T, I, G>
+{
+    /// Moves the items down into the lowest slots, keeping their order, so they are contiguous.
+    /// Returns a map from the key of every item to its new key, which callers use to fix up stored keys.
+    ///
+    /// Keys of moved items are invalidated, the free list is emptied, and new items are placed after the last one.
+    /// Retired slots are never reused, so any below the last item remain as holes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key1 = pool.insert("One");
+    /// let key2 = pool.insert("Two");
+    /// pool.delete(&key1);
+    ///
+    /// let remap = pool.defragment();
+    ///
+    /// assert!(pool.get(&key2).is_none());
+    /// assert_eq!(pool.get(&remap[&key2]), Some(&"Two"));
+    /// ```
+    pub fn defragment(&mut self) -> HashMap<PoolKey<I, G>, PoolKey<I, G>>
+    {
+        let mut remap = HashMap::with_capacity(self.count);
+        let occupied: Vec<usize> = self.occupied.ones().collect();
+
+        // Everything below `target` is packed, so every slot from `target` up to the current item is vacant.
+        let mut target = 0;
+        for index in occupied
+        {
+            // The item's own slot may be on its final generation, which is only retired once vacated.
+            while target < index && self.data[target].is_retired() { target += 1; }
+
+            let key = self.data[index].key(index, self.id).expect("entry is occupied");
+            if target != index
+            {
+                let item = self.data[index].take().expect("entry is occupied");
+                self.data[target].set(item);
+
+                self.occupied.remove(index);
+                self.occupied.insert(target);
+            }
+
+            remap.insert(key, self.data[target].key(target, self.id).expect("entry is occupied"));
+            target += 1;
+        }
+
+        // Reservations are released, lest a slot handed out again still be marked as one.
+        for entry in &mut self.data[target..self.next] { entry.unreserve(); }
+
+        self.free.clear();
+        self.peak_next = self.high_water_mark();
+        self.next = target;
+        self.recount_retired();
+
+        return remap;
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Clone, I: PoolIndex, G: PoolGeneration> ObjectPool<T, I, G>
+{
+    /// Returns a copy of the pool with its items packed into the lowest slots, in index order, and no free slots.
+    /// Also returns a map from the key of every item in this pool to its key in the copy.
+    ///
+    /// The copy has the same capacity and settings, and is a distinct pool: keys of this pool must be remapped to be used with it.
+    /// Its generations start beyond those of this pool, so no stale key of this pool resolves against the copy either.
+    /// See `Clone` for an exact copy instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key1 = pool.insert("One");
+    /// let key2 = pool.insert("Two");
+    /// pool.delete(&key1);
+    ///
+    /// let (compact, remap) = pool.clone_compact();
+    ///
+    /// assert_eq!(compact.get(&remap[&key2]), Some(&"Two"));
+    /// assert!(!remap.contains_key(&key1));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn clone_compact(&self) -> (ObjectPool<T, I, G>, HashMap<PoolKey<I, G>, PoolKey<I, G>>)
+    {
+        // Retired slots are left out, lest a pool that retired one start its copy close to retirement.
+        let generation = self.data.iter().filter(|e| !e.is_retired()).map(|e| e.generation).max().unwrap_or_default();
+
+        let mut pool = ObjectPool {
+            count: 0,
+            next: 0,
+            free: FreeList::new(),
+            data: Vec::with_capacity(self.data.len()),
+            occupied: Occupancy::new(),
+            growable: self.growable,
+            base_generation: generation,
+            peak_next: 0,
+            retired: 0,
+            retired_unused: 0,
+            id: PoolId::unique(),
+            index: PhantomData,
+        }
+        .with_slot_reuse(self.slot_reuse());
+
+        let mut remap = HashMap::with_capacity(self.count);
+        for (key, item) in self.iter_with_keys()
+        {
+            let index = pool.data.len();
+            let mut entry = PoolEntry::with_generation(generation);
+            entry.set(item.clone());
+
+            remap.insert(key, entry.key(index, pool.id).expect("entry is occupied"));
+            pool.data.push(entry);
+            pool.occupied.insert(index);
+        }
+
+        pool.count = pool.data.len();
+        pool.next = pool.data.len();
+        pool.data.resize_with(self.data.len(), || PoolEntry::with_generation(generation));
+
+        return (pool, remap);
+    }
+}
+
+impl<P: DerefMut, I: PoolIndex, G: PoolGeneration> ObjectPool<Pin<P>, I, G>
+{
+    /// Returns a pinned mutable reference to the value behind a pinned pointer, such as a `Pin<Box<T>>`.
+    /// Returns `None` if the key is out of range, stale, or points at an empty entry.
+    ///
+    /// Items themselves are not pinned in place: growing the pool moves them to a new allocation,
+    /// and [`take`] moves them out. What stays put, for as long as the item is in the pool and beyond,
+    /// is the value behind each pinned pointer, which is why `!Unpin` values such as futures are stored that way.
+    ///
+    /// [`take`]: trait.Pool.html#tymethod.take
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::future::Future;
+    /// use std::pin::Pin;
+    /// use std::task::{ Context, Poll, Waker };
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut tasks: ObjectPool<Pin<Box<dyn Future<Output = i32>>>> = ObjectPool::new_growable(1);
+    /// let key = tasks.insert(Box::pin(async { 42 }));
+    ///
+    /// let mut context = Context::from_waker(Waker::noop());
+    /// let task = tasks.get_pin_mut(&key).unwrap();
+    ///
+    /// assert_eq!(task.poll(&mut context), Poll::Ready(42));
+    /// ```
+    pub fn get_pin_mut(&mut self, key: &PoolKey<I, G>) -> Option<Pin<&mut P::Target>>
+    {
+        return self.get_mut(key).map(Pin::as_mut);
+    }
+}
+
+/// The wire form of [`ObjectPool::to_bytes`]: live items, plus just enough to keep every earlier key from resolving wrongly.
+///
+/// Vacant slots all share one generation, the highest of any slot in use, so no stale key can match an item inserted later.
+/// Retired slots are listed, since they must never be reused.
+#[cfg(feature = "postcard")]
+#[derive(Serialize, Deserialize)]
+struct CompactPool<T, R>
+{
+    capacity: usize,
+    next: usize,
+    growable: bool,
+    reuse: SlotReuse,
+    vacant_generation: R,
+    retired: Vec<usize>,
+    items: Vec<(usize, R, T)>,
+    id: PoolId,
+}
+
+#[cfg(feature = "postcard")]
+impl<T, I: PoolIndex, G: PoolGeneration> ObjectPool<T, I, G>
+{
+    /// Encodes the pool with [`postcard`], as a compact binary form for storage or the wire.
+    ///
+    /// Only the live items are written, along with their keys' generations and a handful of fields,
+    /// so the encoding grows with the number of items rather than the capacity.
+    /// Decoded by [`from_bytes`], against which keys issued by this pool still resolve, and stale keys stay stale.
+    ///
+    /// [`postcard`]: https://docs.rs/postcard
+    /// [`from_bytes`]: #method.from_bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(100_000);
+    /// let key = pool.insert(String::from("Howdy!"));
+    ///
+    /// let bytes = pool.to_bytes().unwrap();
+    /// assert!(bytes.len() < 32);
+    ///
+    /// let pool = ObjectPool::<String>::from_bytes(&bytes).unwrap();
+    /// assert_eq!(pool[key], "Howdy!");
+    /// ```
+    pub fn to_bytes(&self) -> Result<Vec<u8>, postcard::Error>
+    where
+        T: Serialize,
+        G::Raw: Serialize,
+    {
+        let vacant_generation = self.data.iter().filter(|e| !e.is_retired()).map(|e| e.generation).max().unwrap_or_default();
+        let compact = CompactPool {
+            capacity: self.data.len(),
+            next: self.next,
+            growable: self.growable,
+            reuse: self.slot_reuse(),
+            vacant_generation: vacant_generation.max(self.base_generation),
+            retired: self.data.iter().enumerate().filter(|(_, e)| e.is_retired() && e.is_empty()).map(|(index, _)| index).collect(),
+            items: self.occupied.ones().map(|index| (index, self.data[index].generation, self.data[index].get().expect("slot is occupied"))).collect(),
+            id: self.id,
+        };
+
+        return postcard::to_allocvec(&compact);
+    }
+
+    /// Decodes a pool encoded by [`to_bytes`].
+    /// Returns an error if the bytes aren't a well formed encoding, including one whose slots are out of range or inconsistent,
+    /// or whose capacity can't be allocated.
+    ///
+    /// The free list is rebuilt rather than decoded, so freed slots may be reused in a different order than by the original pool.
+    ///
+    /// [`to_bytes`]: #method.to_bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, postcard::Error>
+    where
+        T: serde::de::DeserializeOwned,
+        G::Raw: serde::de::DeserializeOwned,
+    {
+        let compact: CompactPool<T, G::Raw> = postcard::from_bytes(bytes)?;
+
+        let in_range = |index: usize| index < compact.next;
+        let consistent = compact.capacity <= Self::max_capacity()
+            && compact.next <= compact.capacity
+            && compact.vacant_generation < G::RETIRED
+            && compact.retired.iter().all(|&index| index < compact.capacity)
+            && compact.items.iter().all(|&(index, generation, _)| in_range(index) && G::new(generation).is_some() && generation < G::NULL.get());
+        if !consistent { return Err(postcard::Error::DeserializeBadEncoding); }
+
+        // The capacity costs a few bytes to encode whatever its size, so allocate fallibly, lest a hostile one abort the process.
+        let mut pool = <Self as Pool<T, I, G>>::new(0);
+        pool.data.try_reserve_exact(compact.capacity).map_err(|_| postcard::Error::DeserializeBadEncoding)?;
+        pool.data.resize_with(compact.capacity, || PoolEntry::with_generation(compact.vacant_generation));
+
+        for &index in &compact.retired { pool.data[index].generation = G::RETIRED; }
+        for (index, generation, item) in compact.items
+        {
+            let entry = &mut pool.data[index];
+            if !entry.is_empty() { return Err(postcard::Error::DeserializeBadEncoding); }
+
+            entry.generation = generation;
+            entry.slot = Slot::Occupied(item);
+            pool.occupied.insert(index);
+            pool.count += 1;
+        }
+
+        // Pushed in reverse, so the slots pop in ascending order.
+        for index in (0..compact.next).rev()
+        {
+            let entry = &pool.data[index];
+            if entry.is_empty() && !entry.is_retired() { pool.free.push(&mut pool.data, index); }
+        }
+
+        pool.free.set_reuse(compact.reuse);
+        pool.next = compact.next;
+        pool.recount_retired();
+        pool.growable = compact.growable;
+        pool.base_generation = compact.vacant_generation;
+        pool.id = compact.id;
+
+        return Ok(pool);
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T, I: PoolIndex, G: PoolGeneration> ObjectPool<T, I, G>
+{
+    /// Returns a parallel iterator over the items, skipping empty slots. Available with the `rayon` feature.
+    /// Work is split over the slots, so items are visited in no particular order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rayon::prelude::*;
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let pool: ObjectPool<i32> = (1..=100).collect();
+    ///
+    /// assert_eq!(pool.par_iter().sum::<i32>(), 5050);
+    /// ```
+    pub fn par_iter(&self) -> impl ParallelIterator<Item = &T> + '_ where T: Sync
+    {
+        // Slots at or beyond `next` have never been occupied since the last clear.
+        self.data[..self.next].par_iter().filter_map(PoolEntry::get)
+    }
+
+    /// Returns a parallel iterator over the items, mutably, skipping empty slots. Available with the `rayon` feature.
+    /// Work is split over the slots, so items are visited in no particular order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rayon::prelude::*;
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool: ObjectPool<i32> = (1..=3).collect();
+    /// pool.par_iter_mut().for_each(|item| *item *= 10);
+    ///
+    /// assert!(pool.iter().eq(&[10, 20, 30]));
+    /// ```
+    pub fn par_iter_mut(&mut self) -> impl ParallelIterator<Item = &mut T> + '_ where T: Send
+    {
+        self.data[..self.next].par_iter_mut().filter_map(PoolEntry::get_mut)
+    }
+}
+
+/// Zero-copy access to an archived pool, checking keys the same way [`ObjectPool`] does.
+///
+/// [`ObjectPool`]: struct.ObjectPool.html
+#[cfg(feature = "rkyv")]
+impl<T: rkyv::Archive, I: PoolIndex> ArchivedObjectPool<T, I, NonZeroU32>
+{
+    /// Returns the maximum capacity of the pool.
+    pub fn capacity(&self) -> usize { self.data.len() }
+
+    /// Returns the number of items currently held by the pool.
+    pub fn len(&self) -> usize { self.count.to_native() as usize }
+
+    /// Returns `true` if the pool holds no items.
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Returns a reference to the archived item corresponding to the [`PoolKey`].
+    /// Returns `None` if the key is out of range, stale, or points at an empty entry.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    pub fn get(&self, key: &PoolKey<I>) -> Option<&rkyv::Archived<T>>
+    {
+        if !self.id.admits(key.pool_id) { return None; }
+
+        let entry = self.data.get(key.index.into_usize())?;
+        if entry.generation.to_native() != key.generation.get() { return None; }
+
+        match entry.slot
+        {
+            ArchivedSlot::Occupied(ref item) => Some(item),
+            ArchivedSlot::Vacant(_)          => None,
+        }
+    }
+
+    /// Returns `true` if the [`PoolKey`] referenced corresponds to an item in the pool.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    pub fn contains_key(&self, key: &PoolKey<I>) -> bool
+    {
+        self.get(key).is_some()
+    }
+}
+
+impl<T, I, G: PoolGeneration> IntoIterator for ObjectPool<T, I, G>
+{
+    type Item = T;
+    type IntoIter = IntoIter<T, G>;
+
+    /// Consumes the pool, returning an iterator over its items by value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// pool.insert("Move");
+    /// let key = pool.insert("Nope");
+    /// pool.insert("Me!");
+    ///
+    /// pool.delete(&key);
+    ///
+    /// let items: Vec<_> = pool.into_iter().collect();
+    /// assert_eq!(items, ["Move", "Me!"]);
+    /// ```
+    fn into_iter(self) -> IntoIter<T, G>
+    {
+        IntoIter { inner: self.data.into_iter() }
+    }
+}
+
+// Implemented by hand, as an empty pool holds no `T`, so needn't require `T: Default`.
+impl<T, I, G: PoolGeneration> Default for ObjectPool<T, I, G>
+{
+    /// Returns a new pool with no capacity, which allocates nothing. See [`new_empty`].
+    ///
+    /// [`new_empty`]: struct.ObjectPool.html#method.new_empty
+    fn default() -> Self
+    {
+        Self {
+            count: 0,
+            next: 0,
+            free: FreeList::new(),
+            data: Vec::new(),
+            occupied: Occupancy::new(),
+            growable: false,
+            base_generation: G::Raw::default(),
+            peak_next: 0,
+            retired: 0,
+            retired_unused: 0,
+            id: PoolId::unique(),
+            index: PhantomData,
+        }
+    }
+}
+
+// The traits below are implemented by hand too, as derives would also bound the index type, which the pool only holds as a marker.
+impl<T: Clone, I, G: PoolGeneration> Clone for ObjectPool<T, I, G>
+{
+    fn clone(&self) -> Self
+    {
+        Self {
+            count: self.count,
+            next: self.next,
+            free: self.free,
+            data: self.data.clone(),
+            occupied: self.occupied.clone(),
+            growable: self.growable,
+            base_generation: self.base_generation,
+            peak_next: self.peak_next,
+            retired: self.retired,
+            retired_unused: self.retired_unused,
+            id: self.id,
+            index: PhantomData,
+        }
+    }
+}
+
+impl<T: PartialEq, I, G: PoolGeneration> PartialEq for ObjectPool<T, I, G>
+{
+    fn eq(&self, other: &Self) -> bool
+    {
+        self.count == other.count
+            && self.next == other.next
+            && self.free == other.free
+            && self.data == other.data
+            && self.occupied == other.occupied
+            && self.growable == other.growable
+            && self.base_generation == other.base_generation
+            && self.peak_next == other.peak_next
+            && self.id == other.id
+    }
+}
+
+impl<T: Eq, I, G: PoolGeneration> Eq for ObjectPool<T, I, G> {}
+
+impl<T: Hash, I, G: PoolGeneration> Hash for ObjectPool<T, I, G>
+{
+    fn hash<H: Hasher>(&self, state: &mut H)
+    {
+        self.count.hash(state);
+        self.next.hash(state);
+        self.free.hash(state);
+        self.data.hash(state);
+        self.occupied.hash(state);
+        self.growable.hash(state);
+        self.base_generation.hash(state);
+        self.peak_next.hash(state);
+        self.id.hash(state);
+    }
+}
+
+impl<T, I: PoolIndex, G: PoolGeneration> FromIterator<T> for ObjectPool<T, I, G>
+{
+    /// Returns a pool holding the items of the iterator, with capacity for exactly that many.
+    /// Items are inserted, and later iterated, in order.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the number of items exceeds the range of the pool's index type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let pool: ObjectPool<i32> = (0..5).collect();
+    ///
+    /// assert_eq!(pool.len(), 5);
+    /// assert_eq!(pool.capacity(), 5);
+    /// assert!(pool.iter().eq(&[0, 1, 2, 3, 4]));
+    /// ```
+    fn from_iter<It: IntoIterator<Item = T>>(iter: It) -> Self
+    {
+        let data: Vec<PoolEntry<T, G>> = iter.into_iter()
+            .map(|value| {
+                let mut entry = PoolEntry::new();
+                entry.set(value);
+                entry
+            })
+            .collect();
+
+        if data.len() > Self::max_capacity() { panic!("{}", PoolError::CapacityOverflow); }
+
+        let mut pool = <Self as Pool<T, I, G>>::new(0);
+        pool.count = data.len();
+        pool.next = data.len();
+        pool.occupied = (0..data.len()).collect();
+        pool.data = data;
+        return pool;
+    }
+}
+
+// Only for the default index type, so the pool type needn't be annotated, the same as `new`.
+impl<T> From<Vec<T>> for ObjectPool<T>
+{
+    /// Returns a pool holding the items of the vector, with capacity for exactly that many.
+    /// The item at position `i` goes into slot `i`, under the first generation, so [`into_values`] gives back the same vector.
+    ///
+    /// [`into_values`]: struct.ObjectPool.html#method.into_values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let pool = ObjectPool::from(vec!["a", "b", "c"]);
+    ///
+    /// assert_eq!(pool.len(), 3);
+    /// assert_eq!(pool.into_values(), vec!["a", "b", "c"]);
+    /// ```
+    fn from(values: Vec<T>) -> Self
+    {
+        return values.into_iter().collect();
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for ObjectPool<T>
+{
+    /// Returns a pool holding the items of the array, with capacity for exactly that many.
+    /// The item at position `i` goes into slot `i`, under the first generation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let pool = ObjectPool::from([1, 2, 3]);
+    /// assert!(pool.iter().eq(&[1, 2, 3]));
+    /// ```
+    fn from(values: [T; N]) -> Self
+    {
+        return IntoIterator::into_iter(values).collect();
+    }
+}
+
+impl<T, I: PoolIndex, G: PoolGeneration> Extend<T> for ObjectPool<T, I, G>
+{
+    /// Inserts the items of the iterator, in order.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the pool becomes full, unless the pool is growable. See [`insert`].
+    ///
+    /// [`insert`]: trait.Pool.html#tymethod.insert
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new_growable(1);
+    /// pool.insert(0);
+    ///
+    /// pool.extend(1..5);
+    ///
+    /// assert_eq!(pool.len(), 5);
+    /// assert!(pool.iter().eq(&[0, 1, 2, 3, 4]));
+    /// ```
+    fn extend<It: IntoIterator<Item = T>>(&mut self, iter: It)
+    {
+        let iter = iter.into_iter();
+        self.grow_for(iter.size_hint().0);
+
+        for value in iter { self.insert(value); }
+    }
+}
+
+/// Shows the live items as a map from key to item, along with a summary of the pool.
+/// Vacant slots are left out, see [`debug_layout`] for those.
+///
+/// [`debug_layout`]: struct.ObjectPool.html#method.debug_layout
+///
+/// # Examples
+///
+/// ```
+/// use spool::{ ObjectPool, Pool };
+///
+/// let mut pool = ObjectPool::new(10_000);
+/// pool.insert("Howdy!");
+///
+/// let output = format!("{:?}", pool);
+/// assert!(output.starts_with("ObjectPool { capacity: 10000, len: 1, free: 0, items: {PoolKey"));
+/// assert!(output.ends_with(": \"Howdy!\"} }"));
+/// ```
+impl<T: fmt::Debug, I: PoolIndex + fmt::Debug, G: PoolGeneration> fmt::Debug for ObjectPool<T, I, G>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        f.debug_struct("ObjectPool")
+            .field("capacity", &self.capacity())
+            .field("len", &self.count)
+            .field("free", &self.free.len())
+            .field("items", &DebugItems(self))
+            .finish()
+    }
+}
+
+struct DebugItems<'a, T, I, G: PoolGeneration>(&'a ObjectPool<T, I, G>);
+
+impl<'a, T: fmt::Debug, I: PoolIndex + fmt::Debug, G: PoolGeneration> fmt::Debug for DebugItems<'a, T, I, G>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        f.debug_map().entries(self.0.iter_with_keys()).finish()
+    }
+}
+
+struct DebugLayout<'a, T, I, G: PoolGeneration>(&'a ObjectPool<T, I, G>);
+
+impl<'a, T: fmt::Debug, I: fmt::Debug, G: PoolGeneration> fmt::Debug for DebugLayout<'a, T, I, G>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let pool = self.0;
+        f.debug_struct("ObjectPool")
+            .field("count", &pool.count)
+            .field("next", &pool.next)
+            .field("free", &pool.free)
+            .field("data", &pool.data)
+            .field("occupied", &pool.occupied)
+            .field("growable", &pool.growable)
+            .field("base_generation", &pool.base_generation)
+            .field("peak_next", &pool.peak_next)
+            .field("id", &pool.id)
+            .field("index", &pool.index)
+            .finish()
+    }
+}
+
+impl<'a, T, I: PoolIndex, G: PoolGeneration> IntoIterator for &'a ObjectPool<T, I, G>
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, G>;
+
+    fn into_iter(self) -> Iter<'a, T, G> { self.iter() }
+}
+
+impl<'a, T, I: PoolIndex, G: PoolGeneration> IntoIterator for &'a mut ObjectPool<T, I, G>
+{
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T, G>;
+
+    fn into_iter(self) -> IterMut<'a, T, G> { self.iter_mut() }
+}
+
+impl<T, I: PoolIndex, G: PoolGeneration> Index<PoolKey<I, G>> for ObjectPool<T, I, G>
+{
+    type Output = T;
+
+    /// Returns a reference to the item corresponding to the [`PoolKey`].
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the key is invalid or stale. See [`get`] for a non-panicking alternative.
+    ///
+    /// [`get`]: trait.Pool.html#tymethod.get
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert("Howdy!");
+    ///
+    /// assert_eq!(pool[key], "Howdy!");
+    /// ```
+    fn index(&self, key: PoolKey<I, G>) -> &T
+    {
+        self.get(&key).expect("invalid or stale PoolKey")
+    }
+}
+
+impl<T, I: PoolIndex, G: PoolGeneration> IndexMut<PoolKey<I, G>> for ObjectPool<T, I, G>
+{
+    /// Returns a mutable reference to the item corresponding to the [`PoolKey`].
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the key is invalid or stale. See [`get_mut`] for a non-panicking alternative.
+    ///
+    /// [`get_mut`]: trait.Pool.html#tymethod.get_mut
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert(1);
+    ///
+    /// pool[key] += 1;
+    /// assert_eq!(pool[key], 2);
+    /// ```
+    fn index_mut(&mut self, key: PoolKey<I, G>) -> &mut T
+    {
+        self.get_mut(&key).expect("invalid or stale PoolKey")
+    }
+}
+
+// ===-===-===-===-===-===-===-===-===-===-===-===-=== //
+
+/// An iterator over references to the items of an [`ObjectPool`].
+///
+/// Created by [`ObjectPool::iter`]. Iterates in index order, from either end, and knows exactly how many items remain.
+///
+/// [`ObjectPool`]: struct.ObjectPool.html
+/// [`ObjectPool::iter`]: struct.ObjectPool.html#method.iter
+#[derive(Debug)]
+pub struct Iter<'a, T, G: PoolGeneration = NonZeroU32>
+{
+    inner: core::slice::Iter<'a, PoolEntry<T, G>>,
+    /// The occupied slots, if tracked, so vacant runs can be skipped rather than visited.
+    occupied: Option<Ones<'a>>,
+    /// The slots `inner` spans, as `start..end`.
+    start: usize,
+    end: usize,
+    remaining: usize,
+}
+
+// Implemented by hand, as cloning the iterator only copies references, so needn't require `T: Clone`.
+impl<'a, T, G: PoolGeneration> Clone for Iter<'a, T, G>
+{
+    fn clone(&self) -> Self
+    {
+        Self {
+            inner: self.inner.clone(),
+            occupied: self.occupied.clone(),
+            start: self.start,
+            end: self.end,
+            remaining: self.remaining,
+        }
+    }
+}
+
+impl<'a, T, G: PoolGeneration> Iter<'a, T, G>
+{
+    /// Returns an iterator over the `len` occupied entries of `data`.
+    pub(crate) fn new(data: &'a [PoolEntry<T, G>], occupied: Option<Ones<'a>>, len: usize) -> Self
+    {
+        Self {
+            inner: data.iter(),
+            occupied,
+            start: 0,
+            end: data.len(),
+            remaining: len,
+        }
+    }
+
+    /// Returns the next entry from the front which may be occupied, skipping vacant runs if occupancy is tracked.
+    fn next_entry(&mut self) -> Option<&'a PoolEntry<T, G>>
+    {
+        match self.occupied
+        {
+            Some(ref mut occupied) =>
+            {
+                let index = occupied.next()?;
+                let entry = self.inner.nth(index - self.start);
+                self.start = index + 1;
+                entry
+            }
+            None => self.inner.next(),
+        }
+    }
+
+    /// Returns the next entry from the back which may be occupied, skipping vacant runs if occupancy is tracked.
+    fn next_entry_back(&mut self) -> Option<&'a PoolEntry<T, G>>
+    {
+        match self.occupied
+        {
+            Some(ref mut occupied) =>
+            {
+                let index = occupied.next_back()?;
+                let entry = self.inner.nth_back(self.end - index - 1);
+                self.end = index;
+                entry
+            }
+            None => self.inner.next_back(),
+        }
+    }
+}
+
+impl<'a, T, G: PoolGeneration> Iterator for Iter<'a, T, G>
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T>
+    {
+        if self.remaining == 0 { return None; }
+
+        while let Some(entry) = self.next_entry()
+        {
+            if let Some(data) = entry.get()
+            {
+                self.remaining -= 1;
+                return Some(data);
+            }
+        }
+
+        return None;
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T, G: PoolGeneration> DoubleEndedIterator for Iter<'a, T, G>
+{
+    fn next_back(&mut self) -> Option<&'a T>
+    {
+        if self.remaining == 0 { return None; }
+
+        while let Some(entry) = self.next_entry_back()
+        {
+            if let Some(data) = entry.get()
+            {
+                self.remaining -= 1;
+                return Some(data);
+            }
+        }
+
+        return None;
+    }
+}
+
+impl<'a, T, G: PoolGeneration> ExactSizeIterator for Iter<'a, T, G> {}
+
+/// An iterator over mutable references to the items of an [`ObjectPool`].
+///
+/// Created by [`ObjectPool::iter_mut`]. Iterates in index order, from either end, and knows exactly how many items remain.
+///
+/// [`ObjectPool`]: struct.ObjectPool.html
+/// [`ObjectPool::iter_mut`]: struct.ObjectPool.html#method.iter_mut
+#[derive(Debug)]
+pub struct IterMut<'a, T, G: PoolGeneration = NonZeroU32>
+{
+    inner: core::slice::IterMut<'a, PoolEntry<T, G>>,
+    /// The occupied slots, if tracked, so vacant runs can be skipped rather than visited.
+    occupied: Option<Ones<'a>>,
+    /// The slots `inner` spans, as `start..end`.
+    start: usize,
+    end: usize,
+    remaining: usize,
+}
+
+impl<'a, T, G: PoolGeneration> IterMut<'a, T, G>
+{
+    /// Returns an iterator over the `len` occupied entries of `data`.
+    pub(crate) fn new(data: &'a mut [PoolEntry<T, G>], occupied: Option<Ones<'a>>, len: usize) -> Self
+    {
+        Self {
+            end: data.len(),
+            inner: data.iter_mut(),
+            occupied,
+            start: 0,
+            remaining: len,
+        }
+    }
+
+    /// Returns the next entry from the front which may be occupied, with its index, skipping vacant runs if occupancy is tracked.
+    fn next_entry(&mut self) -> Option<(usize, &'a mut PoolEntry<T, G>)>
+    {
+        let index = match self.occupied
+        {
+            Some(ref mut occupied) => occupied.next()?,
+            None                   => self.start,
+        };
+
+        let entry = self.inner.nth(index - self.start)?;
+        self.start = index + 1;
+        return Some((index, entry));
+    }
+
+    /// Returns the next entry from the back which may be occupied, with its index, skipping vacant runs if occupancy is tracked.
+    fn next_entry_back(&mut self) -> Option<(usize, &'a mut PoolEntry<T, G>)>
+    {
+        let index = match self.occupied
+        {
+            Some(ref mut occupied) => occupied.next_back()?,
+            None                   => self.end.checked_sub(1)?,
+        };
+
+        let entry = self.inner.nth_back(self.end - index - 1)?;
+        self.end = index;
+        return Some((index, entry));
+    }
+
+    /// Returns the next item from the front, with its index and generation.
+    pub(crate) fn next_indexed(&mut self) -> Option<(usize, G, &'a mut T)>
+    {
+        if self.remaining == 0 { return None; }
+
+        while let Some((index, entry)) = self.next_entry()
+        {
+            let generation = G::new(entry.generation);
+            if let Some(data) = entry.get_mut()
+            {
+                self.remaining -= 1;
+                return Some((index, generation.expect("occupied slots have a non-zero generation"), data));
+            }
+        }
+
+        return None;
+    }
+
+    /// Returns the next item from the back, with its index and generation.
+    pub(crate) fn next_indexed_back(&mut self) -> Option<(usize, G, &'a mut T)>
+    {
+        if self.remaining == 0 { return None; }
+
+        while let Some((index, entry)) = self.next_entry_back()
+        {
+            let generation = G::new(entry.generation);
+            if let Some(data) = entry.get_mut()
+            {
+                self.remaining -= 1;
+                return Some((index, generation.expect("occupied slots have a non-zero generation"), data));
+            }
+        }
+
+        return None;
+    }
+}
+
+impl<'a, T, G: PoolGeneration> Iterator for IterMut<'a, T, G>
+{
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T>
+    {
+        self.next_indexed().map(|(_, _, data)| data)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T, G: PoolGeneration> DoubleEndedIterator for IterMut<'a, T, G>
+{
+    fn next_back(&mut self) -> Option<&'a mut T>
+    {
+        self.next_indexed_back().map(|(_, _, data)| data)
+    }
+}
+
+impl<'a, T, G: PoolGeneration> ExactSizeIterator for IterMut<'a, T, G> {}
+
+/// The iterator behind [`ObjectPool::iter_mut_with_keys`], pairing each item with its key.
+///
+/// [`ObjectPool::iter_mut_with_keys`]: struct.ObjectPool.html#method.iter_mut_with_keys
+struct IterMutWithKeys<'a, T, I, G: PoolGeneration>
+{
+    inner: IterMut<'a, T, G>,
+    id: PoolId,
+    index: PhantomData<I>,
+}
+
+impl<'a, T, I: PoolIndex, G: PoolGeneration> IterMutWithKeys<'a, T, I, G>
+{
+    fn key(&self, index: usize, generation: G) -> PoolKey<I, G>
+    {
+        PoolKey::tagged(I::from_usize(index).expect("slot index exceeds the range of the pool's index type"), generation, self.id)
+    }
+}
+
+impl<'a, T, I: PoolIndex, G: PoolGeneration> Iterator for IterMutWithKeys<'a, T, I, G>
+{
+    type Item = (PoolKey<I, G>, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        let (index, generation, data) = self.inner.next_indexed()?;
+        return Some((self.key(index, generation), data));
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T, I: PoolIndex, G: PoolGeneration> DoubleEndedIterator for IterMutWithKeys<'a, T, I, G>
+{
+    fn next_back(&mut self) -> Option<Self::Item>
+    {
+        let (index, generation, data) = self.inner.next_indexed_back()?;
+        return Some((self.key(index, generation), data));
+    }
+}
+
+/// A draining iterator over the items of an [`ObjectPool`].
+///
+/// Created by [`ObjectPool::drain`]. Holds the pool mutably borrowed until dropped, at which point the pool is empty.
+///
+/// [`ObjectPool`]: struct.ObjectPool.html
+/// [`ObjectPool::drain`]: struct.ObjectPool.html#method.drain
+#[derive(Debug)]
+pub struct Drain<'a, T, I: PoolIndex = usize, G: PoolGeneration = NonZeroU32>
+{
+    pool: &'a mut ObjectPool<T, I, G>,
+    index: usize,
+}
+
+impl<'a, T, I: PoolIndex, G: PoolGeneration> Iterator for Drain<'a, T, I, G>
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T>
+    {
+        // Slots at or beyond `next` have never been occupied since the last clear.
+        while self.index < self.pool.next
+        {
+            let entry = &mut self.pool.data[self.index];
+            self.index += 1;
+
+            if !entry.is_empty()
+            {
+                self.pool.occupied.remove(self.index - 1);
+                self.pool.count -= 1;
+                return entry.invalidate();
+            }
+        }
+
+        return None;
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        (self.pool.count, Some(self.pool.count))
+    }
+}
+
+impl<'a, T, I: PoolIndex, G: PoolGeneration> Drop for Drain<'a, T, I, G>
+{
+    fn drop(&mut self)
+    {
+        self.pool.clear();
+    }
+}
+
+/// A cursor over the items of an [`ObjectPool`], in index order, which can remove the item it's on.
+///
+/// Created by [`ObjectPool::cursor_mut`]. Once moved past the last item, the cursor is on nothing, and stays there.
+///
+/// [`ObjectPool`]: struct.ObjectPool.html
+/// [`ObjectPool::cursor_mut`]: struct.ObjectPool.html#method.cursor_mut
+#[derive(Debug)]
+pub struct CursorMut<'a, T, I: PoolIndex = usize, G: PoolGeneration = NonZeroU32>
+{
+    pool: &'a mut ObjectPool<T, I, G>,
+    index: usize,
+}
+
+impl<'a, T, I: PoolIndex, G: PoolGeneration> CursorMut<'a, T, I, G>
+{
+    fn new(pool: &'a mut ObjectPool<T, I, G>) -> Self
+    {
+        let mut cursor = Self { pool, index: 0 };
+        cursor.skip_vacant();
+
+        return cursor;
+    }
+
+    /// Returns the key of the item the cursor is on, or `None` past the last item.
+    pub fn key(&self) -> Option<PoolKey<I, G>>
+    {
+        self.pool.data.get(self.index)?.key(self.index, self.pool.id)
+    }
+
+    /// Returns the item the cursor is on, or `None` past the last item.
+    pub fn current(&mut self) -> Option<&mut T>
+    {
+        self.pool.data.get_mut(self.index)?.get_mut()
+    }
+
+    /// Moves the cursor on to the next item. Does nothing past the last item.
+    pub fn move_next(&mut self)
+    {
+        if self.index < self.pool.next
+        {
+            self.index += 1;
+            self.skip_vacant();
+        }
+    }
+
+    /// Removes the item the cursor is on, freeing its slot, and moves on to the next item.
+    /// Returns `None`, doing nothing, past the last item.
+    pub fn remove_current(&mut self) -> Option<T>
+    {
+        if self.index >= self.pool.next || self.pool.data[self.index].is_empty() { return None; }
+
+        let value = self.pool.release(self.index);
+        self.pool.occupied.remove(self.index);
+        self.pool.count -= 1;
+
+        self.move_next();
+        return value;
+    }
+
+    // ====-====-====-====-====-==== //
+
+    /// Moves the cursor forward to the first item at or after its position.
+    /// Slots at or beyond `next` have never been occupied since the last clear.
+    fn skip_vacant(&mut self)
+    {
+        while self.index < self.pool.next && self.pool.data[self.index].is_empty()
+        {
+            self.index += 1;
+        }
+    }
+}
+
+/// An owning iterator over the items of an [`ObjectPool`].
+///
+/// Created by [`ObjectPool::into_iter`]. Items not yet yielded are dropped along with the iterator.
+///
+/// [`ObjectPool`]: struct.ObjectPool.html
+/// [`ObjectPool::into_iter`]: struct.ObjectPool.html#method.into_iter
+#[derive(Clone, Debug)]
+pub struct IntoIter<T, G: PoolGeneration = NonZeroU32>
+{
+    inner: alloc::vec::IntoIter<PoolEntry<T, G>>,
+}
+
+impl<T, G: PoolGeneration> Iterator for IntoIter<T, G>
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T>
+    {
+        self.inner.find_map(|e| e.into_data())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+impl_in_any_allocator!(impl Pool<T, I> for ObjectPool
+{
+    /// Returns a new, empty pool. Preallocated with specified capacity.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the capacity exceeds the range of the pool's index type. See [`try_new`] for a non-panicking alternative.
+    ///
+    /// [`try_new`]: struct.ObjectPool.html#method.try_new
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let pool: ObjectPool<i32> = ObjectPool::new(10);
+    /// assert_eq!(pool.capacity(), 10);
+    /// ```
+    fn new(capacity: usize) -> Self
+    {
+        #[cfg(feature = "allocator_api")]
+        return Self::new_in(capacity, A::default());
+
+        #[cfg(not(feature = "allocator_api"))]
+        return match Self::try_new(capacity)
+        {
+            Ok(pool)   => pool,
+            Err(error) => panic!("{}", error),
+        };
+    }
+
+    // ====-====-====-====-====-==== //
+
+    /// Returns the maximum capacity of the pool.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let pool: ObjectPool<i32> = ObjectPool::new(10);
+    /// assert_eq!(pool.capacity(), 10);
+    /// ```
+    fn capacity(&self) -> usize { self.data.len() }
+
+    /// Returns the number of items currently held by the pool.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// let key = pool.insert("Count me!");
+    /// assert_eq!(pool.len(), 1);
+    ///
+    /// pool.delete(&key);
+    /// assert_eq!(pool.len(), 0);
+    /// ```
+    fn len(&self) -> usize { self.count }
+
+    /// Returns `true` if the pool holds no items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// assert!(pool.is_empty());
+    ///
+    /// pool.insert("Not empty anymore!");
+    /// assert!(!pool.is_empty());
+    /// ```
+    fn is_empty(&self) -> bool { self.len() == 0 }
+
+    // ====-====-====-====-====-==== //
+
+    /// Returns a [`PoolKey`] corresponding to the inserted item.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    ///
+    /// # Panics
+    ///
+    /// This function panics if pool is full, unless the pool is growable. See [`try_insert`] for a non-panicking alternative.
+    ///
+    /// [`try_insert`]: #method.try_insert
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let key = pool.insert("Howdy!");
+    /// ```
+    fn insert(&mut self, value: T) -> PoolKey<I, G>
+    {
+        match self.try_insert(value)
+        {
+            Ok(key) => key,
+            Err(_)  => panic_full("ObjectPool", self.capacity()),
+        }
+    }
+
+    /// Returns a [`PoolKey`] corresponding to the inserted item, or [`PoolFull`] if the pool is full and not growable.
+    /// The rejected value is handed back inside the [`PoolFull`].
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    /// [`PoolFull`]: struct.PoolFull.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(1);
+    ///
+    /// let key = pool.try_insert("Howdy!");
+    /// assert!(key.is_ok());
+    ///
+    /// let full = pool.try_insert("Anyone home?");
+    /// assert_eq!(full.unwrap_err().into_inner(), "Anyone home?");
+    /// ```
+    fn try_insert(&mut self, value: T) -> Result<PoolKey<I, G>, PoolFull<T>>
+    {
+        let index = match self.claim_slot()
+        {
+            Some(index) => index,
+            None        => return Err(PoolFull(value)),
+        };
+
+        let generation = self.data[index].set(value);
+        self.occupied.insert(index);
+
+        self.count += 1;
+
+        let index = I::from_usize(index).expect("slot index exceeds the range of the pool's index type");
+        return Ok(PoolKey::tagged(index, generation, self.id));
+    }
+
+    /// Retrieves an Option<&T> corresponding to the [`PoolKey`] referenced.
+    /// Returns `None` if the key is out of range, stale, or points at an empty entry.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// let key1 = pool.insert("I am going to be removed!");
+    /// let key2 = pool.insert("I am going to remain!");
+    ///
+    /// pool.delete(&key1);
+    ///
+    /// assert!(pool.get(&key1).is_none());
+    /// assert!(pool.get(&key2).is_some());
+    /// ```
+    fn get(&self, key: &PoolKey<I, G>) -> Option<&T>
+    {
+        let index = key.index.into_usize();
+        if index >= self.data.len() || !self.id.admits(key.pool_id) { return None; }
+        else
+        {
+            let entry = self.entry_at(index);
+            if !entry.matches(key) { None } else { entry.get() }
+        }
+    }
+
+    /// Retrieves an Option<&mut T> corresponding to the [`PoolKey`] referenced.
+    /// Returns `None` if the key is out of range, stale, or points at an empty entry.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// let key1 = pool.insert("I am going to be removed!");
+    /// let key2 = pool.insert("I am going to remain!");
+    ///
+    /// pool.delete(&key1);
+    ///
+    /// assert!(pool.get_mut(&key1).is_none());
+    /// assert!(pool.get_mut(&key2).is_some());
+    /// ```
+    fn get_mut(&mut self, key: &PoolKey<I, G>) -> Option<&mut T>
+    {
+        let index = key.index.into_usize();
+        if index >= self.data.len() || !self.id.admits(key.pool_id) { return None; }
+        else
+        {
+            let entry = self.entry_at_mut(index);
+            if !entry.matches(key) { None } else { entry.get_mut() }
+        }
+    }
+
+    /// Returns `true` if the [`PoolKey`] referenced corresponds to an item in the pool.
+    /// Performs the same checks as [`get`], without producing a reference.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    /// [`get`]: #method.get
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// let key = pool.insert("Am I in here?");
+    /// assert!(pool.contains_key(&key));
+    ///
+    /// pool.delete(&key);
+    /// assert!(!pool.contains_key(&key));
+    /// ```
+    fn contains_key(&self, key: &PoolKey<I, G>) -> bool
+    {
+        let index = key.index.into_usize();
+        if index >= self.data.len() || !self.id.admits(key.pool_id) { return false; }
+        else
+        {
+            let entry = self.entry_at(index);
+            entry.matches(key) && !entry.is_empty()
+        }
+    }
+
+    /// Extracts an Option<T> corresponding to the [`PoolKey`] referenced.
+    /// When an entry is been [`taken`] it is removed from the pool.
+    /// Returns `None` if the key is out of range, stale, or points at an empty entry.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    /// [`taken`]: #method.take
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// let key = pool.insert("Take me!");
+    ///
+    /// assert!(pool.take(&key).is_some());
+    /// assert!(pool.get(&key).is_none());
+    /// ```
+    fn take(&mut self, key: &PoolKey<I, G>) -> Option<T>
+    {
+        let index = key.index.into_usize();
+        if index >= self.data.len() || !self.id.admits(key.pool_id) { return None; }
+        else
+        {
+            let entry = self.entry_at(index);
+            if !entry.matches(key) || entry.is_empty() { return None; }
+
+            self.count -= 1;
+            self.occupied.remove(index);
+            self.release(index)
+        }
+    }
+
+    /// Deletes an entry corresponding to the [`PoolKey`] referenced.
+    /// When an entry is been [`deleted`] it is removed, however it will not be returned.
+    /// Returns `true` if an item was deleted. Does nothing, returning `false`, if the key is out of range, stale, or points at an empty entry,
+    /// e.g. when deleting the same key twice.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    /// [`take`]: #method.take
+    /// [`deleted`]: #method.delete
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// let key = pool.insert("Delete me!");
+    ///
+    /// assert!(pool.delete(&key));
+    /// assert!(!pool.delete(&key));
+    ///
+    /// assert!(pool.get(&key).is_none());
+    /// ```
+    fn delete(&mut self, key: &PoolKey<I, G>) -> bool
+    {
+        let index = key.index.into_usize();
+        if index >= self.data.len() || !self.id.admits(key.pool_id) { return false; }
+        else
+        {
+            let entry = self.entry_at(index);
+            if !entry.matches(key) || entry.is_empty() { return false; }
+
+            self.release(index);
+            self.occupied.remove(index);
+            self.count -= 1;
+
+            return true;
+        }
+    }
+
+    /// Deletes all entries.
+    /// No entries will be returned, and every previously issued [`PoolKey`] is invalidated.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    ///
+    /// let key1 = pool.insert("Delete me!");
+    /// let key2 = pool.insert("Delete me too!");
+    /// let key3 = pool.insert("Delete me too!");
+    ///
+    /// pool.clear();
+    ///
+    /// assert!(pool.get(&key1).is_none());
+    /// assert!(pool.get(&key2).is_none());
+    /// assert!(pool.get(&key2).is_none());
+    /// ```
+    fn clear(&mut self)
+    {
+        // Advance generations too, so no key issued before the clear can match a later insert.
+        for index in self.occupied.ones() { self.data[index].invalidate(); }
+
+        // The clear ends any reservations too, so every retired slot now counts, and all of them are at or beyond `next`.
+        let mut released = 0;
+        for entry in &mut self.data[..self.next]
+        {
+            entry.unreserve();
+            if entry.is_retired() && entry.is_empty() { released += 1; }
+        }
+        self.retired = released + self.retired_unused;
+        self.retired_unused = self.retired;
+
+        self.occupied.clear();
+        self.free.clear();
+        self.peak_next = self.peak_next.max(self.next);
+        self.next = 0;
+        self.count = 0;
+    }
+});
+
+#[cfg(feature = "allocator_api")]
+impl<T, I: PoolIndex, G: PoolGeneration, A: Allocator> ObjectPool<T, I, G, A>
+{
+    /// Returns a new, empty pool, preallocated with specified capacity in the given allocator.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the capacity exceeds the range of the pool's index type. See [`try_new_in`] for a non-panicking alternative.
+    ///
+    /// [`try_new_in`]: #method.try_new_in
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::Global;
+    /// use std::num::NonZeroU32;
+    /// use spool::{ ObjectPool, Pool };
+    ///
+    /// let mut pool: ObjectPool<i32, usize, NonZeroU32, Global> = ObjectPool::new_in(10, Global);
+    ///
+    /// let key = pool.insert(1);
+    /// assert_eq!(pool.get(&key), Some(&1));
+    /// ```
+    pub fn new_in(capacity: usize, alloc: A) -> Self
+    {
+        match Self::try_new_in(capacity, alloc)
+        {
+            Ok(pool)   => pool,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Returns a new, empty pool, preallocated with specified capacity in the given allocator.
+    /// Returns [`PoolError::CapacityOverflow`] if the capacity exceeds the range of the pool's index type.
+    ///
+    /// [`PoolError::CapacityOverflow`]: enum.PoolError.html#variant.CapacityOverflow
+    pub fn try_new_in(capacity: usize, alloc: A) -> Result<Self, PoolError>
+    {
+        if capacity > Self::index_capacity() { return Err(PoolError::CapacityOverflow); }
+
+        return Ok(Self {
+            count: 0,
+            next: 0,
+            free: FreeList::new(),
+            data: {
+                let mut data = Vec::with_capacity_in(capacity, alloc);
+                data.resize_with(capacity, PoolEntry::new);
+                data
+            },
+            occupied: Occupancy::new(),
+            growable: false,
+            base_generation: G::Raw::default(),
+            peak_next: 0,
+            retired: 0,
+            retired_unused: 0,
+            id: PoolId::unique(),
+            index: PhantomData,
+        });
+    }
+
+    /// Returns a new, empty, growable pool, preallocated with specified capacity in the given allocator.
+    /// Grows in the same allocator once full.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the capacity exceeds the range of the pool's index type.
+    pub fn new_growable_in(capacity: usize, alloc: A) -> Self
+    {
+        let mut pool = Self::new_in(capacity, alloc);
+        pool.growable = true;
+        return pool;
+    }
+
+    /// Returns the allocator the pool keeps its slots in.
+    pub fn allocator(&self) -> &A { self.data.allocator() }
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    mod object_pool
+    {
+        mod new
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn correctly_initializes_a_pool()
+            {
+                let pool: ObjectPool<i32> = ObjectPool::new(10);
+
+                assert_eq!(pool.capacity(), 10);
+                assert_eq!(pool.count, 0);
+                assert_eq!(pool.next, 0);
+                assert_eq!(pool.free.len(), 0);
+                assert_eq!(pool.data.len(), pool.capacity());
+            }
+        }
+
+        mod try_new
+        {
+            use crate::error::PoolError;
+            use super::super::{
+                Pool,
+                PoolKey,
+                ObjectPool,
+            };
+
+            #[test]
+            fn accepts_capacity_within_range_of_index_type()
+            {
+                let pool = ObjectPool::<i32, u16>::try_new(u16::MAX as usize + 1);
+
+                assert!(pool.is_ok());
+                assert_eq!(pool.unwrap().capacity(), u16::MAX as usize + 1);
+            }
+
+            #[test]
+            fn rejects_capacity_beyond_range_of_index_type()
+            {
+                let pool = ObjectPool::<i32, u16>::try_new(u16::MAX as usize + 2);
+                assert_eq!(pool.unwrap_err(), PoolError::CapacityOverflow);
+            }
+
+            #[test]
+            #[should_panic(expected = "capacity exceeds the range of the pool's index type")]
+            fn new_panics_on_capacity_beyond_range_of_index_type()
+            {
+                let _ = ObjectPool::<i32, u8>::new(1000);
+            }
+
+            #[test]
+            fn issues_keys_of_the_pool_index_type()
+            {
+                let mut pool = ObjectPool::<i32, u16>::try_new(10).unwrap();
+                let keys: Vec<PoolKey<u16>> = (0..10).map(|i| pool.insert(i)).collect();
+
+                for (i, key) in keys.iter().enumerate()
+                {
+                    assert_eq!(key.index as usize, i);
+                    assert_eq!(pool.get(key), Some(&(i as i32)));
+                }
+
+                pool.delete(&keys[4]);
+                assert!(pool.get(&keys[4]).is_none());
+                assert_eq!(pool.insert(100).index, 4);
+            }
+
+            #[test]
+            fn narrow_keys_are_smaller()
+            {
+                assert!(std::mem::size_of::<PoolKey<u16>>() < std::mem::size_of::<PoolKey<usize>>());
+            }
+        }
+
+        mod new_growable
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn correctly_initializes_a_pool()
+            {
+                let pool: ObjectPool<i32> = ObjectPool::new_growable(10);
+
+                assert!(pool.is_growable());
+                assert_eq!(pool.capacity(), 10);
+                assert_eq!(pool.count, 0);
+                assert_eq!(pool.next, 0);
+                assert_eq!(pool.free.len(), 0);
+                assert_eq!(pool.data.len(), pool.capacity());
+            }
+
+            #[test]
+            fn grows_past_initial_capacity_keeping_keys_valid()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new_growable(4);
+                let keys: Vec<_> = (0..100).map(|i| pool.insert(i)).collect();
+
+                assert_eq!(pool.len(), 100);
+                assert_eq!(pool.capacity(), 128, "Expected capacity to double whenever full.");
+
+                for (i, key) in keys.iter().enumerate()
+                {
+                    assert_eq!(pool.get(key), Some(&(i as i32)), "Expected key {} to remain valid across growth.", i);
+                }
+            }
+
+            #[test]
+            fn grows_from_zero_capacity()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new_growable(0);
+                let key = pool.insert(100);
+
+                assert_eq!(pool.capacity(), 1);
+                assert_eq!(pool.get(&key), Some(&100));
+            }
+
+            #[test]
+            fn stops_growing_at_range_of_index_type()
+            {
+                let mut pool = ObjectPool::<i32, u8>::try_new_growable(100).unwrap();
+                for i in 0..256 { pool.insert(i); }
+
+                assert_eq!(pool.capacity(), 256, "Expected growth to be capped by the index type.");
+                assert!(pool.try_insert(256).is_err(), "Expected pool to be full at the range of its index type.");
+            }
+
+            #[test]
+            fn reuses_freed_slots_before_growing()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new_growable(2);
+                let key = pool.insert(1);
+                pool.insert(2);
+
+                pool.delete(&key);
+                pool.insert(3);
+
+                assert_eq!(pool.capacity(), 2, "Expected capacity to be unchanged.");
+            }
+        }
+
+        mod len
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn tracks_live_items_through_insert_delete_and_take()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                assert_eq!(pool.len(), 0, "Expected new pool to be empty.");
+                assert!(pool.is_empty());
+
+                let key1 = pool.insert(100);
+                let key2 = pool.insert(200);
+                let key3 = pool.insert(300);
+                assert_eq!(pool.len(), 3, "Expected len to count inserted items.");
+                assert!(!pool.is_empty());
+
+                pool.delete(&key1);
+                assert_eq!(pool.len(), 2, "Expected len to be decremented by delete.");
+
+                pool.take(&key2);
+                assert_eq!(pool.len(), 1, "Expected len to be decremented by take.");
+
+                pool.delete(&key1);
+                pool.take(&key2);
+                assert_eq!(pool.len(), 1, "Expected stale keys to leave len unchanged.");
+
+                pool.take(&key3);
+                assert_eq!(pool.len(), 0, "Expected len to be 0 once every item is removed.");
+                assert!(pool.is_empty());
+                assert_eq!(pool.capacity(), 10, "Expected capacity to be unaffected by len.");
+            }
+        }
+
+        mod insert
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn correctly_updates_pool_state()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(100);
+
+                assert!(!pool.data[key.index].is_empty());
+                assert_eq!(pool.data[key.index].get(), Some(&100));
+                assert_eq!(pool.capacity(), 10);
+                assert_eq!(pool.count, 1);
+                assert_eq!(pool.next, 1);
+                assert_eq!(pool.free.len(), 0);
+                assert_eq!(pool.data.len(), pool.capacity());
+
+                pool.delete(&key);
+
+                let key = pool.insert(200);
+
+                assert!(!pool.data[key.index].is_empty());
+                assert_eq!(pool.data[key.index].get(), Some(&200));
+                assert_eq!(pool.capacity(), 10);
+                assert_eq!(pool.count, 1);
+                assert_eq!(pool.next, 1);
+                assert_eq!(pool.free.len(), 0);
+                assert_eq!(pool.data.len(), pool.capacity());
+            }
+
+            #[test]
+            fn returns_valid_key_pointing_to_expected_data()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(100);
+
+                assert_eq!(key.index, 0, "Expected index of first inserted element to be 0.");
+                assert_eq!(key.generation.get(), 1, "Expected generation of first inserted element to be 1.");
+                assert_eq!(pool.data[key.index].generation, key.generation.get(), "Expected generation of stored item to match key.");
+                assert!(!pool.data[key.index].is_empty(), "Expected data at key index to be Some().");
+                assert_eq!(*pool.data[key.index].get().unwrap(), 100, "Expected value at key index to be 100.");
+            }
+
+            #[test]
+            #[should_panic(expected = "at capacity")]
+            fn should_panic_if_full()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                for i in 0..10 { pool.insert(i); }
+
+                pool.insert(100);
+            }
+        }
+
+        mod try_insert
+        {
+            use crate::error::PoolFull;
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn returns_key_pointing_to_inserted_data()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.try_insert(100);
+
+                assert!(key.is_ok(), "Expected insertion into empty pool to succeed.");
+                assert_eq!(pool.get(&key.unwrap()), Some(&100));
+                assert_eq!(pool.count, 1);
+            }
+
+            #[test]
+            fn returns_err_with_value_if_full()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                for i in 0..10 { pool.try_insert(i).unwrap(); }
+
+                assert_eq!(pool.free.len(), 0);
+                assert_eq!(pool.next, pool.capacity());
+
+                let result = pool.try_insert(100);
+                assert_eq!(result, Err(PoolFull(100)), "Expected rejected value to be handed back.");
+                assert_eq!(pool.count, 10, "Expected count to be unchanged.");
+                assert_eq!(pool.next, 10, "Expected next to be unchanged.");
+            }
+
+            #[test]
+            fn succeeds_again_once_a_slot_is_freed()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let keys: Vec<_> = (0..10).map(|i| pool.try_insert(i).unwrap()).collect();
+
+                assert!(pool.try_insert(100).is_err());
+
+                pool.delete(&keys[3]);
+
+                let key = pool.try_insert(100);
+                assert!(key.is_ok(), "Expected insertion into freed slot to succeed.");
+
+                let key = key.unwrap();
+                assert_eq!(key.index, keys[3].index, "Expected freed slot to be reused.");
+                assert_eq!(pool.get(&key), Some(&100));
+                assert!(pool.try_insert(200).is_err(), "Expected pool to be full again.");
+            }
+        }
+
+        mod get
+        {
+            use std::num::NonZeroU32;
+            use super::super::{
+                Pool,
+                PoolKey,
+                ObjectPool,
+            };
+
+            #[test]
+            fn returns_some_reference_to_entry_specified()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key1 = pool.insert(100);
+                let key2 = pool.insert(200);
+                let key3 = pool.insert(300);
+
+                // Out of order 'cause :shrug:
+                let get2 = pool.get(&key2);
+                let get1 = pool.get(&key1);
+                let get3 = pool.get(&key3);
+
+                assert!(get1.is_some());
+                assert_eq!(*get1.unwrap(), 100);
+
+                assert!(get2.is_some());
+                assert_eq!(*get2.unwrap(), 200);
+
+                assert!(get3.is_some());
+                assert_eq!(*get3.unwrap(), 300);
+            }
+
+            #[test]
+            fn returns_none_if_key_has_invalid_index()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                pool.insert(100);
+
+                let key_at_cap = PoolKey::new(10, NonZeroU32::new(1).unwrap());
+                let get_at_cap = pool.get(&key_at_cap);
+                assert!(get_at_cap.is_none());
+
+                let key_over_cap = PoolKey::new(1000, NonZeroU32::new(1).unwrap());
+                let get_over_cap = pool.get(&key_over_cap);
+                assert!(get_over_cap.is_none());
+            }
+
+            #[test]
+            fn returns_none_if_generation_mismatch()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(100);
+
+                pool.data[key.index].generation = 42;
+
+                let get = pool.get(&key);
+                assert!(get.is_none());
+            }
+
+            #[test]
+            fn returns_none_if_data_is_none()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(100);
+
+                pool.data[key.index].take();
+
+                let get = pool.get(&key);
+                assert!(get.is_none());
+            }
+        }
+
+        mod get_mut
+        {
+            use std::num::NonZeroU32;
+            use super::super::{
+                Pool,
+                PoolKey,
+                ObjectPool,
+            };
+
+            #[test]
+            fn returns_some_reference_to_entry_specified()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key1 = pool.insert(100);
+                let key2 = pool.insert(200);
+                let key3 = pool.insert(300);
+
+                // Out of order 'cause :shrug:
+                let get2 = pool.get_mut(&key2);
+                assert!(get2.is_some());
+                assert_eq!(*get2.unwrap(), 200);
+
+                let get1 = pool.get_mut(&key1);
+                assert!(get1.is_some());
+                assert_eq!(*get1.unwrap(), 100);
+
+                let get3 = pool.get_mut(&key3);
+                assert!(get3.is_some());
+                assert_eq!(*get3.unwrap(), 300);
+            }
+
+            #[test]
+            fn returns_none_if_key_has_invalid_index()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                pool.insert(100);
+
+                let key_at_cap = PoolKey::new(10, NonZeroU32::new(1).unwrap());
+                let get_at_cap = pool.get_mut(&key_at_cap);
+                assert!(get_at_cap.is_none());
+
+                let key_over_cap = PoolKey::new(1000, NonZeroU32::new(1).unwrap());
+                let get_over_cap = pool.get_mut(&key_over_cap);
+                assert!(get_over_cap.is_none());
+            }
+
+            #[test]
+            fn returns_none_if_generation_mismatch()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(100);
+
+                pool.data[key.index].generation = 42;
+
+                let get = pool.get_mut(&key);
+                assert!(get.is_none());
+            }
+
+            #[test]
+            fn returns_none_if_data_is_none()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(100);
+
+                pool.data[key.index].take();
+
+                let get = pool.get_mut(&key);
+                assert!(get.is_none());
+            }
+        }
+
+        mod contains_key
+        {
+            use std::num::NonZeroU32;
+            use super::super::{
+                Pool,
+                PoolKey,
+                ObjectPool,
+            };
+
+            #[test]
+            fn returns_true_for_live_entries()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key1 = pool.insert(100);
+                let key2 = pool.insert(200);
+
+                assert!(pool.contains_key(&key1));
+                assert!(pool.contains_key(&key2));
+            }
+
+            #[test]
+            fn returns_false_if_key_has_invalid_index()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                pool.insert(100);
+
+                let key_at_cap = PoolKey::new(10, NonZeroU32::new(1).unwrap());
+                assert!(!pool.contains_key(&key_at_cap));
+
+                let key_over_cap = PoolKey::new(1000, NonZeroU32::new(1).unwrap());
+                assert!(!pool.contains_key(&key_over_cap));
+            }
+
+            #[test]
+            fn returns_false_if_generation_mismatch()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(100);
+
+                let mut bad_key = key;
+                bad_key.generation = NonZeroU32::new(42).unwrap();
+
+                assert!(!pool.contains_key(&bad_key));
+                assert!(pool.contains_key(&key));
+            }
+
+            #[test]
+            fn returns_false_for_freed_slot_even_if_index_is_reused()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let old_key = pool.insert(100);
+
+                pool.delete(&old_key);
+                assert!(!pool.contains_key(&old_key), "Expected freed key to be absent.");
+
+                let new_key = pool.insert(200);
+                assert_eq!(old_key.index, new_key.index, "Expected freed slot to be reused.");
+                assert!(!pool.contains_key(&old_key), "Expected stale key to be absent after reuse.");
+                assert!(pool.contains_key(&new_key));
+            }
+        }
+
+        mod take
+        {
+            use std::num::NonZeroU32;
+            use super::super::{
+                Pool,
+                PoolKey,
+                ObjectPool,
+            };
+
+            #[test]
+            fn replaces_item_with_none_and_pushes_index_to_free()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(100);
+
+                let old_count = pool.count;
+                let old_free_len = pool.free.len();
+
+                let taken = pool.take(&key);
+
+                assert!(pool.data[key.index].is_empty(), "Expected data to be set to None.");
+                assert_eq!(pool.data[key.index].generation, key.generation.get(), "Expected generation to remain unchanged.");
+                assert_eq!(pool.count, old_count - 1, "Expected count to be decremented.");
+                assert_eq!(pool.free.len(), old_free_len + 1, "Expected free list length to be incremented.");
+
+                let free_item = pool.free.peek();
+                assert!(free_item.is_some());
+                assert_eq!(free_item.unwrap(), key.index, "Expected key index to be most recent addition to free list.");
+
+                assert!(taken.is_some());
+                assert_eq!(taken.unwrap(), 100, "Expected taken value to match what was inserted.");
+            }
+
+            #[test]
+            fn returns_none_if_key_has_invalid_index()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(100);
+
+                let old_count = pool.count;
+                let old_free_len = pool.free.len();
+
+                let key_at_cap = PoolKey::new(1000, NonZeroU32::new(1).unwrap());
+                let taken = pool.take(&key_at_cap);
+
+                assert!(!pool.data[key.index].is_empty(), "Expected data to be unchanged.");
+                assert!(taken.is_none(), "Expected taken value to be None.");
+                assert_eq!(pool.data[key.index].generation, key.generation.get(), "Expected generation to remain unchanged.");
+                assert_eq!(pool.count, old_count, "Expected count to be unchanged.");
+                assert_eq!(pool.free.len(), old_free_len, "Expected free list length to be unchanged.");
+
+
+                let key_over_cap = PoolKey::new(1000, NonZeroU32::new(1).unwrap());
+                let taken = pool.take(&key_over_cap);
+
+                assert!(!pool.data[key.index].is_empty(), "Expected data to be unchanged.");
+                assert!(taken.is_none(), "Expected taken value to be None.");
+                assert_eq!(pool.data[key.index].generation, key.generation.get(), "Expected generation to remain unchanged.");
+                assert_eq!(pool.count, old_count, "Expected count to be unchanged.");
+                assert_eq!(pool.free.len(), old_free_len, "Expected free list length to be unchanged.");
+            }
+
+            #[test]
+            fn returns_none_if_generation_mismatch()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(100);
+
+                let old_count = pool.count;
+                let old_free_len = pool.free.len();
+
+                let mut bad_key = key;
+                bad_key.generation = NonZeroU32::new(100).unwrap();
+                let taken = pool.take(&bad_key);
+
+                assert!(taken.is_none(), "Expected taken value to be None.");
+                assert!(!pool.data[key.index].is_empty(), "Expected data to be unchanged.");
+                assert_eq!(pool.data[key.index].generation, key.generation.get(), "Expected generation to remain unchanged.");
+                assert_eq!(pool.count, old_count, "Expected count to be unchanged.");
+                assert_eq!(pool.free.len(), old_free_len, "Expected free list length to be unchanged.");
+            }
+
+            #[test]
+            fn returns_none_if_data_is_none()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(100);
+
+                let old_count = pool.count;
+                let old_free_len = pool.free.len();
+
+                pool.data[key.index].take();
+                let taken = pool.take(&key);
+
+                assert!(taken.is_none(), "Expected taken value to be None.");
+                assert_eq!(pool.data[key.index].generation, key.generation.get(), "Expected generation to remain unchanged.");
+                assert_eq!(pool.count, old_count, "Expected count to be unchanged.");
+                assert_eq!(pool.free.len(), old_free_len, "Expected free list length to be unchanged.");
+            }
+        }
+
+        mod delete
+        {
+            use std::num::NonZeroU32;
+            use super::super::{
+                Pool,
+                PoolKey,
+                ObjectPool,
+            };
+
+            #[test]
+            fn replaces_item_with_none_and_pushes_index_to_free()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(100);
+
+                let old_count = pool.count;
+                let old_free_len = pool.free.len();
+
+                pool.delete(&key);
+
+                assert!(pool.data[key.index].is_empty(), "Expected data to be set to None.");
+                assert_eq!(pool.data[key.index].generation, key.generation.get(), "Expected generation to remain unchanged.");
+                assert_eq!(pool.count, old_count - 1, "Expected count to be decremented.");
+                assert_eq!(pool.free.len(), old_free_len + 1, "Expected free list length to be incremented.");
+
+                let free_item = pool.free.peek();
+                assert!(free_item.is_some());
+                assert_eq!(free_item.unwrap(), key.index, "Expected key index to be most recent addition to free list.");
+            }
+
+            #[test]
+            fn reports_whether_an_item_was_deleted()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(100);
+
+                assert!(pool.delete(&key), "Expected deleting a live item to return true.");
+                assert!(!pool.delete(&key), "Expected deleting the same key twice to return false.");
+                assert!(!pool.delete(&PoolKey::new(1000, NonZeroU32::new(1).unwrap())), "Expected an out of range key to return false.");
+                assert_eq!(pool.len(), 0);
+            }
+
+            #[test]
+            fn does_nothing_if_key_has_invalid_index()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(100);
+
+                let old_count = pool.count;
+                let old_free_len = pool.free.len();
+
+                let key_at_cap = PoolKey::new(1000, NonZeroU32::new(1).unwrap());
+                pool.delete(&key_at_cap);
+
+                assert!(!pool.data[key.index].is_empty(), "Expected data to be unchanged.");
+                assert_eq!(pool.data[key.index].generation, key.generation.get(), "Expected generation to remain unchanged.");
+                assert_eq!(pool.count, old_count, "Expected count to be unchanged.");
+                assert_eq!(pool.free.len(), old_free_len, "Expected free list length to be unchanged.");
+
+
+                let key_over_cap = PoolKey::new(1000, NonZeroU32::new(1).unwrap());
+                pool.delete(&key_over_cap);
+
+                assert!(!pool.data[key.index].is_empty(), "Expected data to be unchanged.");
+                assert_eq!(pool.data[key.index].generation, key.generation.get(), "Expected generation to remain unchanged.");
+                assert_eq!(pool.count, old_count, "Expected count to be unchanged.");
+                assert_eq!(pool.free.len(), old_free_len, "Expected free list length to be unchanged.");
+            }
+
+            #[test]
+            fn returns_none_if_generation_mismatch()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(100);
+
+                let old_count = pool.count;
+                let old_free_len = pool.free.len();
+
+                let mut bad_key = key;
+                bad_key.generation = NonZeroU32::new(100).unwrap();
+                pool.delete(&bad_key);
+
+                assert!(!pool.data[key.index].is_empty(), "Expected data to be unchanged.");
+                assert_eq!(pool.data[key.index].generation, key.generation.get(), "Expected generation to remain unchanged.");
+                assert_eq!(pool.count, old_count, "Expected count to be unchanged.");
+                assert_eq!(pool.free.len(), old_free_len, "Expected free list length to be unchanged.");
+            }
+
+            #[test]
+            fn returns_none_if_data_is_none()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(100);
+
+                let old_count = pool.count;
+                let old_free_len = pool.free.len();
+
+                pool.data[key.index].take();
+                pool.delete(&key);
+
+                assert_eq!(pool.data[key.index].generation, key.generation.get(), "Expected generation to remain unchanged.");
+                assert_eq!(pool.count, old_count, "Expected count to be unchanged.");
+                assert_eq!(pool.free.len(), old_free_len, "Expected free list length to be unchanged.");
+            }
+        }
+
+        mod clear
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn replaces_all_items_with_none_and_clears_free_queue_and_resets_next()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                for _ in 0..10 { pool.insert(100); }
+
+                pool.clear();
+
+                for i in 0..10
+                {
+                    assert!(pool.data[i].is_empty(), "Expected data at index {} to be None.", i);
+                    assert_eq!(pool.data[i].generation, 2, "Expected generation at index {} to be advanced.", i);
+                }
+                assert_eq!(pool.count, 0, "Expected count to be 0.");
+                assert_eq!(pool.next, 0, "Expected next to be 0.");
+                assert_eq!(pool.free.len(), 0, "Expected free list length to be empty.");
+            }
+
+            #[test]
+            fn leaves_generation_of_empty_slots_unchanged()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(100);
+                pool.delete(&key);
+
+                pool.clear();
+
+                assert_eq!(pool.data[key.index].generation, key.generation.get(), "Expected generation of freed slot unchanged.");
+                assert_eq!(pool.data[1].generation, 0, "Expected generation of unused slot unchanged.");
+            }
+
+            #[test]
+            fn invalidates_keys_issued_before_the_clear()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let old_key = pool.insert(100);
+
+                pool.clear();
+
+                let new_key = pool.insert(200);
+                assert_eq!(old_key.index, new_key.index, "Expected slot to be reused.");
+                assert_ne!(old_key.generation, new_key.generation);
+
+                assert!(pool.get(&old_key).is_none(), "Expected pre-clear key to resolve to None.");
+                assert!(pool.take(&old_key).is_none());
+                assert_eq!(pool.get(&new_key), Some(&200));
+            }
+        }
+
+        mod iter
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn returns_an_empty_iterator_from_empty_pool()
+            {
+                let pool: ObjectPool<i32> = ObjectPool::new(10);
+                let data: Vec<_> = pool.iter().collect();
+                assert!(data.is_empty(), "Expected iterator to be empty.");
+            }
+
+            #[test]
+            fn returns_an_iterator_to_all_contained_elements()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                for i in 0..10 { pool.insert(i); }
+
+                let data: Vec<_> = pool.iter().collect();
+                assert!(data.len() == 10, "Expected iterator to contain 10 elements.");
+                assert_eq!(data, [&0, &1, &2, &3, &4, &5, &6, &7, &8, &9]);
+            }
+
+            #[test]
+            fn returns_an_iterator_correctly_skipping_none_elements()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+
+                let _     = pool.insert(0);
+                let _     = pool.insert(1);
+                let item2 = pool.insert(2);
+                let _     = pool.insert(3);
+                let _     = pool.insert(4);
+                let _     = pool.insert(5);
+                let item6 = pool.insert(6);
+                let item7 = pool.insert(7);
+                let _     = pool.insert(8);
+                let item9 = pool.insert(9);
+
+                pool.delete(&item2);
+                pool.delete(&item6);
+                pool.delete(&item7);
+                pool.delete(&item9);
+
+                let data: Vec<_> = pool.iter().collect();
+                assert!(data.len() == 6, "Expected iterator to contain 6 elements.");
+                assert_eq!(data, [&0, &1, &3, &4, &5, &8]);
+            }
+
+            #[test]
+            fn len_matches_live_count()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let keys: Vec<_> = (0..10).map(|i| pool.insert(i)).collect();
+                pool.delete(&keys[3]);
+                pool.delete(&keys[7]);
+
+                let count = pool.len();
+                let mut iter = pool.iter();
+                assert_eq!(iter.len(), count, "Expected iterator length to equal the live count.");
+
+                iter.next();
+                iter.next_back();
+                assert_eq!(iter.len(), count - 2);
+                assert_eq!(iter.size_hint(), (count - 2, Some(count - 2)), "Expected an exact size hint.");
+            }
+
+            #[test]
+            fn next_back_yields_reverse_index_order()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(200);
+                let keys: Vec<_> = (0..200).map(|i| pool.insert(i)).collect();
+                for key in keys.iter().filter(|key| key.index % 30 != 0) { pool.delete(key); }
+
+                let data: Vec<_> = pool.iter().rev().copied().collect();
+                assert_eq!(data, [180, 150, 120, 90, 60, 30, 0]);
+
+                let mut iter = pool.iter();
+                assert_eq!(iter.next().copied(), Some(0));
+                assert_eq!(iter.next_back().copied(), Some(180));
+                assert_eq!(iter.copied().collect::<Vec<_>>(), [30, 60, 90, 120, 150], "Expected both ends to meet without overlap.");
+            }
+        }
+
+        mod iter_mut
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn returns_an_empty_iterator_from_empty_pool()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let data: Vec<_> = pool.iter_mut().collect();
+                assert!(data.is_empty(), "Expected iterator to be empty.");
+            }
+
+            #[test]
+            fn returns_an_iterator_to_all_contained_elements()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                for i in 0..10 { pool.insert(i); }
+
+                let data: Vec<_> = pool.iter_mut().collect();
+                assert!(data.len() == 10, "Expected iterator to contain 10 elements.");
+                assert_eq!(data, [&0, &1, &2, &3, &4, &5, &6, &7, &8, &9]);
+            }
+
+            #[test]
+            fn returns_an_iterator_correctly_skipping_none_elements()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+
+                let _     = pool.insert(0);
+                let _     = pool.insert(1);
+                let item2 = pool.insert(2);
+                let _     = pool.insert(3);
+                let _     = pool.insert(4);
+                let _     = pool.insert(5);
+                let item6 = pool.insert(6);
+                let item7 = pool.insert(7);
+                let _     = pool.insert(8);
+                let item9 = pool.insert(9);
+
+                pool.delete(&item2);
+                pool.delete(&item6);
+                pool.delete(&item7);
+                pool.delete(&item9);
+
+                let data: Vec<_> = pool.iter_mut().collect();
+                assert!(data.len() == 6, "Expected iterator to contain 6 elements.");
+                assert_eq!(data, [&0, &1, &3, &4, &5, &8]);
+            }
+
+            #[test]
+            fn len_matches_live_count()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let keys: Vec<_> = (0..10).map(|i| pool.insert(i)).collect();
+                pool.delete(&keys[3]);
+                pool.delete(&keys[7]);
+
+                let count = pool.len();
+                let mut iter = pool.iter_mut();
+                assert_eq!(iter.len(), count, "Expected iterator length to equal the live count.");
+
+                iter.next();
+                iter.next_back();
+                assert_eq!(iter.len(), count - 2);
+                assert_eq!(iter.size_hint(), (count - 2, Some(count - 2)), "Expected an exact size hint.");
+            }
+
+            #[test]
+            fn next_back_yields_reverse_index_order()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(200);
+                let keys: Vec<_> = (0..200).map(|i| pool.insert(i)).collect();
+                for key in keys.iter().filter(|key| key.index % 30 != 0) { pool.delete(key); }
+
+                let data: Vec<_> = pool.iter_mut().rev().map(|item| *item).collect();
+                assert_eq!(data, [180, 150, 120, 90, 60, 30, 0]);
+
+                let mut iter = pool.iter_mut();
+                assert_eq!(iter.next().map(|item| *item), Some(0));
+                assert_eq!(iter.next_back().map(|item| *item), Some(180));
+                assert_eq!(iter.map(|item| *item).collect::<Vec<_>>(), [30, 60, 90, 120, 150], "Expected both ends to meet without overlap.");
+            }
+        }
+
+        mod into_iter
+        {
+            use std::rc::Rc;
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn returns_an_empty_iterator_from_empty_pool()
+            {
+                let pool: ObjectPool<i32> = ObjectPool::new(10);
+                let data: Vec<_> = pool.into_iter().collect();
+                assert!(data.is_empty(), "Expected iterator to be empty.");
+            }
+
+            #[test]
+            fn moves_out_live_items_in_order_skipping_holes()
+            {
+                let mut pool: ObjectPool<String> = ObjectPool::new(10);
+                let keys: Vec<_> = (0..10).map(|i| pool.insert(i.to_string())).collect();
+
+                pool.delete(&keys[0]);
+                pool.delete(&keys[4]);
+                pool.take(&keys[9]);
+
+                let data: Vec<String> = pool.into_iter().collect();
+                assert_eq!(data, ["1", "2", "3", "5", "6", "7", "8"]);
+            }
+
+            #[test]
+            fn drops_remaining_items_when_dropped_partway()
+            {
+                let tracker = Rc::new(());
+                let mut pool: ObjectPool<Rc<()>> = ObjectPool::new(10);
+                for _ in 0..5 { pool.insert(tracker.clone()); }
+
+                assert_eq!(Rc::strong_count(&tracker), 6);
+
+                let mut iter = pool.into_iter();
+                let first = iter.next();
+                let second = iter.next();
+                drop(iter);
+
+                assert_eq!(Rc::strong_count(&tracker), 3, "Expected non-yielded items to be dropped.");
+
+                drop(first);
+                drop(second);
+                assert_eq!(Rc::strong_count(&tracker), 1);
+            }
+
+            #[test]
+            fn supports_for_loops_over_references()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(1);
+                pool.insert(2);
+                pool.insert(3);
+                pool.delete(&key);
+
+                for item in &mut pool { *item *= 10; }
+
+                let mut data = Vec::new();
+                for item in &pool { data.push(*item); }
+
+                assert_eq!(data, [20, 30]);
+            }
+        }
+
+        mod iter_with_keys
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn returns_an_empty_iterator_from_empty_pool()
+            {
+                let pool: ObjectPool<i32> = ObjectPool::new(10);
+                assert_eq!(pool.iter_with_keys().count(), 0, "Expected iterator to be empty.");
+            }
+
+            #[test]
+            fn yields_keys_resolving_to_the_yielded_items()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let keys: Vec<_> = (0..5).map(|i| pool.insert(i)).collect();
+
+                pool.delete(&keys[2]);
+                let reused = pool.insert(100);
+
+                let data: Vec<_> = pool.iter_with_keys().collect();
+                assert_eq!(data.len(), 5, "Expected iterator to contain 5 elements.");
+
+                for (key, item) in data
+                {
+                    assert_eq!(pool.get(&key), Some(item), "Expected yielded key to resolve to yielded item.");
+                }
+
+                let (key, item) = pool.iter_with_keys().nth(2).unwrap();
+                assert_eq!(key, reused, "Expected key to carry the current generation.");
+                assert_eq!(*item, 100);
+            }
+        }
+
+        mod iter_mut_with_keys
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn yields_keys_resolving_to_the_yielded_items()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let keys: Vec<_> = (0..5).map(|i| pool.insert(i)).collect();
+
+                pool.delete(&keys[1]);
+
+                let mut seen = Vec::new();
+                for (key, item) in pool.iter_mut_with_keys()
+                {
+                    *item *= 10;
+                    seen.push((key, *item));
+                }
+
+                assert_eq!(seen.len(), 4, "Expected iterator to contain 4 elements.");
+                for (key, item) in seen
+                {
+                    assert_eq!(pool.get(&key), Some(&item), "Expected yielded key to resolve to yielded item.");
+                }
+                assert!(pool.get(&keys[1]).is_none());
+            }
+
+            #[test]
+            fn walks_a_sparse_pool_from_both_ends()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(1000);
+                let keys: Vec<_> = (0..1000).map(|i| pool.insert(i)).collect();
+                for key in keys.iter().filter(|key| ![3, 500, 998].contains(&key.index)) { pool.delete(key); }
+
+                let mut items = pool.iter_mut_with_keys();
+                let (first, item) = items.next().unwrap();
+                *item = -1;
+                let (last, item) = items.next_back().unwrap();
+                *item = -2;
+                assert_eq!(items.map(|(key, _)| key.index).collect::<Vec<_>>(), [500]);
+
+                assert_eq!((first.index, last.index), (3, 998));
+                assert_eq!((pool[first], pool[last]), (-1, -2), "Expected the yielded keys to resolve to the items changed through them.");
+            }
+        }
+
+        mod index
+        {
+            use std::num::NonZeroU32;
+            use super::super::{
+                Pool,
+                PoolKey,
+                ObjectPool,
+            };
+
+            #[test]
+            fn returns_reference_to_entry_specified()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key1 = pool.insert(100);
+                let key2 = pool.insert(200);
+
+                assert_eq!(pool[key2], 200);
+                assert_eq!(pool[key1], 100);
+            }
+
+            #[test]
+            #[should_panic(expected = "invalid or stale PoolKey")]
+            fn panics_if_generation_mismatch()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(100);
+
+                pool.delete(&key);
+                pool.insert(200);
+
+                let _ = pool[key];
+            }
+
+            #[test]
+            #[should_panic(expected = "invalid or stale PoolKey")]
+            fn panics_if_key_has_invalid_index()
+            {
+                let pool: ObjectPool<i32> = ObjectPool::new(10);
+                let _ = pool[PoolKey::new(1000, NonZeroU32::new(1).unwrap())];
+            }
+        }
+
+        mod index_mut
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn allows_mutation_of_entry_specified()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key1 = pool.insert(100);
+                let key2 = pool.insert(200);
+
+                pool[key1] += 1;
+                pool[key2] = 0;
+
+                assert_eq!(pool.get(&key1), Some(&101));
+                assert_eq!(pool.get(&key2), Some(&0));
+            }
+
+            #[test]
+            #[should_panic(expected = "invalid or stale PoolKey")]
+            fn panics_if_generation_mismatch()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(100);
+
+                pool.take(&key);
+
+                pool[key] = 200;
+            }
+        }
+
+        mod retain
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn deletes_items_failing_the_predicate()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let keys: Vec<_> = (0..10).map(|i| pool.insert(i)).collect();
+
+                pool.retain(|_, item| *item % 3 == 0);
+
+                assert_eq!(pool.count, 4, "Expected count to reflect retained items.");
+                assert_eq!(pool.free.len(), 6, "Expected culled indices to be pushed to free list.");
+
+                for (i, key) in keys.iter().enumerate()
+                {
+                    if i % 3 == 0 { assert_eq!(pool.get(key), Some(&(i as i32)), "Expected retained key {} to resolve.", i); }
+                    else          { assert!(pool.get(key).is_none(), "Expected culled key {} to resolve to None.", i); }
+                }
+            }
+
+            #[test]
+            fn passes_valid_keys_and_allows_mutation()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let keys: Vec<_> = (0..5).map(|i| pool.insert(i)).collect();
+                pool.delete(&keys[1]);
+
+                let mut culled = Vec::new();
+                pool.retain(|key, item| {
+                    *item *= 10;
+                    if *item > 20 { culled.push(key); false } else { true }
+                });
+
+                assert_eq!(culled, [keys[3], keys[4]], "Expected closure to receive the keys of culled items.");
+                assert_eq!(pool.get(&keys[0]), Some(&0));
+                assert_eq!(pool.get(&keys[2]), Some(&20));
+                assert_eq!(pool.count, 2);
+                assert_eq!(pool.free.len(), 3);
+            }
+
+            #[test]
+            fn does_nothing_if_every_item_is_retained()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                for i in 0..5 { pool.insert(i); }
+
+                pool.retain(|_, _| true);
+
+                assert_eq!(pool.count, 5, "Expected count to be unchanged.");
+                assert_eq!(pool.free.len(), 0, "Expected free list to be unchanged.");
+            }
+        }
+
+        mod drain
+        {
+            use std::rc::Rc;
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn yields_all_items_and_leaves_pool_empty()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let keys: Vec<_> = (0..10).map(|i| pool.insert(i)).collect();
+
+                pool.delete(&keys[3]);
+                pool.delete(&keys[7]);
+
+                let data: Vec<_> = pool.drain().collect();
+                assert_eq!(data, [0, 1, 2, 4, 5, 6, 8, 9]);
+
+                assert_eq!(pool.count, 0, "Expected count to be 0.");
+                assert_eq!(pool.next, 0, "Expected next to be 0.");
+                assert_eq!(pool.free.len(), 0, "Expected free list length to be empty.");
+                assert_eq!(pool.capacity(), 10, "Expected capacity to be unchanged.");
+
+                for key in keys.iter()
+                {
+                    assert!(pool.get(key).is_none(), "Expected drained key to resolve to None.");
+                }
+            }
+
+            #[test]
+            fn empties_pool_and_drops_remaining_items_when_dropped_early()
+            {
+                let tracker = Rc::new(());
+                let mut pool: ObjectPool<Rc<()>> = ObjectPool::new(10);
+                let keys: Vec<_> = (0..5).map(|_| pool.insert(tracker.clone())).collect();
+
+                let mut drain = pool.drain();
+                let first = drain.next();
+                drop(drain);
+
+                assert!(first.is_some());
+                assert_eq!(Rc::strong_count(&tracker), 2, "Expected non-yielded items to be dropped.");
+
+                assert_eq!(pool.count, 0, "Expected count to be 0.");
+                assert_eq!(pool.next, 0, "Expected next to be 0.");
+                assert_eq!(pool.free.len(), 0, "Expected free list length to be empty.");
+
+                for key in keys.iter()
+                {
+                    assert!(pool.get(key).is_none(), "Expected drained key to resolve to None.");
+                }
+            }
+
+            #[test]
+            fn advances_generations_so_old_keys_stay_invalid_after_reuse()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let old_key = pool.insert(100);
+
+                pool.drain().for_each(drop);
+
+                let new_key = pool.insert(200);
+                assert_eq!(old_key.index, new_key.index, "Expected slot to be reused.");
+                assert!(pool.get(&old_key).is_none(), "Expected drained key to resolve to None.");
+                assert_eq!(pool.get(&new_key), Some(&200));
+            }
+        }
+
+        mod reserve
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn extends_capacity_keeping_keys_valid()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(4);
+                let keys: Vec<_> = (0..4).map(|i| pool.insert(i)).collect();
+
+                pool.reserve(4);
+                assert_eq!(pool.capacity(), 8);
+                assert_eq!(pool.data.len(), pool.capacity());
+
+                for i in 4..8 { pool.insert(i); }
+                assert!(pool.try_insert(8).is_err(), "Expected non-growable pool to be full again.");
+
+                for (i, key) in keys.iter().enumerate()
+                {
+                    assert_eq!(pool.get(key), Some(&(i as i32)), "Expected key {} to remain valid.", i);
+                }
+            }
+
+            #[test]
+            #[should_panic(expected = "capacity exceeds the range of the pool's index type")]
+            fn panics_beyond_range_of_index_type()
+            {
+                let mut pool = ObjectPool::<i32, u8>::try_new(200).unwrap();
+                pool.reserve(257);
+            }
+
+            #[test]
+            fn accounts_for_free_slots_and_unused_tail()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let keys: Vec<_> = (0..6).map(|i| pool.insert(i)).collect();
+                pool.delete(&keys[0]);
+                pool.delete(&keys[1]);
+
+                pool.reserve(6);
+                assert_eq!(pool.capacity(), 10, "Expected no growth when enough slots are available.");
+
+                pool.reserve(8);
+                assert_eq!(pool.capacity(), 12, "Expected growth by the shortfall only.");
+            }
+
+            #[test]
+            fn growable_pool_does_not_grow_during_reserved_inserts()
+            {
+                let mut pool = ObjectPool::new_growable(1);
+                let key = pool.insert(0);
+
+                pool.reserve(100);
+                let capacity = pool.capacity();
+                let allocation = pool.data.capacity();
+
+                for i in 1..=100 { pool.insert(i); }
+                assert_eq!(pool.capacity(), capacity, "Expected no growth within the reserved amount.");
+                assert_eq!(pool.data.capacity(), allocation, "Expected no reallocation within the reserved amount.");
+                assert_eq!(pool.get(&key), Some(&0));
+            }
+        }
+
+        mod try_reserve
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+                PoolError,
+            };
+
+            #[test]
+            fn reports_overflow_without_changing_the_pool()
+            {
+                let mut pool = ObjectPool::<i32, u8>::try_new(200).unwrap();
+                let key = pool.insert(1);
+
+                assert_eq!(pool.try_reserve(257), Err(PoolError::CapacityOverflow));
+                assert_eq!(pool.capacity(), 200);
+                assert_eq!(pool.get(&key), Some(&1));
+            }
+
+            #[test]
+            fn reports_allocation_failure()
+            {
+                let mut pool: ObjectPool<[u8; 1 << 20]> = ObjectPool::new(0);
+
+                assert_eq!(pool.try_reserve(usize::MAX >> 8), Err(PoolError::AllocationFailed));
+                assert_eq!(pool.capacity(), 0);
+            }
+        }
+
+        mod shrink_to_fit
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn drops_trailing_empty_slots_keeping_keys_valid()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let keys: Vec<_> = (0..8).map(|i| pool.insert(i)).collect();
+
+                pool.delete(&keys[2]);
+                pool.delete(&keys[6]);
+                pool.delete(&keys[7]);
+
+                pool.shrink_to_fit();
+
+                assert_eq!(pool.capacity(), 6, "Expected capacity to end at last occupied slot.");
+                assert_eq!(pool.next, 6, "Expected next to be clamped to capacity.");
+                assert!(pool.free.iter(&pool.data).eq(vec![2]), "Expected out of range free indices to be pruned.");
+                assert_eq!(pool.count, 5, "Expected count to be unchanged.");
+
+                for (i, key) in keys.iter().enumerate()
+                {
+                    match i
+                    {
+                        2 | 6 | 7 => assert!(pool.get(key).is_none()),
+                        _         => assert_eq!(pool.get(key), Some(&(i as i32)), "Expected key {} to remain valid.", i),
+                    }
+                }
+            }
+
+            #[test]
+            fn shrinks_empty_pool_to_zero()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(100);
+                pool.delete(&key);
+
+                pool.shrink_to_fit();
+
+                assert_eq!(pool.capacity(), 0);
+                assert_eq!(pool.next, 0);
+                assert_eq!(pool.free.len(), 0);
+                assert!(pool.try_insert(100).is_err());
+            }
+
+            #[test]
+            fn does_nothing_if_last_slot_is_occupied()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(4);
+                let keys: Vec<_> = (0..4).map(|i| pool.insert(i)).collect();
+                pool.delete(&keys[1]);
+
+                pool.shrink_to_fit();
+
+                assert_eq!(pool.capacity(), 4, "Expected capacity to be unchanged.");
+                assert!(pool.free.iter(&pool.data).eq(vec![1]), "Expected free list to be unchanged.");
+            }
+
+            #[test]
+            fn dropped_slots_do_not_reissue_stale_keys_after_growing()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(2);
+                pool.insert(0);
+                let stale = pool.insert(1);
+                pool.delete(&stale);
+
+                pool.shrink_to_fit();
+                pool.reserve(1);
+
+                let key = pool.insert(2);
+                assert_eq!(key.index, stale.index, "Expected dropped slot to be recreated.");
+                assert_ne!(key.generation, stale.generation, "Expected recreated slot to advance past old generations.");
+                assert!(pool.get(&stale).is_none());
+            }
+        }
+
+
+
+        mod clear_and_shrink
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn releases_capacity_and_invalidates_keys()
+            {
+                let mut pool = ObjectPool::new_growable(100);
+                let keys: Vec<_> = (0..50).map(|i| pool.insert(i)).collect();
+                pool.delete(&keys[10]);
+
+                pool.clear_and_shrink();
+                assert_eq!(pool.capacity(), 0, "Expected the capacity to be released.");
+                assert!(pool.is_empty());
+                assert_eq!(pool.free_count(), 0);
+
+                let new_keys: Vec<_> = (0..50).map(|i| pool.insert(i)).collect();
+                for key in &keys
+                {
+                    assert!(pool.get(key).is_none(), "Expected key {:?} to stay invalid after regrowth.", key);
+                }
+                assert_eq!(pool.get(&new_keys[10]), Some(&10));
+            }
+        }
+
+        mod try_take
+        {
+            use super::super::{
+                Pool,
+                PoolKey,
+                ObjectPool,
+                RemoveError,
+            };
+
+            #[test]
+            fn tells_each_failure_apart()
+            {
+                let mut pool = ObjectPool::new(4);
+                let stale = pool.insert(1);
+                pool.delete(&stale);
+                pool.insert(2);
+
+                let emptied = pool.insert(3);
+                pool.delete(&emptied);
+
+                let out_of_range = PoolKey::from_u64(1 << 32 | 10).unwrap();
+                let snapshot = pool.clone();
+
+                assert_eq!(pool.try_take(&out_of_range), Err(RemoveError::OutOfRange));
+                assert_eq!(pool.try_take(&stale), Err(RemoveError::StaleGeneration));
+                assert_eq!(pool.try_take(&emptied), Err(RemoveError::AlreadyEmpty));
+                assert_eq!(pool.try_delete(&emptied), Err(RemoveError::AlreadyEmpty));
+
+                assert_eq!(pool, snapshot, "Expected failed removals to leave the pool untouched.");
+            }
+
+            #[test]
+            fn removes_live_items()
+            {
+                let mut pool = ObjectPool::new(4);
+                let key1 = pool.insert(1);
+                let key2 = pool.insert(2);
+
+                assert_eq!(pool.try_take(&key1), Ok(1));
+                assert_eq!(pool.try_delete(&key2), Ok(()));
+                assert!(pool.is_empty());
+            }
+        }
+
+        mod from_iter
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn collects_items_in_order()
+            {
+                let pool: ObjectPool<i32> = (0..10).collect();
+
+                assert_eq!(pool.len(), 10);
+                assert_eq!(pool.capacity(), 10, "Expected capacity to match the number of items.");
+                assert!(pool.iter().eq((0..10).collect::<Vec<_>>().iter()), "Expected items in insertion order.");
+            }
+
+            #[test]
+            fn issues_keys_consistent_with_insert()
+            {
+                let mut pool: ObjectPool<i32> = (0..3).collect();
+                let keys: Vec<_> = pool.iter_with_keys().map(|(key, _)| key).collect();
+
+                pool.delete(&keys[1]);
+                let key = pool.insert(100);
+
+                assert_eq!(key.index, keys[1].index, "Expected the freed slot to be reused.");
+                assert!(pool.get(&keys[1]).is_none());
+                assert_eq!(pool.get(&key), Some(&100));
+            }
+
+            #[test]
+            #[should_panic]
+            fn panics_beyond_range_of_index_type()
+            {
+                let _pool: ObjectPool<i32, u8> = (0..257).collect();
+            }
+        }
+
+        mod from
+        {
+            use std::num::NonZeroU32;
+
+            use super::super::{
+                Pool,
+                PoolKey,
+                ObjectPool,
+            };
+
+            #[test]
+            fn vec_keeps_order_and_length()
+            {
+                let source = vec![String::from("a"), String::from("b"), String::from("c")];
+                let pool: ObjectPool<String> = ObjectPool::from(source.clone());
+
+                assert_eq!(pool.len(), source.len());
+                assert_eq!(pool.capacity(), source.len(), "Expected the pool to be sized to the input.");
+                assert!(pool.iter().eq(source.iter()), "Expected iteration in source order.");
+                assert_eq!(pool.get(&PoolKey::new(1, NonZeroU32::new(1).unwrap())), Some(&source[1]), "Expected keys to be indices at the first generation.");
+            }
+
+            #[test]
+            fn array_keeps_order_and_length()
+            {
+                let pool: ObjectPool<i32> = ObjectPool::from([3, 1, 2]);
+
+                assert_eq!(pool.len(), 3);
+                assert!(pool.iter().eq(&[3, 1, 2]));
+            }
+
+            #[test]
+            fn round_trips_through_into_values()
+            {
+                let source = vec![5, 6, 7, 8];
+                assert_eq!(ObjectPool::<i32>::from(source.clone()).into_values(), source);
+            }
+        }
+
+        mod extend
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn inserts_items_in_order()
+            {
+                let mut pool = ObjectPool::new(10);
+                pool.insert(0);
+
+                pool.extend(1..10);
+
+                assert_eq!(pool.len(), 10);
+                assert!(pool.iter().eq((0..10).collect::<Vec<_>>().iter()));
+            }
+
+            #[test]
+            fn grows_growable_pool()
+            {
+                let mut pool = ObjectPool::new_growable(1);
+
+                pool.extend(0..100);
+
+                assert_eq!(pool.len(), 100);
+                assert!(pool.capacity() >= 100);
+            }
+
+            #[test]
+            #[should_panic]
+            fn panics_when_fixed_pool_fills()
+            {
+                let mut pool = ObjectPool::new(2);
+                pool.extend(0..3);
+            }
+        }
+
+
+
+        mod keys
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn yields_a_valid_key_per_item()
+            {
+                let mut pool = ObjectPool::new(10);
+                for i in 0..5 { pool.insert(i); }
+
+                let key = pool.keys().nth(2).unwrap();
+                pool.delete(&key);
+
+                let keys: Vec<_> = pool.keys().collect();
+                assert_eq!(keys.len(), pool.len(), "Expected a key per item.");
+
+                for key in &keys
+                {
+                    assert!(pool.get(key).is_some(), "Expected each key to resolve.");
+                }
+                assert!(keys.windows(2).all(|w| w[0].index < w[1].index), "Expected keys in index order.");
+            }
+        }
+
+        mod values
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn matches_iter()
+            {
+                let mut pool = ObjectPool::new(10);
+                for i in 0..5 { pool.insert(i); }
+
+                assert!(pool.values().eq(pool.iter()));
+            }
+
+            #[test]
+            fn values_mut_modifies_items()
+            {
+                let mut pool = ObjectPool::new(10);
+                for i in 0..5 { pool.insert(i); }
+
+                for value in pool.values_mut() { *value *= 10; }
+
+                assert!(pool.values().eq(&[0, 10, 20, 30, 40]));
+            }
+        }
+
+
+
+        mod get_disjoint_mut
+        {
+            use super::super::{
+                Pool,
+                PoolKey,
+                ObjectPool,
+            };
+
+            #[test]
+            fn returns_all_items_for_valid_keys()
+            {
+                let mut pool = ObjectPool::new(10);
+                let keys: Vec<_> = (0..3).map(|i| pool.insert(i)).collect();
+
+                let [a, b, c] = pool.get_disjoint_mut([keys[2], keys[0], keys[1]]).expect("Expected valid, distinct keys to succeed.");
+                assert_eq!((*a, *b, *c), (2, 0, 1));
+
+                *a += 10;
+                *b += 10;
+                *c += 10;
+
+                assert!(pool.iter().eq(&[10, 11, 12]));
+            }
+
+            #[test]
+            fn returns_none_for_duplicate_keys()
+            {
+                let mut pool = ObjectPool::new(10);
+                let key1 = pool.insert(1);
+                let key2 = pool.insert(2);
+
+                assert!(pool.get_disjoint_mut([key1, key2, key1]).is_none(), "Expected aliasing keys to fail.");
+            }
+
+            #[test]
+            fn returns_none_for_stale_key()
+            {
+                let mut pool = ObjectPool::new(10);
+                let key1 = pool.insert(1);
+                let key2 = pool.insert(2);
+
+                pool.delete(&key1);
+                pool.insert(3);
+
+                assert!(pool.get_disjoint_mut([key1, key2]).is_none(), "Expected a stale key to fail.");
+            }
+
+            #[test]
+            fn returns_none_for_out_of_range_key()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let key = pool.insert(1);
+                let invalid = PoolKey::<usize>::from_u64(100 | (1 << 32)).unwrap();
+
+                assert!(pool.get_disjoint_mut([key, invalid]).is_none());
+            }
+        }
+
+        mod get2_mut
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn returns_both_items_in_argument_order()
+            {
+                let mut pool = ObjectPool::new(10);
+                let key1 = pool.insert(1);
+                let key2 = pool.insert(2);
+
+                let (a, b) = pool.get2_mut(&key2, &key1).expect("Expected valid, distinct keys to succeed.");
+                assert_eq!((*a, *b), (2, 1));
+
+                core::mem::swap(a, b);
+                assert_eq!(pool[key1], 2);
+                assert_eq!(pool[key2], 1);
+            }
+
+            #[test]
+            fn returns_none_for_aliasing_keys()
+            {
+                let mut pool = ObjectPool::new(10);
+                let key = pool.insert(1);
+
+                assert!(pool.get2_mut(&key, &key).is_none(), "Expected aliasing keys to fail.");
+            }
+
+            #[test]
+            fn returns_none_for_one_invalid_key()
+            {
+                let mut pool = ObjectPool::new(10);
+                let key1 = pool.insert(1);
+                let key2 = pool.insert(2);
+                pool.delete(&key2);
+
+                assert!(pool.get2_mut(&key1, &key2).is_none(), "Expected a stale key to fail.");
+                assert!(pool.get2_mut(&key2, &key1).is_none(), "Expected a stale key to fail.");
+            }
+        }
+
+        mod get_pin_mut
+        {
+            use std::future::Future;
+            use std::pin::Pin;
+            use std::task::{ Context, Poll, Waker };
+
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            /// Pending on the first poll, ready on the second.
+            struct YieldOnce(bool);
+
+            impl Future for YieldOnce
+            {
+                type Output = ();
+
+                fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()>
+                {
+                    if self.0 { return Poll::Ready(()); }
+
+                    self.0 = true;
+                    return Poll::Pending;
+                }
+            }
+
+            #[test]
+            fn polls_futures_across_growth()
+            {
+                let mut context = Context::from_waker(Waker::noop());
+                let mut pool: ObjectPool<Pin<Box<dyn Future<Output = i32>>>> = ObjectPool::new_growable(1);
+
+                let key = pool.insert(Box::pin(async { YieldOnce(false).await; 7 }));
+                assert_eq!(pool.get_pin_mut(&key).unwrap().poll(&mut context), Poll::Pending);
+
+                // Grows the pool, moving the boxes but not the futures behind them.
+                pool.insert(Box::pin(async { YieldOnce(false).await; 8 }));
+
+                assert_eq!(pool.get_pin_mut(&key).unwrap().poll(&mut context), Poll::Ready(7), "Expected the suspended future to resume.");
+            }
+
+            #[test]
+            fn ignores_stale_key()
+            {
+                let mut pool = ObjectPool::new(10);
+                let key = pool.insert(Box::pin(0));
+                pool.delete(&key);
+
+                assert!(pool.get_pin_mut(&key).is_none(), "Expected a stale key to be rejected.");
+            }
+        }
+
+        mod map_into
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn keys_resolve_to_transformed_items()
+            {
+                let mut pool = ObjectPool::new(10);
+                let keys: Vec<_> = (0..5).map(|i| pool.insert(i)).collect();
+                pool.delete(&keys[1]);
+
+                let mut pool = pool.map_into(|item| item.to_string());
+
+                assert_eq!(pool.get(&keys[0]).map(String::as_str), Some("0"), "Expected a captured key to resolve to the transformed item.");
+                assert_eq!(pool.get(&keys[4]).map(String::as_str), Some("4"));
+                assert!(pool.get(&keys[1]).is_none(), "Expected empty slots to stay empty.");
+                assert_eq!(pool.len(), 4);
+
+                let key = pool.insert(String::from("new"));
+                assert_eq!(key.index, keys[1].index, "Expected the free list to carry over.");
+                assert!(pool.get(&keys[1]).is_none(), "Expected the stale key to remain invalid.");
+            }
+        }
+
+        mod debug
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn shows_only_live_items()
+            {
+                let mut pool = ObjectPool::new(100);
+                let keys: Vec<_> = (0..3).map(|i| pool.insert(i * 10)).collect();
+                pool.delete(&keys[1]);
+
+                let output = format!("{:?}", pool);
+                let expected = format!("ObjectPool {{ capacity: 100, len: 2, free: 1, items: {{{:?}: 0, {:?}: 20}} }}", keys[0], keys[2]);
+
+                assert_eq!(output, expected, "Expected a summary and only the live items.");
+            }
+
+            #[test]
+            fn layout_shows_every_slot()
+            {
+                let mut pool = ObjectPool::new(3);
+                let key = pool.insert(1);
+                pool.delete(&key);
+
+                let output = format!("{:?}", pool.debug_layout());
+                assert_eq!(output.matches("PoolEntry").count(), 3, "Expected vacant slots in the layout.");
+                assert!(output.contains("PoolEntry { generation: 1, slot: Vacant("), "Expected the deleted slot's generation.");
+            }
+        }
+
+        mod zero_sized
+        {
+            use std::mem::size_of;
+
+            use super::super::{
+                Pool,
+                PoolEntry,
+                ObjectPool,
+            };
+
+            #[test]
+            fn issues_and_validates_keys_as_tokens()
+            {
+                let mut pool = ObjectPool::new(2);
+                let key1 = pool.insert(());
+                let key2 = pool.insert(());
+                assert!(pool.try_insert(()).is_err(), "Expected capacity to apply to zero-sized items.");
+
+                assert!(pool.delete(&key1));
+                let key3 = pool.insert(());
+
+                assert_eq!(key3.index, key1.index, "Expected the freed slot to be reused.");
+                assert_ne!(key3.generation, key1.generation, "Expected the reused slot to issue a new generation.");
+                assert!(!pool.contains_key(&key1), "Expected the stale token to be rejected.");
+                assert!(pool.contains_key(&key2));
+                assert_eq!(pool.get(&key3), Some(&()));
+            }
+
+            #[test]
+            fn items_add_no_storage()
+            {
+                // Items share storage with the free list link of vacant slots, so a unit item is no larger than an empty slot.
+                assert_eq!(size_of::<PoolEntry<()>>(), size_of::<PoolEntry<usize>>(), "Expected a unit item to add nothing to the slot.");
+            }
+        }
+
+        mod zero_capacity
+        {
+            use super::super::{
+                Pool,
+                PoolKey,
+                ObjectPool,
+            };
+
+            #[test]
+            fn holds_nothing()
+            {
+                for mut pool in [ObjectPool::new_empty(), ObjectPool::new(0), ObjectPool::default()]
+                {
+                    assert_eq!(pool.capacity(), 0);
+                    assert!(pool.try_insert(1).is_err(), "Expected every insert to fail.");
+                    assert!(pool.is_empty());
+                    assert!(pool.iter().next().is_none(), "Expected nothing to iterate.");
+                    assert!(pool.get(&PoolKey::default()).is_none());
+                    assert!(!pool.delete(&PoolKey::default()));
+                    pool.clear();
+                }
+            }
+
+            #[test]
+            #[should_panic]
+            fn insert_panics()
+            {
+                let mut pool = ObjectPool::new_empty();
+                pool.insert(1);
+            }
+
+            #[test]
+            fn growable_grows_on_first_insert()
+            {
+                let mut pool = ObjectPool::new_growable(0);
+                assert_eq!(pool.capacity(), 0);
+
+                let key = pool.insert(1);
+                assert!(pool.capacity() >= 1, "Expected the pool to grow.");
+                assert_eq!(pool.get(&key), Some(&1));
+            }
+        }
+
+        mod content_eq
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+                SlotReuse,
+            };
+
+            #[test]
+            fn ignores_layout_of_vacant_slots()
+            {
+                let mut pool = ObjectPool::new(10);
+                let keys: Vec<_> = (0..4).map(|i| pool.insert(i)).collect();
+
+                let mut other = pool.clone().with_slot_reuse(SlotReuse::Fifo);
+                other.reserve(50);
+
+                pool.delete(&keys[1]);
+                pool.delete(&keys[3]);
+                other.delete(&keys[3]);
+                other.delete(&keys[1]);
+
+                assert_ne!(pool, other, "Expected the layouts to differ.");
+                assert!(pool.content_eq(&other), "Expected the same items under the same keys.");
+                assert!(other.content_eq(&pool));
+            }
+
+            #[test]
+            fn compares_items_and_generations()
+            {
+                let mut pool = ObjectPool::new(10);
+                let key = pool.insert(1);
+                let mut other = pool.clone();
+
+                other[key] = 2;
+                assert!(!pool.content_eq(&other), "Expected differing items to compare unequal.");
+
+                other.delete(&key);
+                other.insert(1);
+                assert!(!pool.content_eq(&other), "Expected an item under a newer key to compare unequal.");
+
+                other.insert(3);
+                assert!(!pool.content_eq(&other), "Expected differing lengths to compare unequal.");
+            }
+        }
+
+        mod into_values
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn returns_only_live_items_in_index_order()
+            {
+                let mut pool = ObjectPool::new(10);
+                let keys: Vec<_> = (0..6).map(|i| pool.insert(i)).collect();
+
+                pool.delete(&keys[0]);
+                pool.delete(&keys[3]);
+                pool.delete(&keys[4]);
+                pool.insert(10);
+
+                assert_eq!(pool.into_values(), vec![1, 2, 10, 5], "Expected the reused slot's item in its index position.");
+            }
+        }
+
+        mod introspection
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn tracks_a_known_sequence()
+            {
+                let mut pool = ObjectPool::new(10);
+                assert_eq!((pool.len(), pool.free_count(), pool.occupied_upper_bound()), (0, 0, 0));
+
+                let keys: Vec<_> = (0..5).map(|i| pool.insert(i)).collect();
+                assert_eq!((pool.len(), pool.free_count(), pool.occupied_upper_bound()), (5, 0, 5));
+
+                pool.delete(&keys[1]);
+                pool.delete(&keys[3]);
+                assert_eq!((pool.len(), pool.free_count(), pool.occupied_upper_bound()), (3, 2, 5));
+
+                pool.insert(5);
+                assert_eq!((pool.len(), pool.free_count(), pool.occupied_upper_bound()), (4, 1, 5), "Expected reuse of a freed slot.");
+
+                pool.clear();
+                assert_eq!((pool.len(), pool.free_count(), pool.occupied_upper_bound()), (0, 0, 0));
+            }
+
+            #[test]
+            fn high_water_mark_stays_at_its_peak()
+            {
+                let mut pool = ObjectPool::new_growable(10);
+                let keys: Vec<_> = (0..6).map(|i| pool.insert(i)).collect();
+
+                for key in &keys[2..] { pool.delete(key); }
+                let stats = pool.stats();
+                assert_eq!((stats.len, stats.free_slots, stats.high_water_mark), (2, 4, 6), "Expected deletes to leave the peak alone.");
+
+                pool.clear();
+                pool.insert(10);
+                assert_eq!(pool.stats().high_water_mark, 6, "Expected a clear to leave the peak alone.");
+
+                pool.shrink_to_fit();
+                assert_eq!(pool.stats().high_water_mark, 6, "Expected a shrink to leave the peak alone.");
+
+                pool.extend(0..8);
+                assert_eq!(pool.stats().high_water_mark, 9, "Expected the peak to follow a new high.");
+            }
+
+            #[test]
+            fn max_generation_covers_every_slot()
+            {
+                let mut pool = ObjectPool::new(10);
+                let key = pool.insert(1);
+                pool.insert(2);
+
+                for _ in 0..3
+                {
+                    let key = pool.insert(3);
+                    pool.delete(&key);
+                }
+                pool.delete(&key);
+
+                assert_eq!(pool.stats().max_generation, 3, "Expected the most reused slot's generation, though it is vacant.");
+            }
+
+            #[test]
+            fn occupied_indices_match_live_positions()
+            {
+                let mut pool = ObjectPool::new(200);
+                let keys: Vec<_> = (0..150).map(|i| pool.insert(i)).collect();
+                for key in keys.iter().filter(|key| key.index % 3 != 0 || key.index > 100) { pool.delete(key); }
+
+                let expected: Vec<_> = (0..=100).step_by(3).collect();
+                assert_eq!(pool.occupied_indices().collect::<Vec<_>>(), expected, "Expected the live positions, ascending.");
+                assert!(pool.occupied_indices().eq(pool.keys().map(|key| key.index)), "Expected the same positions as the keys.");
+            }
+
+            #[test]
+            fn free_and_occupied_indices_cover_the_capacity()
+            {
+                let mut pool = ObjectPool::new_growable(8);
+                let mut keys = Vec::new();
+                let check = |pool: &ObjectPool<usize>|
+                {
+                    let mut indices: Vec<_> = pool.occupied_indices().chain(pool.free_indices()).collect();
+                    indices.sort_unstable();
+                    assert!(indices.into_iter().eq(0..pool.capacity()), "Expected every slot to be either free or occupied, once.");
+                };
+
+                for step in 0..200
+                {
+                    match step % 7
+                    {
+                        0 | 1 | 3 | 5 => keys.push(pool.insert(step)),
+                        2 | 4         => { if let Some(key) = keys.pop() { pool.delete(&key); } },
+                        _             => { let key = keys.remove(step % keys.len()); pool.delete(&key); },
+                    }
+                    check(&pool);
+
+                    if step == 100 { pool.shrink_to_fit(); check(&pool); }
+                    if step == 150 { pool.clear(); keys.clear(); check(&pool); }
+                }
+            }
+
+            #[test]
+            fn free_indices_follow_the_reuse_order()
+            {
+                let mut pool = ObjectPool::new(6);
+                let keys: Vec<_> = (0..4).map(|i| pool.insert(i)).collect();
+                pool.delete(&keys[0]);
+                pool.delete(&keys[2]);
+
+                let order: Vec<_> = pool.free_indices().collect();
+                assert_eq!(order, vec![2, 0, 4, 5], "Expected the freed slots, most recent first, then the unused ones.");
+
+                let claimed: Vec<_> = (0..4).map(|i| pool.insert(i).index).collect();
+                assert_eq!(claimed, order, "Expected inserts to claim slots in the same order.");
+            }
+
+            #[test]
+            fn inspects_slots_by_index()
+            {
+                let mut pool = ObjectPool::new(4);
+                let keys: Vec<_> = (0..2).map(|i| pool.insert(i)).collect();
+                pool.delete(&keys[1]);
+
+                assert_eq!((pool.is_slot_occupied(0), pool.slot_generation(0)), (Some(true), Some(1)), "Expected an occupied slot at its key's generation.");
+                assert_eq!((pool.is_slot_occupied(1), pool.slot_generation(1)), (Some(false), Some(1)), "Expected a freed slot to keep its generation.");
+                assert_eq!((pool.is_slot_occupied(2), pool.slot_generation(2)), (Some(false), Some(0)), "Expected an unused slot at generation zero.");
+                assert_eq!((pool.is_slot_occupied(4), pool.slot_generation(4)), (None, None), "Expected out of range slots to be reported as such.");
+
+                let key = pool.insert(10);
+                assert_eq!(key.index, 1);
+                assert_eq!(pool.slot_generation(1), Some(key.generation.get()), "Expected reuse to advance the generation.");
+            }
+        }
+
+        mod insert_at
+        {
+            use super::super::{
+                Pool,
+                PoolKey,
+                ObjectPool,
+                InsertAtError,
+            };
+
+            enum Op
+            {
+                Insert(u64, i32),
+                Delete(u64),
+            }
+
+            #[test]
+            fn replaying_a_record_restores_identical_keys()
+            {
+                let mut pool = ObjectPool::new_growable(4);
+                let mut record = Vec::new();
+                let mut keys = Vec::new();
+
+                for i in 0..8
+                {
+                    let key = pool.insert(i);
+                    record.push(Op::Insert(key.as_u64(), i));
+                    keys.push(key);
+
+                    if i % 3 == 1
+                    {
+                        pool.delete(&keys[i as usize - 1]);
+                        record.push(Op::Delete(keys[i as usize - 1].as_u64()));
+                    }
+                }
+
+                let mut restored = ObjectPool::new(0);
+                for op in &record
+                {
+                    match *op
+                    {
+                        Op::Insert(raw, item) => restored.insert_at(PoolKey::from_u64(raw).unwrap(), item).expect("Expected replayed inserts to succeed."),
+                        Op::Delete(raw)       => assert!(restored.delete(&PoolKey::from_u64(raw).unwrap()), "Expected replayed deletes to succeed."),
+                    }
+                }
+
+                assert_eq!(restored.len(), pool.len());
+                for key in &keys
+                {
+                    let restored_key = PoolKey::from_u64(key.as_u64()).unwrap();
+                    assert_eq!(restored.get(&restored_key), pool.get(key), "Expected key {:?} to resolve identically.", key);
+                }
+            }
+
+            #[test]
+            fn skipped_slots_remain_usable()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(0);
+                pool.insert_at(PoolKey::new(3, core::num::NonZeroU32::new(5).unwrap()), 3).unwrap();
+
+                assert_eq!(pool.capacity(), 4);
+                assert_eq!(pool.free_count(), 3, "Expected the slots skipped over to be free.");
+
+                let keys: Vec<_> = (0..3).map(|i| pool.insert(i)).collect();
+                assert!(keys.iter().all(|key| key.index < 3));
+                assert!(pool.try_insert(4).is_err(), "Expected the claimed slot not to be handed out again.");
+            }
+
+            #[test]
+            fn rejects_occupied_stale_and_null_keys()
+            {
+                let mut pool = ObjectPool::new(10);
+                let key = pool.insert(1);
+
+                assert_eq!(pool.insert_at(key, 2), Err(InsertAtError::Occupied(2)));
+
+                pool.delete(&key);
+                assert_eq!(pool.insert_at(key, 3), Err(InsertAtError::Stale(3)), "Expected a vacated key not to be revived.");
+                assert_eq!(pool.insert_at(PoolKey::null(), 4), Err(InsertAtError::Invalid(4)));
+
+                let newer = PoolKey::new(key.index, core::num::NonZeroU32::new(key.generation.get() + 5).unwrap());
+                assert_eq!(pool.insert_at(newer, 5), Ok(()));
+                assert_eq!(pool.get(&newer), Some(&5));
+                assert_eq!(pool.free_count(), 0, "Expected the claimed slot to leave the free list.");
+            }
+
+            #[test]
+            fn rejects_slots_beyond_reach()
+            {
+                let generation = core::num::NonZeroU32::new(1).unwrap();
+
+                let mut pool: ObjectPool<i32, u8> = Pool::new(0);
+                assert_eq!(pool.insert_at(PoolKey::new(255, generation), 1), Ok(()), "Expected the last slot of the index type to be reachable.");
+
+                let mut pool: ObjectPool<i32> = ObjectPool::new(0);
+                let far = PoolKey::new(ObjectPool::<i32>::INSERT_AT_MAX_GROWTH, generation);
+                assert_eq!(pool.insert_at(far, 2), Err(InsertAtError::OutOfRange(2)), "Expected growth past the limit to be refused.");
+                assert_eq!(pool.insert_at(PoolKey::new(usize::MAX - 2, generation), 3), Err(InsertAtError::OutOfRange(3)));
+                assert_eq!(pool.capacity(), 0, "Expected a refused key not to grow the pool.");
+
+                pool.reserve(ObjectPool::<i32>::INSERT_AT_MAX_GROWTH);
+                assert_eq!(pool.insert_at(far, 4), Ok(()), "Expected reserving ahead to bring the slot within reach.");
+                assert_eq!(pool.get(&far), Some(&4));
+            }
+        }
+
+        mod raw_parts
+        {
+            use super::super::{
+                Pool,
+                PoolKey,
+                ObjectPool,
+            };
+
+            #[test]
+            fn round_trip_preserves_keys_and_free_order()
+            {
+                let mut pool = ObjectPool::new(10);
+                let keys: Vec<_> = (0..6).map(|i| pool.insert(i)).collect();
+                pool.delete(&keys[1]);
+                pool.delete(&keys[4]);
+
+                let expected = pool.clone();
+                let (data, free, next, count) = pool.into_raw_parts();
+                assert_eq!(free, vec![4, 1]);
+
+                let mut pool = ObjectPool::<i32>::from_raw_parts(data, free, next, count);
+                assert!(pool.iter().eq(expected.iter()));
+                assert_eq!(pool.free_count(), 2);
+
+                for (key, item) in expected.iter_with_keys()
+                {
+                    let key = PoolKey::from_u64(key.as_u64()).unwrap();
+                    assert_eq!(pool.get(&key), Some(item), "Expected key {:?} to survive the round trip.", key);
+                }
+
+                assert_eq!(pool.insert(6).index, 4, "Expected the free list to pop in the original order.");
+                assert_eq!(pool.insert(7).index, 1);
+            }
+
+            #[test]
+            #[cfg(debug_assertions)]
+            #[should_panic(expected = "count doesn't match the occupied slots")]
+            fn debug_asserts_on_inconsistent_parts()
+            {
+                ObjectPool::<i32>::from_raw_parts(vec![(1, Some(0)), (1, None)], vec![1], 2, 2);
+            }
+
+            #[test]
+            #[cfg(debug_assertions)]
+            #[should_panic(expected = "free slots must be distinct, vacant, and below next")]
+            fn debug_asserts_on_occupied_free_slot()
+            {
+                ObjectPool::<i32>::from_raw_parts(vec![(1, Some(0)), (1, None)], vec![0], 2, 1);
+            }
+        }
+
+        #[cfg(feature = "std")]
+        mod clone_compact
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn packs_items_and_remaps_every_key()
+            {
+                let mut pool = ObjectPool::new(10);
+                let keys: Vec<_> = (0..8).map(|i| pool.insert(i)).collect();
+                for key in keys.iter().step_by(3) { pool.delete(key); }
+
+                let (compact, remap) = pool.clone_compact();
+
+                assert_eq!(compact.len(), pool.len());
+                assert_eq!(compact.next, compact.len(), "Expected no holes below next.");
+                assert_eq!(compact.free_count(), 0);
+                assert_eq!(compact.capacity(), pool.capacity());
+                assert_eq!(remap.len(), pool.len());
+
+                for (key, item) in pool.iter_with_keys()
+                {
+                    assert_eq!(compact.get(&remap[&key]), Some(item), "Expected {:?} to map to a key resolving to the same item.", key);
+                }
+            }
+
+            #[test]
+            fn old_keys_do_not_alias_the_copy()
+            {
+                let mut pool = ObjectPool::new(10);
+                let keys: Vec<_> = (0..4).map(|i| pool.insert(i)).collect();
+                pool.delete(&keys[0]);
+
+                let (compact, _) = pool.clone_compact();
+
+                for key in &keys
+                {
+                    assert!(compact.get(key).is_none(), "Expected keys of the original not to resolve unmapped.");
+                }
+            }
+        }
+
+        #[cfg(feature = "std")]
+        mod defragment
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+                PoolEntry,
+            };
+
+            #[test]
+            fn packs_items_into_the_lowest_slots()
+            {
+                let mut pool = ObjectPool::new(10);
+                let keys: Vec<_> = (0..10).map(|i| pool.insert(i)).collect();
+                for key in keys.iter().filter(|key| key.index % 3 != 2) { pool.delete(key); }
+
+                let before: Vec<_> = pool.iter_with_keys().map(|(key, item)| (key, *item)).collect();
+                let remap = pool.defragment();
+
+                assert!(pool.data[..pool.len()].iter().all(|e| !e.is_empty()), "Expected slots 0..len() to be occupied.");
+                assert_eq!(pool.next, pool.len());
+                assert_eq!(pool.free_count(), 0);
+                assert!(pool.iter().eq(&[2, 5, 8]), "Expected the order of items to be kept.");
+
+                for (key, item) in before
+                {
+                    assert_eq!(pool.get(&remap[&key]), Some(&item), "Expected {:?} to map to a key resolving to its item.", key);
+                    if remap[&key] != key { assert!(pool.get(&key).is_none(), "Expected the key of a moved item to be invalid."); }
+                }
+            }
+
+            #[test]
+            fn new_items_follow_the_last_one()
+            {
+                let mut pool = ObjectPool::new(4);
+                let keys: Vec<_> = (0..4).map(|i| pool.insert(i)).collect();
+                pool.delete(&keys[0]);
+                pool.delete(&keys[2]);
+
+                pool.defragment();
+                let key = pool.insert(4);
+
+                assert_eq!(key.index, 2);
+                assert!(pool.iter().eq(&[1, 3, 4]));
+                assert!(pool.get(&keys[2]).is_none(), "Expected the old key of the reused slot to stay invalid.");
+            }
+
+            #[test]
+            fn skips_retired_slots()
+            {
+                let mut pool = ObjectPool::new(4);
+                pool.data[0] = PoolEntry::with_generation(u32::MAX - 1);
+                pool.next = 1;
+                let key = pool.insert(1);
+                assert_eq!(key.index, 1);
+
+                let remap = pool.defragment();
+                assert_eq!(remap[&key], key, "Expected the item not to move into a retired slot.");
+            }
+        }
+
+        mod swap
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn keys_resolve_to_each_others_former_items()
+            {
+                let mut pool = ObjectPool::new(10);
+                let key1 = pool.insert(1);
+                let key2 = pool.insert(2);
+
+                assert!(pool.swap(&key1, &key2));
+                assert_eq!(pool.get(&key1), Some(&2), "Expected the first key to resolve to the second item.");
+                assert_eq!(pool.get(&key2), Some(&1), "Expected the second key to resolve to the first item.");
+            }
+
+            #[test]
+            fn same_key_is_a_no_op()
+            {
+                let mut pool = ObjectPool::new(10);
+                let key = pool.insert(1);
+
+                assert!(pool.swap(&key, &key));
+                assert_eq!(pool[key], 1);
+            }
+
+            #[test]
+            fn fails_without_swapping_for_invalid_key()
+            {
+                let mut pool = ObjectPool::new(10);
+                let key1 = pool.insert(1);
+                let key2 = pool.insert(2);
+                pool.delete(&key2);
+                let key3 = pool.insert(3);
+
+                assert!(!pool.swap(&key1, &key2), "Expected a stale key to fail.");
+                assert!(!pool.swap(&key2, &key3), "Expected a stale key to fail, even against its own slot.");
+                assert_eq!(pool[key1], 1);
+                assert_eq!(pool[key3], 3);
+            }
+        }
+
+
+
+        mod aliases
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn remove_matches_take()
+            {
+                let mut pool = ObjectPool::new(10);
+                let keys: Vec<_> = (0..4).map(|i| pool.insert(i)).collect();
+                pool.delete(&keys[1]);
+                let mut other = pool.clone();
+
+                for key in &keys
+                {
+                    assert_eq!(pool.remove(key), other.take(key), "Expected remove to behave as take for {:?}.", key);
+                    assert_eq!(pool.remove(key), other.take(key), "Expected a second remove to behave as take for {:?}.", key);
+                }
+                assert_eq!(pool, other);
+            }
+
+            #[test]
+            fn contains_matches_contains_key()
+            {
+                let mut pool = ObjectPool::new(10);
+                let keys: Vec<_> = (0..4).map(|i| pool.insert(i)).collect();
+                pool.delete(&keys[2]);
+                pool.insert(10);
+
+                for key in &keys
+                {
+                    assert_eq!(pool.contains(key), pool.contains_key(key), "Expected contains to behave as contains_key for {:?}.", key);
+                }
+            }
+        }
+
+        mod replace
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn swaps_item_and_keeps_key_valid()
+            {
+                let mut pool = ObjectPool::new(10);
+                let key = pool.insert(1);
+                let generation = pool.data[key.index].generation;
+
+                assert_eq!(pool.replace(&key, 2), Some(1), "Expected the previous item back.");
+                assert_eq!(pool.data[key.index].generation, generation, "Expected the generation to be unchanged.");
+                assert_eq!(pool.get(&key), Some(&2));
+                assert_eq!(pool.len(), 1);
+            }
+
+            #[test]
+            fn ignores_stale_key()
+            {
+                let mut pool = ObjectPool::new(10);
+                let key = pool.insert(1);
+                pool.delete(&key);
+                let new_key = pool.insert(2);
+
+                assert_eq!(pool.replace(&key, 3), None, "Expected a stale key to be rejected.");
+                assert_eq!(pool.get(&new_key), Some(&2), "Expected the pool to be untouched.");
+            }
+        }
+
+
+
+        mod get_or_insert_with
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn returns_existing_item_for_valid_key()
+            {
+                let mut pool = ObjectPool::new(10);
+                let key = pool.insert(1);
+
+                let (found_key, item) = pool.get_or_insert_with(&key, || panic!("Expected f not to be called."));
+                *item += 1;
+
+                assert_eq!(found_key, key, "Expected the same key back.");
+                assert_eq!(pool[key], 2);
+                assert_eq!(pool.len(), 1, "Expected nothing to be inserted.");
+            }
+
+            #[test]
+            fn inserts_into_fresh_slot_for_stale_key()
+            {
+                let mut pool = ObjectPool::new(10);
+                let key = pool.insert(1);
+                pool.delete(&key);
+
+                let (new_key, item) = pool.get_or_insert_with(&key, || 5);
+                assert_eq!(*item, 5);
+
+                assert_ne!(new_key, key, "Expected a new key for the inserted item.");
+                assert!(!pool.contains_key(&key), "Expected the stale key to stay stale.");
+                assert_eq!(pool.get(&new_key), Some(&5));
+                assert_eq!(pool.len(), 1);
+            }
+        }
+
+
+
+        mod generation_overflow
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn retires_slot_instead_of_wrapping()
+            {
+                let mut pool = ObjectPool::new(1);
+                let key = pool.insert(1);
+                pool.delete(&key);
+
+                pool.data[0].generation = u32::MAX - 2;
+
+                let last_key = pool.insert(2);
+                assert_eq!(last_key.generation.get(), u32::MAX - 1, "Expected the final generation to be issued.");
+                assert!(!last_key.is_null());
+                assert_eq!(pool.get(&last_key), Some(&2));
+
+                pool.delete(&last_key);
+                assert_eq!(pool.free.len(), 0, "Expected the retired slot not to be freed.");
+
+                assert!(pool.try_insert(3).is_err(), "Expected the retired slot not to be reused.");
+                assert!(pool.get(&last_key).is_none());
+                assert!(pool.get(&key).is_none(), "Expected the oldest key not to alias anything.");
+            }
+
+            #[test]
+            fn growable_pool_skips_retired_slot()
+            {
+                let mut pool = ObjectPool::new_growable(1);
+                pool.insert(1);
+
+                pool.data[0].generation = u32::MAX - 1;
+                let key = pool.keys().next().unwrap();
+                assert_eq!(pool.take(&key), Some(1));
+
+                let new_key = pool.insert(2);
+                assert_eq!(new_key.index, 1, "Expected a new slot rather than the retired one.");
+                assert_eq!(pool.capacity(), 2);
+            }
+
+            #[test]
+            fn clear_skips_retired_slot()
+            {
+                let mut pool = ObjectPool::new(2);
+                pool.insert(1);
+                pool.insert(2);
+
+                pool.data[0].generation = u32::MAX - 1;
+                pool.clear();
+
+                let key1 = pool.insert(3);
+                assert_eq!(key1.index, 1, "Expected the retired slot to be skipped.");
+                assert!(pool.try_insert(4).is_err());
+
+                pool.shrink_to_fit();
+                assert_eq!(pool.capacity(), 2, "Expected shrinking to keep the retired slot.");
+            }
+
+            #[test]
+            fn counts_retired_slots_and_never_reissues_them()
+            {
+                let mut pool = ObjectPool::new_growable(4);
+                let keys: Vec<_> = (0..4).map(|i| pool.insert(i)).collect();
+                pool.delete(&keys[2]);
+                pool.data[2].generation = u32::MAX - 2;
+                assert_eq!(pool.retired_slots(), 0, "Expected a slot with a generation left not to be retired.");
+
+                let last_key = pool.insert(2);
+                assert_eq!(last_key.index, 2);
+                assert_eq!(pool.retired_slots(), 0, "Expected the slot's final occupant not to count as retired.");
+
+                pool.delete(&last_key);
+                assert_eq!(pool.retired_slots(), 1, "Expected deleting the final occupant to retire the slot.");
+
+                for i in 0..100
+                {
+                    let key = pool.insert(i);
+                    assert_ne!(key.index, 2, "Expected the retired slot never to be handed out again.");
+                    if i % 3 == 0 { pool.delete(&key); }
+                }
+
+                pool.clear();
+                pool.shrink_to_fit();
+                assert_eq!(pool.retired_slots(), 1, "Expected the retirement to survive clearing and shrinking.");
+                assert!((0..4).map(|i| pool.insert(i)).all(|key| key.index != 2), "Expected the retired slot to be skipped after a clear.");
+            }
+        }
+
+        mod generation_type
+        {
+            use std::num::{ NonZeroU16, NonZeroU64 };
+
+            use super::super::{
+                Pool,
+                ObjectPool,
+                PoolKey,
+            };
+
+            #[test]
+            fn narrow_generation_retires_at_its_own_threshold()
+            {
+                let mut pool: ObjectPool<i32, u16, NonZeroU16> = Pool::new(2);
+                let first_key = pool.insert(0);
+                pool.delete(&first_key);
+
+                for i in 1..u16::MAX - 1
+                {
+                    let key = pool.insert(i32::from(i));
+                    assert_eq!(key.index, 0, "Expected the slot to be reused until it retires.");
+                    pool.delete(&key);
+                }
+
+                assert_eq!(pool.slot_generation(0), Some(u16::MAX - 1));
+                assert_eq!(pool.retired_slots(), 1, "Expected the slot to retire one short of the null generation.");
+                assert_eq!(pool.insert(1).index, 1, "Expected the retired slot to be skipped.");
+                assert!(pool.get(&first_key).is_none());
+            }
+
+            #[test]
+            fn wide_generation_keys_resolve()
+            {
+                let mut pool: ObjectPool<&str, usize, NonZeroU64> = Pool::new(4);
+                let key = pool.insert("Howdy!");
+                pool.delete(&key);
+
+                let reused = pool.insert("Hi!");
+                assert_eq!(reused.generation.get(), 2u64);
+                assert_eq!(pool.get(&reused), Some(&"Hi!"));
+                assert!(pool.get(&key).is_none());
+                assert!(pool.get(&PoolKey::null()).is_none());
+            }
+
+            #[test]
+            #[cfg(not(feature = "pool-id"))]
+            fn narrow_generation_keys_are_smaller()
+            {
+                assert_eq!(std::mem::size_of::<PoolKey<u16, NonZeroU16>>(), 4);
+                assert_eq!(std::mem::size_of::<Option<PoolKey<u16, NonZeroU16>>>(), 4);
+            }
+        }
+
+
+
+        mod null_key
+        {
+            use super::super::{
+                Pool,
+                PoolKey,
+                ObjectPool,
+            };
+
+            #[test]
+            fn never_resolves()
+            {
+                let mut pool = ObjectPool::<i32, u8>::new(256);
+                for i in 0..256 { pool.insert(i); }
+
+                let null = PoolKey::null();
+                assert_eq!(null.index, u8::MAX, "Expected the null key to point at a live slot, for the sake of the test.");
+
+                assert!(pool.get(&null).is_none());
+                assert!(pool.get_mut(&null).is_none());
+                assert!(!pool.contains_key(&null));
+                assert!(pool.take(&null).is_none());
+
+                pool.delete(&null);
+                assert_eq!(pool.len(), 256, "Expected deleting the null key to do nothing.");
+            }
+
+            #[test]
+            fn never_resolves_after_filling_index_zero()
+            {
+                let mut pool = ObjectPool::new(1);
+                pool.insert(1);
+
+                let null: PoolKey = PoolKey::null();
+                assert!(pool.get(&null).is_none());
+            }
+
+            #[test]
+            fn is_default_and_distinct_from_issued_keys()
+            {
+                let mut pool = ObjectPool::new(10);
+                let key = pool.insert(1);
+
+                assert!(PoolKey::<usize>::default().is_null());
+                assert!(!key.is_null());
+                assert_ne!(key, PoolKey::null());
+            }
+        }
+
+
+
+        #[cfg(feature = "pool-id")]
+        mod pool_id
+        {
+            use super::super::{
+                Pool,
+                PoolKey,
+                ObjectPool,
+                RemoveError,
+            };
+
+            #[test]
+            fn rejects_keys_from_another_pool()
+            {
+                let mut pool_a = ObjectPool::new(10);
+                let mut pool_b = ObjectPool::new(10);
+
+                let key_a = pool_a.insert(1);
+                let key_b = pool_b.insert(2);
+                assert_eq!((key_a.index, key_a.generation), (key_b.index, key_b.generation), "Expected keys to differ by pool id alone.");
+
+                assert!(pool_b.get(&key_a).is_none());
+                assert!(pool_b.get_mut(&key_a).is_none());
+                assert!(!pool_b.contains_key(&key_a));
+                assert!(pool_b.take(&key_a).is_none());
+
+                pool_b.delete(&key_a);
+                assert_eq!(pool_b.get(&key_b), Some(&2), "Expected deleting a foreign key to do nothing.");
+                assert_eq!(pool_b.try_take(&key_a), Err(RemoveError::ForeignKey));
+            }
+
+            #[test]
+            fn accepts_keys_from_a_clone_or_unpacked()
+            {
+                let mut pool = ObjectPool::new(10);
+                let key = pool.insert(1);
+                let clone = pool.clone();
+
+                assert_eq!(clone.get(&key), Some(&1));
+                assert_eq!(pool.get(&PoolKey::from_u64(key.as_u64()).unwrap()), Some(&1));
+            }
+
+            #[test]
+            fn tags_keys_yielded_by_iteration()
+            {
+                let mut pool = ObjectPool::new(10);
+                let key = pool.insert(1);
+
+                assert_eq!(pool.keys().next(), Some(key));
+                assert_eq!(pool.iter_with_keys().next().map(|(key, _)| key), Some(key));
+            }
+        }
+
+
+
+        mod insert_with_key
+        {
+            use super::super::{
+                Pool,
+                PoolKey,
+                ObjectPool,
+            };
+
+            #[test]
+            fn stores_item_holding_its_own_key()
+            {
+                let mut pool: ObjectPool<(PoolKey, i32)> = ObjectPool::new(10);
+                pool.insert((PoolKey::null(), 0));
+
+                let key = pool.insert_with_key(|key| (key, 1));
+
+                assert_eq!(pool.get(&key), Some(&(key, 1)), "Expected the item to hold the returned key.");
+                assert_eq!(pool.len(), 2);
+            }
+
+            #[test]
+            fn reuses_freed_slot_with_new_generation()
+            {
+                let mut pool: ObjectPool<PoolKey> = ObjectPool::new(1);
+                let old_key = pool.insert_with_key(|key| key);
+                pool.delete(&old_key);
+
+                let key = pool.insert_with_key(|key| key);
+
+                assert_eq!(key.index, old_key.index);
+                assert_ne!(key, old_key);
+                assert_eq!(pool.get(&key), Some(&key));
+                assert!(pool.get(&old_key).is_none());
+            }
+
+            #[test]
+            fn grows_growable_pool()
+            {
+                let mut pool: ObjectPool<PoolKey> = ObjectPool::new_growable(1);
+                pool.insert_with_key(|key| key);
+
+                let key = pool.insert_with_key(|key| key);
+                assert_eq!(pool.get(&key), Some(&key));
+            }
+
+            #[test]
+            #[should_panic(expected = "spool: ObjectPool is at capacity (1)")]
+            fn panics_when_full()
+            {
+                let mut pool: ObjectPool<PoolKey> = ObjectPool::new(1);
+                pool.insert_with_key(|key| key);
+                pool.insert_with_key(|key| key);
+            }
+
+            #[test]
+            fn releases_slot_if_constructor_panics()
+            {
+                let mut pool: ObjectPool<PoolKey> = ObjectPool::new(2);
+                let first = pool.insert_with_key(|key| key);
+                pool.delete(&first);
+                let remaining = pool.capacity_remaining();
+
+                let mut leaked = PoolKey::null();
+                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    pool.insert_with_key(|key| { leaked = key; panic!("constructor failed") });
+                }));
+
+                assert!(result.is_err());
+                assert_eq!(pool.capacity_remaining(), remaining, "Expected the claimed slot to be handed back.");
+                assert_eq!(pool.len(), 0);
+
+                let reused = pool.insert_with_key(|key| key);
+                assert_eq!(reused.index, leaked.index);
+                assert!(pool.get(&leaked).is_none(), "Expected the key passed to the panicking constructor never to resolve.");
+                pool.delete(&reused);
+
+                let keys: Vec<_> = (0..2).map(|_| pool.insert_with_key(|key| key)).collect();
+                assert!(keys.iter().all(|key| pool.get(key) == Some(key)), "Expected both slots to remain usable.");
+            }
+        }
+
+
+
+        mod insert_many
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn returns_a_resolving_key_per_item()
+            {
+                let mut pool = ObjectPool::new(10);
+                let keys = pool.insert_many(0..10);
+
+                assert_eq!(keys.len(), 10);
+                for (i, key) in keys.iter().enumerate()
+                {
+                    assert_eq!(pool.get(key), Some(&(i as i32)), "Expected each key to resolve to its item.");
+                }
+            }
+
+            #[test]
+            fn grows_growable_pool_upfront()
+            {
+                let mut pool = ObjectPool::new_growable(1);
+                let keys = pool.insert_many(0..100);
+
+                assert_eq!(keys.len(), 100);
+                assert_eq!(pool.capacity(), 100, "Expected a single grow to the exact size.");
+            }
+
+            #[test]
+            #[should_panic]
+            fn panics_beyond_capacity()
+            {
+                let mut pool = ObjectPool::new(2);
+                pool.insert_many(0..3);
+            }
+        }
+
+        mod delete_many
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn leaves_only_untouched_keys_valid()
+            {
+                let mut pool = ObjectPool::new(10);
+                let keys = pool.insert_many(0..6);
+
+                let stale = keys[0];
+                pool.delete(&stale);
+                pool.insert(100);
+
+                pool.delete_many(&[keys[1], keys[3], stale, keys[3]]);
+
+                assert_eq!(pool.len(), 4, "Expected stale and repeated keys to be ignored.");
+                assert!(pool.get(&keys[1]).is_none());
+                assert!(pool.get(&keys[3]).is_none());
+                for key in [keys[2], keys[4], keys[5]].iter()
+                {
+                    assert!(pool.get(key).is_some(), "Expected untouched keys to remain valid.");
+                }
+            }
+        }
+
+        mod with_slot_reuse
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+                SlotReuse,
+            };
+
+            fn max_generation_after_churn(reuse: SlotReuse) -> u32
+            {
+                let mut pool = ObjectPool::new(16).with_slot_reuse(reuse);
+                let keys: Vec<_> = (0..16).map(|i| pool.insert(i)).collect();
+                for key in &keys[4..] { pool.delete(key); }
+
+                for i in 0..1000
+                {
+                    let key = pool.insert(i);
+                    pool.delete(&key);
+                }
+
+                return pool.data.iter().map(|e| e.generation).max().unwrap();
+            }
+
+            #[test]
+            fn defaults_to_lifo()
+            {
+                let pool: ObjectPool<i32> = ObjectPool::new(10);
+                assert_eq!(pool.slot_reuse(), SlotReuse::Lifo);
+            }
+
+            #[test]
+            fn fifo_spreads_generation_growth()
+            {
+                let lifo = max_generation_after_churn(SlotReuse::Lifo);
+                let fifo = max_generation_after_churn(SlotReuse::Fifo);
+
+                assert!(lifo > 1000, "Expected LIFO to reuse a single slot throughout.");
+                assert!(fifo < lifo / 10, "Expected FIFO to spread reuse across all twelve freed slots.");
+            }
+
+            #[test]
+            fn fifo_reuses_oldest_freed_slot_first()
+            {
+                let mut pool = ObjectPool::new(10).with_slot_reuse(SlotReuse::Fifo);
+                let keys: Vec<_> = (0..3).map(|i| pool.insert(i)).collect();
+
+                pool.delete(&keys[1]);
+                pool.delete(&keys[0]);
+                pool.delete(&keys[2]);
+
+                assert_eq!(pool.insert(3).index, keys[1].index);
+                assert_eq!(pool.insert(4).index, keys[0].index);
+                assert_eq!(pool.insert(5).index, keys[2].index);
+            }
+
+            #[test]
+            fn survives_clear_and_retain()
+            {
+                let mut pool = ObjectPool::new(10).with_slot_reuse(SlotReuse::Fifo);
+                pool.insert(1);
+                pool.clear();
+                assert_eq!(pool.slot_reuse(), SlotReuse::Fifo, "Expected clear to keep the reuse order.");
+
+                let keys: Vec<_> = (0..4).map(|i| pool.insert(i)).collect();
+                pool.retain(|_, item| *item % 2 == 0);
+
+                assert_eq!(pool.insert(4).index, keys[1].index);
+                assert_eq!(pool.insert(5).index, keys[3].index);
+            }
+        }
+
+        mod occupied
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            fn assert_in_sync(pool: &ObjectPool<i32>)
+            {
+                for (index, entry) in pool.data.iter().enumerate()
+                {
+                    assert_eq!(pool.occupied.contains(index), !entry.is_empty(), "Expected slot {} to match its occupancy bit.", index);
+                }
+            }
+
+            #[test]
+            fn tracks_every_way_items_come_and_go()
+            {
+                let mut pool: ObjectPool<i32> = (0..10).collect();
+                assert_in_sync(&pool);
+
+                let keys: Vec<_> = pool.keys().collect();
+                pool.delete(&keys[0]);
+                pool.take(&keys[1]);
+                pool.retain(|_, item| *item != 5);
+                assert_in_sync(&pool);
+
+                pool.insert(10);
+                pool.insert_with_key(|_| 11);
+                pool.shrink_to_fit();
+                assert_in_sync(&pool);
+
+                drop(pool.drain().take(3));
+                assert_in_sync(&pool);
+
+                pool.insert(12);
+                pool.clear();
+                assert_in_sync(&pool);
+            }
+
+            #[test]
+            fn iteration_skips_vacant_runs()
+            {
+                let mut pool = ObjectPool::new(1000);
+                let keys: Vec<_> = (0..1000).map(|i| pool.insert(i)).collect();
+                for key in keys.iter().filter(|key| key.index % 100 != 7) { pool.delete(key); }
+
+                let expected: Vec<_> = (0..10).map(|i| i * 100 + 7).collect();
+                assert!(pool.iter().copied().eq(expected.clone()));
+                assert!(pool.iter_mut().map(|item| *item).eq(expected.clone()));
+                assert!(pool.keys().map(|key| key.index).eq(expected));
+            }
+        }
+
+        mod new_default
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn preallocates_the_default_capacity()
+            {
+                let pool: ObjectPool<i32> = ObjectPool::new_default();
+
+                assert_eq!(ObjectPool::<i32>::default_capacity(), 16);
+                assert_eq!(pool.capacity(), ObjectPool::<i32>::default_capacity(), "Expected the advertised default capacity.");
+                assert!(pool.is_empty());
+            }
+        }
+
+        mod entry_access
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            // Small enough to run under miri, which checks the unchecked indexing for out of bounds access.
+            #[test]
+            fn every_keyed_access_stays_in_bounds()
+            {
+                let mut pool = ObjectPool::new(2);
+                let key1 = pool.insert(String::from("First"));
+                let key2 = pool.insert(String::from("Second"));
+
+                let mut out_of_range = key2;
+                out_of_range.index = 2;
+
+                assert!(pool.get(&out_of_range).is_none(), "Expected an out of range key to miss.");
+                assert!(pool.get_mut(&out_of_range).is_none(), "Expected an out of range key to miss.");
+                assert!(!pool.contains_key(&out_of_range), "Expected an out of range key to miss.");
+                assert!(pool.take(&out_of_range).is_none(), "Expected an out of range key to miss.");
+                assert!(!pool.delete(&out_of_range), "Expected an out of range key to miss.");
+
+                pool.get_mut(&key1).unwrap().push('!');
+                assert_eq!(pool.take(&key1).as_deref(), Some("First!"));
+                assert!(pool.delete(&key2));
+
+                let key3 = pool.insert(String::from("Third"));
+                assert!(pool.get(&key1).is_none(), "Expected a stale key to miss.");
+                assert!(!pool.contains_key(&key2), "Expected a stale key to miss.");
+                assert_eq!(pool.get(&key3).map(String::as_str), Some("Third"));
+            }
+        }
+
+        mod reinsert
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+            use crate::InsertAtError;
+
+            #[test]
+            fn puts_a_taken_item_back_in_its_slot()
+            {
+                let mut pool = ObjectPool::new(4);
+                let _key0 = pool.insert(0);
+                let key1 = pool.insert(1);
+                let _key2 = pool.insert(2);
+
+                let value = pool.take(&key1).unwrap();
+                let new_key = pool.reinsert(key1, value * 10).unwrap();
+
+                assert_eq!(new_key.index, key1.index, "Expected the item to return to its old slot.");
+                assert_eq!(new_key.generation.get(), key1.generation.get() + 1, "Expected the slot's next generation.");
+                assert_eq!(pool.get(&new_key), Some(&10));
+                assert!(pool.get(&key1).is_none(), "Expected the old key to stay invalid.");
+                assert_eq!(pool.len(), 3);
+
+                let key3 = pool.insert(3);
+                assert_eq!(key3.index, 3, "Expected the reinserted slot to have left the free list.");
+            }
+
+            #[test]
+            fn rejects_a_slot_reused_since()
+            {
+                let mut pool = ObjectPool::new(4);
+                let key = pool.insert(1);
+
+                pool.delete(&key);
+                let reused = pool.insert(2);
+
+                assert_eq!(pool.reinsert(key, 1), Err(InsertAtError::Stale(1)));
+                assert_eq!(pool.get(&reused), Some(&2), "Expected the reusing item to be untouched.");
+            }
+
+            #[test]
+            fn rejects_a_key_whose_item_is_present()
+            {
+                let mut pool = ObjectPool::new(4);
+                let key = pool.insert(1);
+
+                assert_eq!(pool.reinsert(key, 2), Err(InsertAtError::Occupied(2)));
+                assert_eq!(pool.get(&key), Some(&1));
+            }
+
+            #[test]
+            fn rejects_a_cleared_slot()
+            {
+                let mut pool = ObjectPool::new(4);
+                let key = pool.insert(1);
+
+                pool.clear();
+
+                assert_eq!(pool.reinsert(key, 1), Err(InsertAtError::Stale(1)));
+                assert!(pool.is_empty());
+            }
+        }
+
+        mod retain_keys
+        {
+            use std::collections::HashSet;
+
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn deletes_every_item_not_in_the_keep_set()
+            {
+                let mut pool = ObjectPool::new(6);
+                let keys: Vec<_> = (0..6).map(|i| pool.insert(i)).collect();
+                let keep: HashSet<_> = [keys[1], keys[4]].iter().copied().collect();
+
+                pool.retain_keys(|key| keep.contains(key));
+
+                assert_eq!(pool.len(), 2, "Expected only the kept items to remain.");
+                assert_eq!(pool.free.len(), 4, "Expected every deleted slot to be freed.");
+                for key in &keys
+                {
+                    assert_eq!(pool.contains_key(key), keep.contains(key), "Expected {:?} to survive only if kept.", key);
+                }
+            }
+        }
+
+        mod extract_if
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn extracts_from_a_sparse_pool()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(1000);
+                let keys: Vec<_> = (0..1000).map(|i| pool.insert(i)).collect();
+                for key in keys.iter().filter(|key| key.index % 100 != 0) { pool.delete(key); }
+
+                let extracted = pool.extract_if(|&item| item % 200 == 0);
+                pool.retain(|_, item| *item != 100);
+
+                assert!(extracted.iter().copied().eq((0..1000).step_by(200)));
+                assert!(pool.iter().copied().eq([300, 500, 700, 900]));
+                assert_eq!(pool.validate(), Ok(()));
+            }
+
+            #[test]
+            fn moves_matching_items_into_a_new_pool()
+            {
+                let mut pool = ObjectPool::new(8);
+                let keys: Vec<_> = (0..8).map(|i| pool.insert(i)).collect();
+                let original_len = pool.len();
+
+                let extracted = pool.extract_if(|item| *item % 3 == 0);
+
+                assert_eq!(original_len, pool.len() + extracted.len(), "Expected every item to end up in exactly one pool.");
+                assert!(extracted.iter().eq(&[0, 3, 6]), "Expected the matching items, in index order.");
+                assert_eq!(extracted.capacity(), 3);
+                assert_eq!(pool.free.len(), 3, "Expected every extracted slot to be freed.");
+
+                for (i, key) in keys.iter().enumerate()
+                {
+                    if i % 3 == 0 { assert!(pool.get(key).is_none(), "Expected the key of an extracted item to be invalid."); }
+                    else          { assert_eq!(pool.get(key), Some(&(i as i32)), "Expected the key of a remaining item to resolve."); }
+                }
+            }
+
+            #[test]
+            fn keeps_growability()
+            {
+                let mut pool = ObjectPool::new_growable(2);
+                pool.insert(1);
+
+                let mut extracted = pool.extract_if(|_| true);
+                extracted.insert(2);
+
+                assert!(pool.is_empty());
+                assert_eq!(extracted.len(), 2, "Expected the new pool to grow like the original.");
+            }
+        }
+
+        mod get_many_unchecked_mut
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            // Small enough to run under miri, which checks the unchecked borrows don't alias or go out of bounds.
+            #[test]
+            fn borrows_distinct_valid_keys()
+            {
+                let mut pool = ObjectPool::new(4);
+                let keys: Vec<_> = (0..4).map(|i| pool.insert(i * 10)).collect();
+                pool.delete(&keys[1]);
+
+                let [a, b, c] = unsafe { pool.get_many_unchecked_mut([keys[3], keys[0], keys[2]]) };
+                *a += 1;
+                *b += 2;
+                *c += 3;
+
+                assert!(pool.iter().eq(&[2, 23, 31]), "Expected each borrow to reach its own item.");
+            }
+        }
+
+        mod cursor_mut
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn sweeps_removing_every_other_item()
+            {
+                let mut pool = ObjectPool::new(8);
+                let keys: Vec<_> = (0..7).map(|i| pool.insert(i)).collect();
+                pool.delete(&keys[2]);
+
+                let mut removed = Vec::new();
+                let mut cursor = pool.cursor_mut();
+                let mut keep = true;
+                while let Some(key) = cursor.key()
+                {
+                    if keep { cursor.move_next(); }
+                    else    { removed.push((key, cursor.remove_current().unwrap())); }
+                    keep = !keep;
+                }
+
+                assert_eq!(removed, [(keys[1], 1), (keys[4], 4), (keys[6], 6)], "Expected every other live item to be removed, in order.");
+                assert!(pool.iter().eq(&[0, 3, 5]), "Expected the rest to survive.");
+                assert_eq!(pool.len(), 3);
+                assert_eq!(pool.free.len(), 4, "Expected the removed slots to join the deleted one on the free list.");
+                for (key, _) in &removed
+                {
+                    assert!(pool.get(key).is_none(), "Expected the key of a removed item to be invalid.");
+                }
+            }
+
+            #[test]
+            fn stays_past_the_end()
+            {
+                let mut pool = ObjectPool::new(4);
+                let key = pool.insert(1);
+
+                let mut cursor = pool.cursor_mut();
+                assert_eq!(cursor.key(), Some(key));
+
+                cursor.move_next();
+                cursor.move_next();
+
+                assert!(cursor.current().is_none());
+                assert!(cursor.key().is_none());
+                assert!(cursor.remove_current().is_none());
+                assert_eq!(pool.len(), 1);
+            }
+
+            #[test]
+            fn is_on_nothing_in_an_empty_pool()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(4);
+                assert!(pool.cursor_mut().current().is_none());
+            }
+        }
+
+        mod with_capacity_and_generation_start
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn never_reissues_keys_of_a_default_pool()
+            {
+                let mut default = ObjectPool::new(16);
+                let mut resumed = ObjectPool::with_capacity_and_generation_start(16, 100);
+
+                let mut default_keys = Vec::new();
+                let mut resumed_keys = Vec::new();
+                for round in 0..40
+                {
+                    let default_key = default.insert(round);
+                    let resumed_key = resumed.insert(round);
+                    assert_eq!(default_key.index, resumed_key.index, "Expected both pools to use the same slots.");
+
+                    default_keys.push(default_key);
+                    resumed_keys.push(resumed_key);
+                    if round % 3 != 0 { default.delete(&default_key); resumed.delete(&resumed_key); }
+                }
+
+                for key in &resumed_keys
+                {
+                    assert!(key.generation.get() > 100, "Expected generations above the start.");
+                    assert!(!default_keys.contains(key), "Expected no key shared with the default pool.");
+                }
+            }
+
+            #[test]
+            fn starts_grown_slots_at_the_same_generation()
+            {
+                let mut pool = ObjectPool::with_capacity_and_generation_start(0, 100);
+                pool.reserve(1);
+
+                assert_eq!(pool.insert(1).generation.get(), 101);
+            }
+        }
+
+        mod get_mut_expect
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn returns_the_item_of_a_valid_key()
+            {
+                let mut pool = ObjectPool::new(4);
+                let key = pool.insert(String::from("Entity"));
+
+                pool.get_mut_expect(&key, "missing entity").push('!');
+                assert_eq!(pool[key], "Entity!");
+            }
+
+            #[test]
+            #[should_panic(expected = "missing entity 42")]
+            fn panics_with_the_supplied_message_on_a_stale_key()
+            {
+                let mut pool = ObjectPool::new(4);
+                let key = pool.insert(1);
+                pool.delete(&key);
+
+                pool.get_mut_expect(&key, &format!("missing entity {}", 42));
+            }
+        }
+
+        mod get_or_default
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn returns_the_existing_item_of_a_valid_key()
+            {
+                let mut pool = ObjectPool::new(4);
+                let key = pool.insert(5);
+
+                let (same_key, item) = pool.get_or_default(&key);
+                *item += 1;
+
+                assert_eq!(same_key, key, "Expected the key to be unchanged.");
+                assert_eq!(pool[key], 6);
+                assert_eq!(pool.len(), 1, "Expected nothing to be inserted.");
+            }
+
+            #[test]
+            fn inserts_the_default_under_a_fresh_key()
+            {
+                let mut pool: ObjectPool<Vec<i32>> = ObjectPool::new(4);
+                let stale = pool.insert(vec![1]);
+                pool.delete(&stale);
+
+                let (new_key, item) = pool.get_or_default(&stale);
+                assert!(item.is_empty(), "Expected the default value.");
+                item.push(2);
+
+                assert_ne!(new_key, stale, "Expected a fresh key.");
+                assert_eq!(pool.get(&new_key), Some(&vec![2]), "Expected the fresh key to resolve.");
+                assert!(pool.get(&stale).is_none(), "Expected the stale key to stay invalid.");
+            }
+        }
+
+        mod shrink_free_list
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn releases_bookkeeping_after_a_delete_burst()
+            {
+                let mut pool = ObjectPool::new(1_000);
+                let keys: Vec<_> = (0..1_000).map(|i| pool.insert(i)).collect();
+                for key in keys.iter().rev() { pool.delete(key); }
+                let before = pool.occupied.capacity();
+
+                let key = pool.insert(-1);
+                assert_eq!(key.index, 0, "Expected the lowest slot, freed last, to be reused first.");
+                pool.shrink_free_list();
+
+                assert!(pool.occupied.capacity() < before, "Expected the occupancy bitmap to shrink.");
+                assert_eq!(pool.capacity(), 1_000, "Expected the slots to be left alone.");
+                assert!(pool.iter().eq(&[-1]));
+
+                let keys: Vec<_> = (0..999).map(|i| pool.insert(i)).collect();
+                assert!(keys.iter().all(|key| pool.contains_key(key)), "Expected the bitmap to grow again for new items.");
+                assert_eq!(pool.iter().count(), 1_000);
+                assert_eq!(pool.get(&key), Some(&-1));
+            }
+        }
+
+        mod validate
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+                Slot,
+            };
+            use crate::error::Corruption;
+
+            fn churned_pool() -> ObjectPool<i32>
+            {
+                let mut pool = ObjectPool::new(4);
+                let keys: Vec<_> = (0..3).map(|i| pool.insert(i)).collect();
+                pool.delete(&keys[0]);
+                pool.delete(&keys[1]);
+                return pool;
+            }
+
+            #[test]
+            fn accepts_a_consistent_pool()
+            {
+                assert_eq!(churned_pool().validate(), Ok(()));
+                assert_eq!(ObjectPool::<i32>::new(0).validate(), Ok(()));
+            }
+
+            #[test]
+            fn rejects_a_duplicate_free_index()
+            {
+                let mut pool = churned_pool();
+
+                // The list runs 1 -> 0; linking 0 back to 1 lists 1 twice.
+                pool.data[0].slot = Slot::Vacant(1);
+
+                assert_eq!(pool.validate(), Err(Corruption::DuplicateFreeIndex { index: 1 }));
+            }
+
+            #[test]
+            fn rejects_an_occupied_free_slot()
+            {
+                let mut pool = churned_pool();
+                pool.data[0].slot = Slot::Occupied(0);
+                pool.occupied.insert(0);
+                pool.count += 1;
+
+                assert_eq!(pool.validate(), Err(Corruption::FreeSlotOccupied { index: 0 }));
+            }
+
+            #[test]
+            fn rejects_a_wrong_count()
+            {
+                let mut pool = churned_pool();
+                pool.count = 3;
+
+                assert_eq!(pool.validate(), Err(Corruption::CountMismatch { count: 3, occupied: 1 }));
+            }
+
+            #[test]
+            fn rejects_a_next_beyond_capacity()
+            {
+                let mut pool = churned_pool();
+                pool.next = 5;
+
+                assert_eq!(pool.validate(), Err(Corruption::NextOutOfRange { next: 5, capacity: 4 }));
+            }
+
+            #[test]
+            fn rejects_occupancy_beyond_capacity()
+            {
+                let mut pool = churned_pool();
+                pool.occupied.insert(70);
+
+                assert_eq!(pool.validate(), Err(Corruption::OccupancyMismatch { index: 70 }));
+            }
+
+            #[test]
+            fn rejects_an_exhausted_base_generation()
+            {
+                let mut pool = churned_pool();
+                pool.base_generation = u32::MAX - 1;
+
+                assert_eq!(pool.validate(), Err(Corruption::RetiredBaseGeneration { generation: u64::from(u32::MAX - 1) }));
+            }
+
+            #[test]
+            fn rejects_a_stale_retired_count()
+            {
+                let mut pool = churned_pool();
+                pool.data[3].generation = u32::MAX - 1;
+
+                assert_eq!(pool.validate(), Err(Corruption::RetiredCountMismatch { count: 0, retired: 1 }));
+            }
+        }
+
+        mod reserve_slot
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+            use crate::InsertAtError;
+
+            #[test]
+            fn resolves_only_once_filled()
+            {
+                let mut pool = ObjectPool::new(4);
+                let key = pool.reserve_slot();
+
+                assert!(pool.get(&key).is_none(), "Expected a reserved key to resolve to nothing.");
+                assert!(!pool.contains_key(&key));
+                assert_eq!(pool.len(), 0, "Expected a reservation not to count as an item.");
+
+                assert_eq!(pool.fill(&key, 1), Ok(()));
+
+                assert_eq!(pool.get(&key), Some(&1));
+                assert_eq!(pool.len(), 1);
+                assert_eq!(pool.validate(), Ok(()));
+            }
+
+            #[test]
+            fn is_not_handed_out_by_insert()
+            {
+                let mut pool = ObjectPool::new(3);
+                let key0 = pool.insert(0);
+                pool.delete(&key0);
+
+                let reserved = pool.reserve_slot();
+                assert_eq!(reserved.index, key0.index, "Expected the freed slot to be reserved.");
+
+                let others = [pool.insert(1), pool.insert(2)];
+                assert!(others.iter().all(|key| key.index != reserved.index), "Expected inserts to skip the reserved slot.");
+                assert!(pool.try_insert(3).is_err(), "Expected the reserved slot to count against capacity.");
+
+                pool.fill(&reserved, 0).unwrap();
+                assert!(pool.iter().eq(&[0, 1, 2]));
+            }
+
+            #[test]
+            fn rejects_keys_other_than_a_pending_reservation()
+            {
+                let mut pool = ObjectPool::new(4);
+                let inserted = pool.insert(1);
+                let taken = pool.insert(2);
+                pool.take(&taken);
+
+                assert_eq!(pool.fill(&inserted, 10), Err(InsertAtError::Occupied(10)));
+                assert_eq!(pool.fill(&taken, 20), Err(InsertAtError::Stale(20)), "Expected a freed slot not to be fillable.");
+
+                let reserved = pool.reserve_slot();
+                pool.clear();
+                assert_eq!(pool.fill(&reserved, 30), Err(InsertAtError::Stale(30)), "Expected clearing to release reservations.");
+            }
+
+            #[test]
+            fn survives_shrink_to_fit()
+            {
+                let mut pool = ObjectPool::new(8);
+                pool.insert(0);
+                let key = pool.reserve_slot();
+
+                pool.shrink_to_fit();
+
+                assert_eq!(pool.capacity(), 2, "Expected the reserved slot to be kept.");
+                assert_eq!(pool.fill(&key, 1), Ok(()));
+            }
+        }
+
+        mod as_vec
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn references_every_item_in_index_order()
+            {
+                let mut pool = ObjectPool::new(8);
+                let keys: Vec<_> = (0..8).map(|i| pool.insert(i)).collect();
+                pool.delete(&keys[0]);
+                pool.delete(&keys[5]);
+                pool.insert(100);
+
+                let values = pool.as_vec();
+
+                assert_eq!(values.len(), pool.len());
+                assert_eq!(values, [&1, &2, &3, &4, &100, &6, &7], "Expected the items in ascending index order.");
+            }
+        }
+
+        mod drop_order
+        {
+            use std::cell::RefCell;
+            use std::rc::Rc;
+
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            struct Recorder(i32, Rc<RefCell<Vec<i32>>>);
+
+            impl Drop for Recorder
+            {
+                fn drop(&mut self) { self.1.borrow_mut().push(self.0); }
+            }
+
+            /// Returns a pool whose items were inserted out of index order, by reusing freed slots.
+            fn shuffled_pool(dropped: &Rc<RefCell<Vec<i32>>>) -> ObjectPool<Recorder>
+            {
+                let mut pool = ObjectPool::new(4);
+                let keys: Vec<_> = (0..4).map(|_| pool.insert(Recorder(-1, dropped.clone()))).collect();
+                for key in &keys { pool.delete(key); }
+                dropped.borrow_mut().clear();
+
+                // Freed slots are reused last-freed first, so these land in slots 3, 2, 1, 0.
+                for i in (0..4).rev() { pool.insert(Recorder(i, dropped.clone())); }
+                return pool;
+            }
+
+            #[test]
+            fn drops_items_in_ascending_index_order()
+            {
+                let dropped = Rc::new(RefCell::new(Vec::new()));
+                let pool = shuffled_pool(&dropped);
+                assert!(pool.iter().map(|item| item.0).eq(0..4), "Expected slot i to hold item i.");
+
+                drop(pool);
+                assert_eq!(*dropped.borrow(), [0, 1, 2, 3]);
+            }
+
+            #[test]
+            fn clear_drops_items_in_ascending_index_order()
+            {
+                let dropped = Rc::new(RefCell::new(Vec::new()));
+                let mut pool = shuffled_pool(&dropped);
+
+                pool.clear();
+                assert_eq!(*dropped.borrow(), [0, 1, 2, 3]);
+            }
+
+            #[test]
+            fn into_iter_drops_unyielded_items_in_ascending_index_order()
+            {
+                let dropped = Rc::new(RefCell::new(Vec::new()));
+                let mut items = shuffled_pool(&dropped).into_iter();
+
+                let first = items.next().expect("Expected the pool to yield an item.");
+                assert_eq!(first.0, 0);
+                drop(first);
+
+                drop(items);
+                assert_eq!(*dropped.borrow(), [0, 1, 2, 3], "Expected the items left in the iterator to drop in slot order.");
+            }
+
+            #[test]
+            fn delete_and_take_still_drop_eagerly()
+            {
+                let dropped = Rc::new(RefCell::new(Vec::new()));
+                let mut pool = ObjectPool::new(4);
+                let key1 = pool.insert(Recorder(1, dropped.clone()));
+                let key2 = pool.insert(Recorder(2, dropped.clone()));
+
+                pool.delete(&key2);
+                assert_eq!(*dropped.borrow(), [2], "Expected delete to drop the item at once.");
+
+                let taken = pool.take(&key1);
+                assert_eq!(*dropped.borrow(), [2], "Expected take to hand the item back undropped.");
+
+                drop(taken);
+                assert_eq!(*dropped.borrow(), [2, 1]);
+            }
+        }
+
+        mod claim_checks
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            #[cfg(debug_assertions)]
+            #[should_panic(expected = "spool: claimed slot 3 is not free")]
+            fn debug_asserts_on_a_free_list_reaching_past_next()
+            {
+                let mut pool = ObjectPool::new(4);
+                pool.insert(0);
+                pool.insert(1);
+
+                // Slot 3 has never been handed out, so it belongs to the unused tail, not the free list.
+                pool.free.push(&mut pool.data, 3);
+
+                pool.insert(2);
+            }
+
+            #[test]
+            #[cfg(debug_assertions)]
+            #[should_panic(expected = "spool: claimed slot 0 is not free")]
+            fn debug_asserts_on_a_next_behind_occupied_slots()
+            {
+                let mut pool = ObjectPool::new(4);
+                pool.insert(0);
+                pool.next = 0;
+
+                pool.insert(1);
+            }
+        }
+
+        mod capacity_remaining
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn counts_free_and_unused_slots_in_every_state()
+            {
+                let mut pool = ObjectPool::new(4);
+                assert_eq!((pool.capacity_remaining(), pool.is_full()), (4, false), "Expected an empty pool to have room for its capacity.");
+
+                let keys: Vec<_> = (0..3).map(|i| pool.insert(i)).collect();
+                assert_eq!((pool.capacity_remaining(), pool.is_full()), (1, false), "Expected only the unused tail to remain.");
+
+                pool.delete(&keys[0]);
+                pool.delete(&keys[2]);
+                assert_eq!((pool.capacity_remaining(), pool.is_full()), (3, false), "Expected freed slots to count.");
+
+                while pool.try_insert(0).is_ok() {}
+                assert_eq!((pool.capacity_remaining(), pool.is_full()), (0, true), "Expected a full pool to have no room.");
+                assert_eq!(pool.len(), pool.capacity());
+            }
+
+            #[test]
+            fn leaves_out_retired_slots()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::from_raw_parts(vec![(u32::MAX - 1, None), (0, None)], vec![], 1, 0);
+
+                assert_eq!(pool.capacity_remaining(), 1);
+                pool.insert(1);
+                assert!(pool.is_full(), "Expected the retired slot never to be counted.");
+            }
+
+            #[test]
+            fn keeps_the_retired_counts_in_step_with_the_slots()
+            {
+                let expect_consistent = |pool: &ObjectPool<i32>, step: &str| {
+                    assert_eq!(pool.validate(), Ok(()), "Expected the retired counts to match the slots after {}.", step);
+                };
+
+                // Every slot has two generations left: one for a first occupant, and a final one.
+                let mut pool = ObjectPool::with_capacity_and_generation_start(8, u32::MAX - 3);
+                let keys: Vec<_> = (0..6).map(|i| pool.insert(i)).collect();
+                pool.reserve_slot();
+                pool.delete(&keys[0]);
+                pool.delete(&keys[1]);
+                expect_consistent(&pool, "deleting");
+                assert_eq!((pool.retired_slots(), pool.capacity_remaining()), (0, 3));
+
+                pool.clear();
+                expect_consistent(&pool, "clearing");
+                assert_eq!((pool.retired_slots(), pool.capacity_remaining()), (4, 4), "Expected the slots cleared of an item to retire.");
+
+                let keys: Vec<_> = (0..2).map(|i| pool.insert(i)).collect();
+                let reserved = pool.reserve_slot();
+                assert_eq!(reserved.index, 6, "Expected the retired slots to be skipped.");
+                pool.delete(&keys[0]);
+                expect_consistent(&pool, "retiring a slot");
+                assert_eq!((pool.retired_slots(), pool.capacity_remaining()), (5, 1), "Expected the reservation not to count as retired.");
+
+                pool.shrink_to_fit();
+                expect_consistent(&pool, "shrinking");
+                assert_eq!((pool.capacity(), pool.capacity_remaining()), (7, 0));
+
+                #[cfg(feature = "std")]
+                {
+                    pool.defragment();
+                    expect_consistent(&pool, "defragmenting");
+                    assert_eq!(pool.retired_slots(), 6, "Expected the released reservation to retire.");
+                }
+            }
+        }
+
+        mod default
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[derive(Debug, PartialEq)]
+            struct NoDefault(i32);
+
+            #[test]
+            fn needs_no_default_item()
+            {
+                let mut pool = ObjectPool::<NoDefault>::default();
+
+                assert_eq!(pool.capacity(), 0);
+                assert!(pool.is_empty());
+                assert!(pool.try_insert(NoDefault(1)).is_err(), "Expected a default pool to have no capacity, like new_empty.");
+
+                let mut pool = ObjectPool::<NoDefault, u8>::default();
+                pool.reserve(1);
+                let key = pool.insert(NoDefault(2));
+                assert_eq!(pool.get(&key), Some(&NoDefault(2)));
+            }
+        }
+
+        mod trait_bounds
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+            use crate::TypedPool;
+
+            /// Clone, but neither `Default`, `PartialEq`, `Eq`, nor `Hash`.
+            #[derive(Clone, Debug)]
+            struct Handle(f32);
+
+            /// Not even `Clone`.
+            struct Unique(i32);
+
+            fn assert_clone<C: Clone>(_: &C) {}
+
+            #[test]
+            fn clone_needs_only_a_clone_item()
+            {
+                let mut pool = ObjectPool::new(4);
+                let key = pool.insert(Handle(1.5));
+
+                let copy = pool.clone();
+                assert_eq!(copy.get(&key).map(|handle| handle.0), Some(1.5));
+
+                let typed: TypedPool<Handle> = TypedPool::new(4);
+                assert_clone(&typed);
+            }
+
+            #[test]
+            fn iter_clones_without_a_clone_item()
+            {
+                let mut pool = ObjectPool::new(4);
+                pool.insert(Unique(1));
+                pool.insert(Unique(2));
+
+                let iter = pool.iter();
+                assert_eq!(iter.clone().map(|unique| unique.0).sum::<i32>(), 3);
+                assert_eq!(iter.count(), 2);
+            }
+        }
 
-#[cfg(test)]
-mod tests
-{
-    use super::*;
-
-    mod object_pool
-    {
-        mod new
+        mod key_for_index
         {
             use super::super::{
                 Pool,
@@ -394,218 +8862,331 @@ mod tests
             };
 
             #[test]
-            fn correctly_initializes_a_pool()
+            fn round_trips_inserted_keys()
             {
-                let pool: ObjectPool<i32> = ObjectPool::new(10);
+                let mut pool = ObjectPool::new(4);
+                let old = pool.insert(0);
+                pool.delete(&old);
+                let keys: Vec<_> = (0..3).map(|i| pool.insert(i)).collect();
 
-                assert_eq!(pool.capacity(), 10);
-                assert_eq!(pool.count, 0);
-                assert_eq!(pool.next, 0);
-                assert_eq!(pool.free.len(), 0);
-                assert_eq!(pool.data.len(), pool.capacity());
+                for key in &keys
+                {
+                    assert_eq!(pool.key_for_index(key.index), Some(*key), "Expected the index to rebuild its key, generation included.");
+                }
+                assert_ne!(pool.key_for_index(old.index), Some(old), "Expected the live generation, not the old one.");
+            }
+
+            #[test]
+            fn returns_none_for_freed_and_out_of_range_indices()
+            {
+                let mut pool = ObjectPool::new(4);
+                let key = pool.insert(0);
+                pool.insert(1);
+                pool.delete(&key);
+
+                assert_eq!(pool.key_for_index(key.index), None, "Expected a freed slot to have no key.");
+                assert_eq!(pool.key_for_index(3), None, "Expected an unused slot to have no key.");
+                assert_eq!(pool.key_for_index(4), None, "Expected an out of range index to have no key.");
             }
         }
 
-        mod insert
+        mod builder
         {
             use super::super::{
                 Pool,
                 ObjectPool,
+                SlotReuse,
             };
 
             #[test]
-            fn correctly_updates_pool_state()
+            fn defaults_match_new_default()
             {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                let key = pool.insert(100);
+                let built: ObjectPool<i32> = ObjectPool::builder().build();
+                let default: ObjectPool<i32> = ObjectPool::new_default();
 
-                assert!(pool.data[key.index].data.is_some());
-                assert_eq!(pool.data[key.index].data.unwrap(), 100);
-                assert_eq!(pool.capacity(), 10);
-                assert_eq!(pool.count, 1);
-                assert_eq!(pool.next, 1);
-                assert_eq!(pool.free.len(), 0);
-                assert_eq!(pool.data.len(), pool.capacity());
+                assert_eq!(built.capacity(), default.capacity());
+                assert_eq!(built.is_growable(), default.is_growable());
+                assert_eq!(built.slot_reuse(), default.slot_reuse());
+            }
 
-                pool.delete(&key);
+            #[test]
+            fn builds_growable_fifo_pool()
+            {
+                let mut pool = ObjectPool::builder().capacity(2).growable(true).free_policy(SlotReuse::Fifo).build();
+                let keys: Vec<_> = (0..3).map(|i| pool.insert(i)).collect();
+                assert_eq!(pool.capacity(), 4, "Expected the pool to grow rather than panic.");
 
-                let key = pool.insert(200);
+                pool.delete(&keys[0]);
+                pool.delete(&keys[1]);
+                let key = pool.insert(3);
 
-                assert!(pool.data[key.index].data.is_some());
-                assert_eq!(pool.data[key.index].data.unwrap(), 200);
-                assert_eq!(pool.capacity(), 10);
-                assert_eq!(pool.count, 1);
-                assert_eq!(pool.next, 1);
-                assert_eq!(pool.free.len(), 0);
-                assert_eq!(pool.data.len(), pool.capacity());
+                assert_eq!(key.index, keys[0].index, "Expected the oldest freed slot to be reused first.");
             }
 
             #[test]
-            fn returns_valid_key_pointing_to_expected_data()
+            fn builds_fixed_pool_with_generation_start()
             {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                let key = pool.insert(100);
+                let mut pool = ObjectPool::builder().capacity(1).generation_start(100).build();
+                let key = pool.insert(1);
 
-                assert_eq!(key.index, 0, "Expected index of first inserted element to be 0.");
-                assert_eq!(key.generation, 1, "Expected generation of first inserted element to be 1.");
-                assert_eq!(pool.data[key.index].generation, key.generation, "Expected generation of stored item to match key.");
-                assert!(pool.data[key.index].data.is_some(), "Expected data at key index to be Some().");
-                assert_eq!(*pool.data[key.index].data.as_ref().unwrap(), 100, "Expected value at key index to be 100.");
+                assert!(key.generation.get() > 100, "Expected generations to start above 100.");
+                assert!(pool.try_insert(2).is_err(), "Expected a fixed pool to refuse inserts once full.");
             }
 
+            #[cfg(feature = "pool-id")]
             #[test]
-            #[should_panic]
-            fn should_panic_if_full()
+            fn builds_pool_without_id()
             {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                for i in 0..10 { pool.insert(i); }
+                let mut tagged = ObjectPool::builder().build();
+                let mut untagged = ObjectPool::builder().with_pool_id(false).build();
+                let tagged_key = tagged.insert(1);
+                let untagged_key = untagged.insert(2);
 
-                pool.insert(100);
+                assert_eq!(tagged.get(&untagged_key), Some(&1), "Expected an untagged key to be accepted by any pool.");
+                assert_eq!(untagged.get(&tagged_key), Some(&2), "Expected an untagged pool to accept any key.");
             }
         }
 
-        mod get
+        mod clear_into
         {
             use super::super::{
                 Pool,
-                PoolKey,
                 ObjectPool,
             };
 
             #[test]
-            fn returns_some_reference_to_entry_specified()
+            fn moves_exactly_the_live_items_in_index_order()
             {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                let key1 = pool.insert(100);
-                let key2 = pool.insert(200);
-                let key3 = pool.insert(300);
-
-                // Out of order 'cause :shrug:
-                let get2 = pool.get(&key2);
-                let get1 = pool.get(&key1);
-                let get3 = pool.get(&key3);
+                let mut pool = ObjectPool::new(10);
+                let keys: Vec<_> = (0..6).map(|i| pool.insert(i)).collect();
+                pool.delete(&keys[1]);
+                pool.delete(&keys[4]);
 
-                assert!(get1.is_some());
-                assert_eq!(*get1.unwrap(), 100);
-
-                assert!(get2.is_some());
-                assert_eq!(*get2.unwrap(), 200);
+                let mut out = vec![-1];
+                pool.clear_into(&mut out);
 
-                assert!(get3.is_some());
-                assert_eq!(*get3.unwrap(), 300);
+                assert_eq!(out, [-1, 0, 2, 3, 5], "Expected the live items appended after the existing contents.");
+                assert!(pool.is_empty(), "Expected the pool to end empty.");
+                assert_eq!(pool.iter().count(), 0);
             }
 
             #[test]
-            fn returns_none_if_key_has_invalid_index()
+            fn invalidates_previous_keys()
             {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                pool.insert(100);
+                let mut pool = ObjectPool::new(10);
+                let key = pool.insert(1);
 
-                let key_at_cap = PoolKey { index: 10, generation: 0 };
-                let get_at_cap = pool.get(&key_at_cap);
-                assert!(get_at_cap.is_none());
+                pool.clear_into(&mut Vec::new());
+                let reused = pool.insert(2);
 
-                let key_over_cap = PoolKey { index: 1000, generation: 0 };
-                let get_over_cap = pool.get(&key_over_cap);
-                assert!(get_over_cap.is_none());
+                assert_eq!(reused.index, key.index);
+                assert!(pool.get(&key).is_none(), "Expected the clear to bump the generation, as clear does.");
+            }
+        }
+
+        mod get_by_index
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn returns_current_occupant_regardless_of_generation()
+            {
+                let mut pool = ObjectPool::new(4);
+                let old = pool.insert(1);
+                pool.delete(&old);
+                let new = pool.insert(2);
+
+                assert_eq!(old.index, new.index);
+                assert_ne!(old.generation, new.generation);
+                assert_eq!(pool.get_by_index(old.index), Some(&2), "Expected the current occupant, whatever the generation.");
             }
 
             #[test]
-            fn returns_none_if_generation_mismatch()
+            fn returns_none_for_vacant_and_out_of_range_indices()
             {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                let key = pool.insert(100);
+                let mut pool = ObjectPool::new(4);
+                let key = pool.insert(1);
+                pool.delete(&key);
 
-                pool.data[key.index].generation = 42;
+                assert_eq!(pool.get_by_index(key.index), None, "Expected a freed slot to hold nothing.");
+                assert_eq!(pool.get_by_index(3), None, "Expected an unused slot to hold nothing.");
+                assert_eq!(pool.get_by_index(4), None, "Expected an out of range index to hold nothing.");
+            }
+        }
 
-                let get = pool.get(&key);
-                assert!(get.is_none());
+        mod append
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+
+            #[test]
+            fn conserves_count_and_empties_other()
+            {
+                let mut pool = ObjectPool::new(4);
+                let mut other = ObjectPool::new(10);
+                pool.extend(0..3);
+                let keys: Vec<_> = (0..6).map(|i| other.insert(i)).collect();
+                other.delete(&keys[2]);
+
+                let remap = pool.append(&mut other);
+
+                assert_eq!(remap.len(), 5);
+                assert_eq!(pool.len(), 8, "Expected every item to be moved across.");
+                assert!(pool.capacity() >= 8, "Expected a fixed pool to grow to fit.");
+                assert!(other.is_empty(), "Expected the other pool to be emptied.");
+                assert!(keys.iter().all(|key| !other.contains_key(key)), "Expected keys into the other pool to be invalidated.");
             }
 
             #[test]
-            fn returns_none_if_data_is_none()
+            fn maps_every_old_key_to_its_value()
             {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                let key = pool.insert(100);
+                let mut pool = ObjectPool::new(4);
+                let mut other = ObjectPool::new(10);
+                pool.insert(String::from("Master"));
+                let keys: Vec<_> = (0..5).map(|i| other.insert(i.to_string())).collect();
+                other.delete(&keys[1]);
+                let expected: Vec<_> = keys.iter().filter_map(|key| other.get(key).cloned().map(|value| (*key, value))).collect();
 
-                pool.data[key.index].data = None;
+                let remap = pool.append(&mut other);
 
-                let get = pool.get(&key);
-                assert!(get.is_none());
+                assert_eq!(remap.iter().map(|&(old, _)| old).collect::<Vec<_>>(), expected.iter().map(|&(old, _)| old).collect::<Vec<_>>());
+                for ((_, new), (_, value)) in remap.iter().zip(&expected)
+                {
+                    assert_eq!(pool.get(new), Some(value), "Expected each new key to resolve to the item its old key did.");
+                }
             }
         }
 
-        mod get_mut
+        mod try_get
         {
             use super::super::{
+                AccessError,
                 Pool,
-                PoolKey,
                 ObjectPool,
             };
 
             #[test]
-            fn returns_some_reference_to_entry_specified()
+            fn returns_the_item_for_a_valid_key()
             {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                let key1 = pool.insert(100);
-                let key2 = pool.insert(200);
-                let key3 = pool.insert(300);
+                let mut pool = ObjectPool::new(10);
+                let key = pool.insert(1);
 
-                // Out of order 'cause :shrug:
-                let get2 = pool.get_mut(&key2);
-                assert!(get2.is_some());
-                assert_eq!(*get2.unwrap(), 200);
+                assert_eq!(pool.try_get(&key), Ok(&1));
+                *pool.try_get_mut(&key).unwrap() += 1;
+                assert_eq!(pool.get(&key), Some(&2));
+            }
 
-                let get1 = pool.get_mut(&key1);
-                assert!(get1.is_some());
-                assert_eq!(*get1.unwrap(), 100);
+            #[test]
+            fn reports_out_of_range()
+            {
+                let mut big = ObjectPool::new(10);
+                let mut pool: ObjectPool<i32> = ObjectPool::new(1);
+                let key = (0..5).map(|i| big.insert(i)).last().unwrap();
 
-                let get3 = pool.get_mut(&key3);
-                assert!(get3.is_some());
-                assert_eq!(*get3.unwrap(), 300);
+                assert_eq!(pool.try_get(&key), Err(AccessError::OutOfRange));
+                assert_eq!(pool.try_get_mut(&key), Err(AccessError::OutOfRange));
             }
 
             #[test]
-            fn returns_none_if_key_has_invalid_index()
+            fn reports_stale_generation()
             {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                pool.insert(100);
+                let mut pool = ObjectPool::new(10);
+                let key = pool.insert(1);
+                pool.delete(&key);
+                pool.insert(2);
 
-                let key_at_cap = PoolKey { index: 10, generation: 0 };
-                let get_at_cap = pool.get_mut(&key_at_cap);
-                assert!(get_at_cap.is_none());
+                assert_eq!(pool.try_get(&key), Err(AccessError::StaleGeneration));
+                assert_eq!(pool.try_get_mut(&key), Err(AccessError::StaleGeneration));
+            }
 
-                let key_over_cap = PoolKey { index: 1000, generation: 0 };
-                let get_over_cap = pool.get_mut(&key_over_cap);
-                assert!(get_over_cap.is_none());
+            #[test]
+            fn reports_empty()
+            {
+                let mut pool = ObjectPool::new(10);
+                let key = pool.insert(1);
+                pool.delete(&key);
+
+                assert_eq!(pool.try_get(&key), Err(AccessError::Empty));
+                assert_eq!(pool.try_get_mut(&key), Err(AccessError::Empty));
             }
 
+            #[cfg(feature = "pool-id")]
             #[test]
-            fn returns_none_if_generation_mismatch()
+            fn reports_foreign_key()
             {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                let key = pool.insert(100);
+                let mut other = ObjectPool::new(10);
+                let mut pool = ObjectPool::new(10);
+                let key = other.insert(1);
+                pool.insert(1);
 
-                pool.data[key.index].generation = 42;
+                assert_eq!(pool.try_get(&key), Err(AccessError::ForeignKey));
+                assert_eq!(pool.try_get_mut(&key), Err(AccessError::ForeignKey));
+            }
+        }
 
-                let get = pool.get_mut(&key);
-                assert!(get.is_none());
+        mod iteration_order
+        {
+            use super::super::{
+                Pool,
+                PoolKey,
+                ObjectPool,
+            };
+
+            fn pool_with_interior_gaps() -> (ObjectPool<usize>, Vec<PoolKey>)
+            {
+                let mut pool = ObjectPool::new(16);
+                let keys: Vec<_> = (0..10).map(|i| pool.insert(i)).collect();
+                for &i in &[1, 4, 5, 8] { pool.delete(&keys[i]); }
+
+                return (pool, keys);
             }
 
             #[test]
-            fn returns_none_if_data_is_none()
+            fn yields_ascending_index_order_after_interior_deletes()
             {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                let key = pool.insert(100);
+                let (mut pool, _) = pool_with_interior_gaps();
+                let expected = [0, 2, 3, 6, 7, 9];
+
+                assert!(pool.iter().copied().eq(expected.iter().copied()), "Expected iter in ascending index order.");
+                assert!(pool.values().copied().eq(expected.iter().copied()), "Expected values in ascending index order.");
+                assert!(pool.iter_mut().map(|item| *item).eq(expected.iter().copied()), "Expected iter_mut in ascending index order.");
+                assert!(pool.keys().map(|key| key.index).eq(expected.iter().copied()), "Expected keys in ascending index order.");
+                assert!(pool.iter_with_keys().map(|(key, _)| key.index).eq(expected.iter().copied()));
+                assert!(pool.iter_mut_with_keys().map(|(key, _)| key.index).eq(expected.iter().copied()));
+            }
 
-                pool.data[key.index].data = None;
+            #[test]
+            fn orders_by_index_not_insertion()
+            {
+                let (mut pool, _) = pool_with_interior_gaps();
+                pool.insert(100);
+                pool.insert(101);
 
-                let get = pool.get_mut(&key);
-                assert!(get.is_none());
+                let indices: Vec<_> = pool.keys().map(|key| key.index).collect();
+                assert!(indices.windows(2).all(|w| w[0] < w[1]), "Expected reused slots to be visited at their index.");
+                assert!(pool.iter().position(|&item| item == 101) < pool.iter().position(|&item| item == 9));
+            }
+
+            #[test]
+            fn reverses_into_descending_index_order()
+            {
+                let (mut pool, _) = pool_with_interior_gaps();
+                let expected = [9, 7, 6, 3, 2, 0];
+
+                assert!(pool.iter().rev().copied().eq(expected.iter().copied()));
+                assert!(pool.iter_mut().rev().map(|item| *item).eq(expected.iter().copied()));
+                assert!(pool.keys().rev().map(|key| key.index).eq(expected.iter().copied()));
+                assert!(pool.iter_with_keys().rev().map(|(key, _)| key.index).eq(expected.iter().copied()));
+                assert!(pool.iter_mut_with_keys().rev().map(|(key, _)| key.index).eq(expected.iter().copied()));
             }
         }
 
-        mod take
+        mod get_mut_with_key
         {
             use super::super::{
                 Pool,
@@ -614,319 +9195,506 @@ mod tests
             };
 
             #[test]
-            fn replaces_item_with_none_and_pushes_index_to_free()
+            fn returns_the_input_key_for_a_valid_lookup()
             {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                let key = pool.insert(100);
+                let mut pool = ObjectPool::new(4);
+                let old = pool.insert(1);
+                pool.delete(&old);
+                let key = pool.reinsert(old, 2).unwrap();
 
-                let old_count = pool.count;
-                let old_free_len = pool.free.len();
-
-                let taken = pool.take(&key);
+                let (current, item) = pool.get_mut_with_key(&key).unwrap();
+                *item += 1;
 
-                assert!(pool.data[key.index].data.is_none(), "Expected data to be set to None.");
-                assert_eq!(pool.data[key.index].generation, key.generation, "Expected generation to remain unchanged.");
-                assert_eq!(pool.count, old_count - 1, "Expected count to be decremented.");
-                assert_eq!(pool.free.len(), old_free_len + 1, "Expected free list length to be incremented.");
+                assert_eq!(current, key, "Expected the returned key to equal the one looked up.");
+                assert_eq!(pool.get(&key), Some(&3));
+            }
 
-                let free_item = pool.free.last();
-                assert!(free_item.is_some());
-                assert_eq!(*free_item.unwrap(), key.index, "Expected key index to be most recent addition to free list.");
+            #[test]
+            fn tags_an_unpacked_key_with_the_pool_id()
+            {
+                let mut pool = ObjectPool::new(4);
+                let key = pool.insert(1);
+                let unpacked = PoolKey::from_u64(key.as_u64()).unwrap();
 
-                assert!(taken.is_some());
-                assert_eq!(taken.unwrap(), 100, "Expected taken value to match what was inserted.");
+                let (current, _) = pool.get_mut_with_key(&unpacked).unwrap();
+                assert_eq!(current, key, "Expected the canonical key, as issued by the pool.");
             }
 
             #[test]
-            fn returns_none_if_key_has_invalid_index()
+            fn returns_none_for_stale_and_empty_keys()
             {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                let key = pool.insert(100);
+                let mut pool = ObjectPool::new(4);
+                let key = pool.insert(1);
+                pool.delete(&key);
+                assert!(pool.get_mut_with_key(&key).is_none(), "Expected nothing for a deleted item.");
 
-                let old_count = pool.count;
-                let old_free_len = pool.free.len();
+                pool.insert(2);
+                assert!(pool.get_mut_with_key(&key).is_none(), "Expected nothing for a stale key.");
+            }
+        }
+    }
 
-                let key_at_cap = PoolKey { index: 1000, generation: 0 };
-                let taken = pool.take(&key_at_cap);
+    #[cfg(feature = "serde")]
+    mod serde
+    {
+        use super::{
+            Pool,
+            ObjectPool,
+        };
 
-                assert!(pool.data[key.index].data.is_some(), "Expected data to be unchanged.");
-                assert!(taken.is_none(), "Expected taken value to be None.");
-                assert_eq!(pool.data[key.index].generation, key.generation, "Expected generation to remain unchanged.");
-                assert_eq!(pool.count, old_count, "Expected count to be unchanged.");
-                assert_eq!(pool.free.len(), old_free_len, "Expected free list length to be unchanged.");
+        #[test]
+        fn round_trips_through_json_keeping_keys_valid()
+        {
+            let mut pool: ObjectPool<String> = ObjectPool::new(10);
+            let keys: Vec<_> = (0..8).map(|i| pool.insert(format!("Item {}", i))).collect();
 
+            pool.delete(&keys[2]);
+            pool.take(&keys[5]);
 
-                let key_over_cap = PoolKey { index: 1000, generation: 0 };
-                let taken = pool.take(&key_over_cap);
+            let reused = pool.insert(String::from("Reused"));
+            pool.delete(&keys[6]);
 
-                assert!(pool.data[key.index].data.is_some(), "Expected data to be unchanged.");
-                assert!(taken.is_none(), "Expected taken value to be None.");
-                assert_eq!(pool.data[key.index].generation, key.generation, "Expected generation to remain unchanged.");
-                assert_eq!(pool.count, old_count, "Expected count to be unchanged.");
-                assert_eq!(pool.free.len(), old_free_len, "Expected free list length to be unchanged.");
+            let json = serde_json::to_string(&pool).unwrap();
+            let restored: ObjectPool<String> = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(restored, pool, "Expected internal state to round trip exactly.");
+            assert_eq!(restored.len(), pool.len());
+            assert_eq!(restored.capacity(), pool.capacity());
+
+            for (i, key) in keys.iter().enumerate()
+            {
+                match i
+                {
+                    2 | 5 | 6 => assert!(restored.get(key).is_none(), "Expected stale key {} to resolve to None.", i),
+                    _         => assert_eq!(restored.get(key), Some(&format!("Item {}", i))),
+                }
             }
+            assert_eq!(restored.get(&reused).map(String::as_str), Some("Reused"));
+        }
 
-            #[test]
-            fn returns_none_if_generation_mismatch()
+        #[test]
+        fn round_trip_is_content_eq()
+        {
+            let mut pool: ObjectPool<String> = ObjectPool::new_growable(4);
+            let keys: Vec<_> = (0..6).map(|i| pool.insert(format!("Item {}", i))).collect();
+            pool.delete(&keys[0]);
+            pool.delete(&keys[4]);
+
+            let json = serde_json::to_string(&pool).unwrap();
+            let restored: ObjectPool<String> = serde_json::from_str(&json).unwrap();
+
+            assert!(restored.content_eq(&pool), "Expected the same items under the same keys after a round trip.");
+        }
+
+        #[test]
+        fn round_trips_free_list_so_reuse_order_is_preserved()
+        {
+            let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+            let keys: Vec<_> = (0..5).map(|i| pool.insert(i)).collect();
+
+            pool.delete(&keys[1]);
+            pool.delete(&keys[3]);
+
+            let json = serde_json::to_string(&pool).unwrap();
+            let mut restored: ObjectPool<i32> = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(restored.insert(100), pool.insert(100));
+            assert_eq!(restored.insert(200), pool.insert(200));
+            assert_eq!(restored.insert(300), pool.insert(300));
+        }
+
+        fn tampered_json(from: &str, to: &str) -> Result<ObjectPool<i32>, serde_json::Error>
+        {
+            let mut pool: ObjectPool<i32> = ObjectPool::new(4);
+            let key = pool.insert(1);
+            pool.insert(2);
+            pool.delete(&key);
+
+            let json = serde_json::to_string(&pool).unwrap();
+            assert!(json.contains(from), "Expected {:?} in {}", from, json);
+            return serde_json::from_str(&json.replacen(from, to, 1));
+        }
+
+        #[test]
+        fn rejects_inconsistent_internals()
+        {
+            assert!(tampered_json("\"count\":1", "\"count\":1").is_ok());
+
+            let error = tampered_json("\"count\":1", "\"count\":0").unwrap_err();
+            assert!(error.to_string().contains("count 0 doesn't match the 1 occupied slots"), "Expected the corruption in the error, got {}", error);
+
+            assert!(tampered_json("\"head\":0", "\"head\":1").is_err(), "Expected a free list linking an occupied slot to be rejected.");
+            assert!(tampered_json("\"head\":0", "\"head\":9").is_err(), "Expected a free list linking past next to be rejected.");
+            assert!(tampered_json("\"words\":[2]", "\"words\":[3]").is_err(), "Expected a stray occupancy bit to be rejected.");
+            assert!(tampered_json("\"words\":[2]", "\"words\":[66]").is_err(), "Expected an occupancy bit beyond capacity to be rejected.");
+        }
+    }
+
+    #[cfg(feature = "rkyv")]
+    mod rkyv
+    {
+        use rkyv::rancor::Error;
+
+        use super::{
+            Pool,
+            PoolKey,
+            ObjectPool,
+            ArchivedObjectPool,
+            ArchivedPoolKey,
+        };
+
+        #[test]
+        fn resolves_keys_against_the_archive()
+        {
+            let mut pool: ObjectPool<String> = ObjectPool::new(10);
+            let keys: Vec<_> = (0..6).map(|i| pool.insert(format!("Item {}", i))).collect();
+            pool.delete(&keys[1]);
+            pool.delete(&keys[4]);
+            let reused = pool.insert(String::from("Reused"));
+
+            let bytes = rkyv::to_bytes::<Error>(&pool).unwrap();
+            let archived = rkyv::access::<ArchivedObjectPool<String>, Error>(&bytes).unwrap();
+
+            assert_eq!((archived.len(), archived.capacity()), (pool.len(), pool.capacity()));
+            for (i, key) in keys.iter().enumerate()
             {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                let key = pool.insert(100);
+                match i
+                {
+                    1 | 4 => assert!(archived.get(key).is_none(), "Expected stale key {} to resolve to None.", i),
+                    _     => assert_eq!(archived.get(key).map(|item| item.as_str()), Some(format!("Item {}", i).as_str())),
+                }
+            }
+            assert_eq!(archived.get(&reused).map(|item| item.as_str()), Some("Reused"));
+        }
+
+        #[test]
+        fn round_trips_pools_and_keys()
+        {
+            let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+            let keys: Vec<_> = (0..5).map(|i| pool.insert(i)).collect();
+            pool.delete(&keys[2]);
+
+            let bytes = rkyv::to_bytes::<Error>(&pool).unwrap();
+            let mut restored = rkyv::from_bytes::<ObjectPool<i32>, Error>(&bytes).unwrap();
+            assert!(restored.content_eq(&pool));
+            assert_eq!(restored.insert(10), pool.insert(10), "Expected the free list to carry over.");
+
+            let bytes = rkyv::to_bytes::<Error>(&keys[3]).unwrap();
+            let archived_key = rkyv::access::<ArchivedPoolKey, Error>(&bytes).unwrap();
+            let key: PoolKey = rkyv::deserialize::<PoolKey, Error>(archived_key).unwrap();
+            assert_eq!(pool.get(&key), Some(&3));
+        }
+    }
+
+    #[cfg(feature = "postcard")]
+    mod postcard
+    {
+        use super::{
+            CompactPool,
+            Pool,
+            PoolId,
+            ObjectPool,
+            SlotReuse,
+        };
+
+        #[test]
+        fn round_trips_keeping_keys_valid_and_stale()
+        {
+            let mut pool: ObjectPool<String> = ObjectPool::new_growable(10);
+            let keys: Vec<_> = (0..8).map(|i| pool.insert(format!("Item {}", i))).collect();
+            pool.delete(&keys[2]);
+            pool.delete(&keys[5]);
+            let reused = pool.insert(String::from("Reused"));
+            pool.delete(&keys[6]);
 
-                let old_count = pool.count;
-                let old_free_len = pool.free.len();
+            let mut restored = ObjectPool::<String>::from_bytes(&pool.to_bytes().unwrap()).unwrap();
 
-                let mut bad_key = key;
-                bad_key.generation = 100;
-                let taken = pool.take(&bad_key);
+            assert!(restored.content_eq(&pool), "Expected the same items under the same keys.");
+            assert_eq!(restored.capacity(), pool.capacity());
+            assert_eq!(restored.get(&reused).map(String::as_str), Some("Reused"));
 
-                assert!(taken.is_none(), "Expected taken value to be None.");
-                assert!(pool.data[key.index].data.is_some(), "Expected data to be unchanged.");
-                assert_eq!(pool.data[key.index].generation, key.generation, "Expected generation to remain unchanged.");
-                assert_eq!(pool.count, old_count, "Expected count to be unchanged.");
-                assert_eq!(pool.free.len(), old_free_len, "Expected free list length to be unchanged.");
+            for _ in 0..20 { restored.insert(String::from("New")); }
+            for &i in &[2, 5, 6]
+            {
+                assert!(restored.get(&keys[i]).is_none(), "Expected stale key {} to stay stale after its slot is reused.", i);
             }
+        }
 
-            #[test]
-            fn returns_none_if_data_is_none()
+        #[test]
+        fn size_follows_len_not_capacity()
+        {
+            let build = |capacity: usize|
             {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                let key = pool.insert(100);
+                let mut pool = ObjectPool::new(capacity);
+                let keys: Vec<_> = (0..capacity).map(|i| pool.insert(i as u32)).collect();
+                for key in &keys[10..] { pool.delete(key); }
+                pool
+            };
 
-                let old_count = pool.count;
-                let old_free_len = pool.free.len();
+            let small = build(20).to_bytes().unwrap();
+            let large = build(20_000).to_bytes().unwrap();
 
-                pool.data[key.index].data = None;
-                let taken = pool.take(&key);
+            assert!(large.len() <= small.len() + 4, "Expected 10 items to encode alike whatever the capacity, got {} and {} bytes.", small.len(), large.len());
+            assert!(large.len() < 100);
+        }
 
-                assert!(taken.is_none(), "Expected taken value to be None.");
-                assert_eq!(pool.data[key.index].generation, key.generation, "Expected generation to remain unchanged.");
-                assert_eq!(pool.count, old_count, "Expected count to be unchanged.");
-                assert_eq!(pool.free.len(), old_free_len, "Expected free list length to be unchanged.");
-            }
+        #[test]
+        fn rejects_inconsistent_bytes()
+        {
+            let mut pool = ObjectPool::new(4);
+            pool.insert(1u8);
+            let mut bytes = pool.to_bytes().unwrap();
+
+            bytes[0] = 0; // Capacity 0, yet an item at slot 0.
+            assert!(ObjectPool::<u8>::from_bytes(&bytes).is_err());
+            assert!(ObjectPool::<u8>::from_bytes(&[]).is_err());
         }
 
-        mod delete
+        fn encode(capacity: usize, vacant_generation: u32) -> Vec<u8>
         {
-            use super::super::{
-                Pool,
-                PoolKey,
-                ObjectPool,
+            let compact: CompactPool<i32, u32> = CompactPool {
+                capacity,
+                next: 0,
+                growable: false,
+                reuse: SlotReuse::Lifo,
+                vacant_generation,
+                retired: Vec::new(),
+                items: Vec::new(),
+                id: PoolId::NONE,
             };
+            return ::postcard::to_allocvec(&compact).unwrap();
+        }
 
-            #[test]
-            fn replaces_item_with_none_and_pushes_index_to_free()
-            {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                let key = pool.insert(100);
+        #[test]
+        fn rejects_a_capacity_too_large_to_allocate()
+        {
+            let bytes = encode(1 << 40, 0);
+            assert!(bytes.len() < 16, "Expected a hostile capacity to cost only a few bytes.");
 
-                let old_count = pool.count;
-                let old_free_len = pool.free.len();
+            assert_eq!(ObjectPool::<i32>::from_bytes(&bytes).unwrap_err(), ::postcard::Error::DeserializeBadEncoding);
+            assert!(ObjectPool::<i32>::from_bytes(&encode(8, 0)).is_ok());
+        }
 
-                pool.delete(&key);
+        #[test]
+        fn rejects_an_exhausted_vacant_generation()
+        {
+            assert!(ObjectPool::<i32>::from_bytes(&encode(8, u32::MAX - 2)).is_ok());
+            assert!(ObjectPool::<i32>::from_bytes(&encode(8, u32::MAX - 1)).is_err(), "Expected a generation no slot could be claimed at to be rejected.");
+            assert!(ObjectPool::<i32>::from_bytes(&encode(8, u32::MAX)).is_err());
+        }
+    }
 
-                assert!(pool.data[key.index].data.is_none(), "Expected data to be set to None.");
-                assert_eq!(pool.data[key.index].generation, key.generation, "Expected generation to remain unchanged.");
-                assert_eq!(pool.count, old_count - 1, "Expected count to be decremented.");
-                assert_eq!(pool.free.len(), old_free_len + 1, "Expected free list length to be incremented.");
+    #[cfg(feature = "allocator_api")]
+    mod allocator_api
+    {
+        use std::alloc::{ AllocError, Allocator, Global, Layout };
+        use std::cell::Cell;
+        use std::num::NonZeroU32;
+        use std::ptr::NonNull;
+
+        use super::{
+            Pool,
+            ObjectPool,
+            PoolError,
+        };
 
-                let free_item = pool.free.last();
-                assert!(free_item.is_some());
-                assert_eq!(*free_item.unwrap(), key.index, "Expected key index to be most recent addition to free list.");
+        thread_local!
+        {
+            static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+        }
+
+        /// Counts the allocations made on this thread, leaving the rest to `Global`.
+        #[derive(Default)]
+        struct Counting;
+
+        unsafe impl Allocator for Counting
+        {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError>
+            {
+                ALLOCATIONS.with(|count| count.set(count.get() + 1));
+                Global.allocate(layout)
             }
 
-            #[test]
-            fn does_nothing_if_key_has_invalid_index()
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout)
             {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                let key = pool.insert(100);
+                Global.deallocate(ptr, layout)
+            }
+        }
 
-                let old_count = pool.count;
-                let old_free_len = pool.free.len();
+        #[test]
+        fn behaves_like_the_default_pool()
+        {
+            let mut expected: ObjectPool<i32> = ObjectPool::new_growable(2);
+            let mut pool: ObjectPool<i32, usize, NonZeroU32, Global> = ObjectPool::new_growable_in(2, Global);
 
-                let key_at_cap = PoolKey { index: 1000, generation: 0 };
-                pool.delete(&key_at_cap);
+            let expected_keys: Vec<_> = (0..8).map(|i| expected.insert(i)).collect();
+            let keys: Vec<_> = (0..8).map(|i| pool.insert(i)).collect();
 
-                assert!(pool.data[key.index].data.is_some(), "Expected data to be unchanged.");
-                assert_eq!(pool.data[key.index].generation, key.generation, "Expected generation to remain unchanged.");
-                assert_eq!(pool.count, old_count, "Expected count to be unchanged.");
-                assert_eq!(pool.free.len(), old_free_len, "Expected free list length to be unchanged.");
+            for key in expected_keys.iter().step_by(3) { expected.delete(key); }
+            for key in keys.iter().step_by(3) { pool.delete(key); }
 
+            let expected_keys: Vec<_> = (8..12).map(|i| expected.insert(i)).collect();
+            let keys: Vec<_> = (8..12).map(|i| pool.insert(i)).collect();
 
-                let key_over_cap = PoolKey { index: 1000, generation: 0 };
-                pool.delete(&key_over_cap);
+            assert!(
+                keys.iter().map(|key| (key.index, key.generation)).eq(expected_keys.iter().map(|key| (key.index, key.generation))),
+                "Expected slots to be reused the same way.",
+            );
+            assert_eq!(pool.capacity(), expected.capacity());
+            assert_eq!(pool.len(), expected.len());
+            assert!(pool.iter().eq(expected.iter()), "Expected the same items, in the same slots.");
 
-                assert!(pool.data[key.index].data.is_some(), "Expected data to be unchanged.");
-                assert_eq!(pool.data[key.index].generation, key.generation, "Expected generation to remain unchanged.");
-                assert_eq!(pool.count, old_count, "Expected count to be unchanged.");
-                assert_eq!(pool.free.len(), old_free_len, "Expected free list length to be unchanged.");
-            }
+            pool.clear();
+            assert!(pool.is_empty());
+            assert!(!pool.contains_key(&keys[0]));
+        }
 
-            #[test]
-            fn returns_none_if_generation_mismatch()
-            {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                let key = pool.insert(100);
+        #[test]
+        fn allocates_through_the_given_allocator()
+        {
+            let mut pool: ObjectPool<&str, usize, NonZeroU32, Counting> = ObjectPool::new_growable_in(1, Counting);
+            let initial = ALLOCATIONS.with(Cell::get);
+            assert_eq!(initial, 1, "Expected the slots to be allocated through the allocator given.");
 
-                let old_count = pool.count;
-                let old_free_len = pool.free.len();
+            let key = pool.insert("Howdy!");
+            pool.insert("Howdy again!");
+            assert!(ALLOCATIONS.with(Cell::get) > initial, "Expected growth to allocate through the pool's allocator.");
 
-                let mut bad_key = key;
-                bad_key.generation = 100;
-                pool.delete(&bad_key);
+            assert_eq!(pool.capacity(), 2);
+            assert_eq!(pool.take(&key), Some("Howdy!"));
+            assert!(pool.iter().eq(&["Howdy again!"]));
+        }
 
-                assert!(pool.data[key.index].data.is_some(), "Expected data to be unchanged.");
-                assert_eq!(pool.data[key.index].generation, key.generation, "Expected generation to remain unchanged.");
-                assert_eq!(pool.count, old_count, "Expected count to be unchanged.");
-                assert_eq!(pool.free.len(), old_free_len, "Expected free list length to be unchanged.");
-            }
+        #[test]
+        fn new_creates_the_pool_in_a_default_allocator()
+        {
+            let before = ALLOCATIONS.with(Cell::get);
+            let pool: ObjectPool<i32, usize, NonZeroU32, Counting> = Pool::new(4);
 
-            #[test]
-            fn returns_none_if_data_is_none()
-            {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                let key = pool.insert(100);
+            assert_eq!(pool.capacity(), 4);
+            assert_eq!(ALLOCATIONS.with(Cell::get), before + 1);
+        }
 
-                let old_count = pool.count;
-                let old_free_len = pool.free.len();
+        #[test]
+        fn try_new_in_rejects_capacity_beyond_the_index_type()
+        {
+            assert!(ObjectPool::<i32, u8, NonZeroU32, Global>::try_new_in(256, Global).is_ok());
+            assert!(matches!(ObjectPool::<i32, u8, NonZeroU32, Global>::try_new_in(257, Global), Err(PoolError::CapacityOverflow)));
+        }
+    }
 
-                pool.data[key.index].data = None;
-                pool.delete(&key);
+    #[cfg(feature = "rayon")]
+    mod rayon
+    {
+        use rayon::prelude::*;
 
-                assert_eq!(pool.data[key.index].generation, key.generation, "Expected generation to remain unchanged.");
-                assert_eq!(pool.count, old_count, "Expected count to be unchanged.");
-                assert_eq!(pool.free.len(), old_free_len, "Expected free list length to be unchanged.");
-            }
-        }
+        use super::{
+            Pool,
+            ObjectPool,
+        };
 
-        mod clear
+        #[test]
+        fn par_iter_sums_the_same_as_iter()
         {
-            use super::super::{
-                Pool,
-                ObjectPool,
-            };
-
-            #[test]
-            fn replaces_all_items_with_none_and_clears_free_queue_and_resets_next()
-            {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                for _ in 0..10 { pool.insert(100); }
+            let mut pool = ObjectPool::new(10_000);
+            let keys: Vec<_> = (0..10_000u64).map(|i| pool.insert(i)).collect();
+            for key in keys.iter().step_by(7) { pool.delete(key); }
 
-                pool.clear();
+            let sequential: u64 = pool.iter().sum();
+            let parallel: u64 = pool.par_iter().sum();
 
-                for i in 0..10
-                {
-                    assert!(pool.data[i].data.is_none(), "Expected data at index {} to be None.", i);
-                    assert_eq!(pool.data[i].generation, 1, "Expected generation at index {} unchanged.", i);
-                }
-                assert_eq!(pool.count, 0, "Expected count to be 0.");
-                assert_eq!(pool.next, 0, "Expected next to be 0.");
-                assert_eq!(pool.free.len(), 0, "Expected free list length to be empty.");
-            }
+            assert_eq!(parallel, sequential, "Expected the parallel sum to skip the same empty slots.");
+            assert_eq!(pool.par_iter().count(), pool.len());
         }
 
-        mod iter
+        #[test]
+        fn par_iter_mut_reaches_every_item_once()
         {
-            use super::super::{
-                Pool,
-                ObjectPool,
-            };
+            let mut pool = ObjectPool::new(1_000);
+            let keys: Vec<_> = (0..1_000).map(|i| pool.insert(i)).collect();
+            pool.delete(&keys[500]);
 
-            #[test]
-            fn returns_an_empty_iterator_from_empty_pool()
-            {
-                let pool: ObjectPool<i32> = ObjectPool::new(10);
-                let data: Vec<_> = pool.iter().collect();
-                assert!(data.len() == 0, "Expected iterator to be empty.");
-            }
+            pool.par_iter_mut().for_each(|item| *item += 1);
 
-            #[test]
-            fn returns_an_iterator_to_all_contained_elements()
-            {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                for i in 0..10 { pool.insert(i); }
+            assert!(pool.iter().eq((1..=1_000).filter(|&i| i != 501).collect::<Vec<_>>().iter()));
+        }
+    }
 
-                let data: Vec<_> = pool.iter().collect();
-                assert!(data.len() == 10, "Expected iterator to contain 10 elements.");
-                assert_eq!(data, [&0, &1, &2, &3, &4, &5, &6, &7, &8, &9]);
-            }
+    mod pool_key
+    {
+        mod layout
+        {
+            use std::mem::size_of;
+            use super::super::PoolKey;
 
             #[test]
-            fn returns_an_iterator_correctly_skipping_none_elements()
+            fn option_of_key_is_niche_optimized()
             {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-
-                let _     = pool.insert(0);
-                let _     = pool.insert(1);
-                let item2 = pool.insert(2);
-                let _     = pool.insert(3);
-                let _     = pool.insert(4);
-                let _     = pool.insert(5);
-                let item6 = pool.insert(6);
-                let item7 = pool.insert(7);
-                let _     = pool.insert(8);
-                let item9 = pool.insert(9);
-
-                pool.delete(&item2);
-                pool.delete(&item6);
-                pool.delete(&item7);
-                pool.delete(&item9);
-
-                let data: Vec<_> = pool.iter().collect();
-                assert!(data.len() == 6, "Expected iterator to contain 6 elements.");
-                assert_eq!(data, [&0, &1, &3, &4, &5, &8]);
+                assert_eq!(size_of::<Option<PoolKey>>(), size_of::<PoolKey>());
             }
         }
 
-        mod iter_mut
+        mod as_u64
         {
+            use std::num::NonZeroU32;
             use super::super::{
                 Pool,
+                PoolKey,
                 ObjectPool,
             };
 
             #[test]
-            fn returns_an_empty_iterator_from_empty_pool()
+            fn round_trips_through_from_u64()
             {
                 let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                let data: Vec<_> = pool.iter_mut().collect();
-                assert!(data.len() == 0, "Expected iterator to be empty.");
+                let keys: Vec<_> = (0..10).map(|i| pool.insert(i)).collect();
+
+                pool.delete(&keys[4]);
+                let reused = pool.insert(100);
+
+                for key in keys.iter().chain(std::iter::once(&reused))
+                {
+                    let unpacked = PoolKey::from_u64(key.as_u64()).unwrap();
+
+                    assert_eq!((unpacked.index, unpacked.generation), (key.index, key.generation));
+                    assert_eq!(pool.get(&unpacked), pool.get(key), "Expected the unpacked key to resolve like the original.");
+                }
+
+                let extreme = PoolKey::new(u32::MAX as usize, NonZeroU32::new(u32::MAX).unwrap());
+                assert_eq!(PoolKey::from_u64(extreme.as_u64()), Some(extreme));
             }
 
             #[test]
-            fn returns_an_iterator_to_all_contained_elements()
+            fn packs_index_low_and_generation_high()
             {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
-                for i in 0..10 { pool.insert(i); }
-
-                let data: Vec<_> = pool.iter_mut().collect();
-                assert!(data.len() == 10, "Expected iterator to contain 10 elements.");
-                assert_eq!(data, [&0, &1, &2, &3, &4, &5, &6, &7, &8, &9]);
+                let key: PoolKey = PoolKey::new(7, NonZeroU32::new(3).unwrap());
+                assert_eq!(key.as_u64(), (3 << 32) | 7);
             }
 
             #[test]
-            fn returns_an_iterator_correctly_skipping_none_elements()
+            fn from_u64_rejects_zero_generation()
             {
-                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                assert_eq!(PoolKey::<usize>::from_u64(7), None);
+                assert_eq!(PoolKey::<usize>::from_u64(0), None);
+            }
 
-                let _     = pool.insert(0);
-                let _     = pool.insert(1);
-                let item2 = pool.insert(2);
-                let _     = pool.insert(3);
-                let _     = pool.insert(4);
-                let _     = pool.insert(5);
-                let item6 = pool.insert(6);
-                let item7 = pool.insert(7);
-                let _     = pool.insert(8);
-                let item9 = pool.insert(9);
+            #[test]
+            fn from_u64_rejects_index_out_of_range_of_index_type()
+            {
+                let key: PoolKey = PoolKey::new(300, NonZeroU32::new(1).unwrap());
 
-                pool.delete(&item2);
-                pool.delete(&item6);
-                pool.delete(&item7);
-                pool.delete(&item9);
+                assert_eq!(PoolKey::<u8>::from_u64(key.as_u64()), None);
+                assert!(PoolKey::<u16>::from_u64(key.as_u64()).is_some());
+            }
 
-                let data: Vec<_> = pool.iter_mut().collect();
-                assert!(data.len() == 6, "Expected iterator to contain 6 elements.");
-                assert_eq!(data, [&0, &1, &3, &4, &5, &8]);
+            #[test]
+            #[cfg(debug_assertions)]
+            #[cfg(target_pointer_width = "64")]
+            #[should_panic(expected = "exceeds u32::MAX")]
+            fn panics_in_debug_if_index_would_be_truncated()
+            {
+                let key = PoolKey::new(u32::MAX as usize + 1, NonZeroU32::new(1).unwrap());
+                key.as_u64();
             }
         }
     }
@@ -943,7 +9711,7 @@ mod tests
                 let val: PoolEntry<i32> = Default::default();
 
                 assert_eq!(val.generation, 0);
-                assert!(val.data.is_none());
+                assert!(val.is_empty());
             }
         }
 
@@ -960,9 +9728,9 @@ mod tests
                 val.set(100);
 
                 assert_eq!(val.generation, test_gen);
-                assert!(val.data.is_some());
+                assert!(!val.is_empty());
 
-                let inner = val.data.unwrap();
+                let inner = val.into_data().unwrap();
                 assert_eq!(inner, 100);
             }
         }
@@ -1007,7 +9775,7 @@ mod tests
             }
         }
 
-        mod clear
+        mod take
         {
             use super::super::PoolEntry;
 
@@ -1018,11 +9786,99 @@ mod tests
                 val.set(100);
 
                 let generation = val.generation;
-                val.clear();
+                let data = val.take();
 
                 assert_eq!(generation, val.generation);
-                assert!(val.data.is_none());
+                assert!(val.is_empty());
+                assert_eq!(data, Some(100), "Expected previous contents to be returned.");
+            }
+        }
+
+        mod invalidate
+        {
+            use super::super::PoolEntry;
+
+            #[test]
+            fn sets_contents_to_none_and_advances_generation()
+            {
+                let mut val: PoolEntry<i32> = Default::default();
+                val.set(100);
+
+                let generation = val.generation;
+                let data = val.invalidate();
+
+                assert_eq!(generation + 1, val.generation);
+                assert!(val.is_empty());
+                assert_eq!(data, Some(100), "Expected previous contents to be returned.");
+            }
+
+            #[test]
+            fn saturates_generation()
+            {
+                let mut val: PoolEntry<i32> = PoolEntry::with_generation(u32::MAX - 2);
+                val.set(100);
+
+                val.invalidate();
+
+                assert_eq!(val.generation, u32::MAX, "Expected the generation not to wrap.");
+                assert!(val.is_retired());
             }
         }
     }
+
+    mod free_list
+    {
+        use super::super::{
+            Pool,
+            ObjectPool,
+            FreeList,
+            SlotReuse,
+        };
+
+        #[test]
+        fn links_through_vacant_slots_without_allocating()
+        {
+            assert!(core::mem::size_of::<FreeList>() <= 4 * core::mem::size_of::<usize>(), "Expected only links, a length and the reuse order.");
+
+            let mut pool = ObjectPool::new(10);
+            let keys: Vec<_> = (0..5).map(|i| pool.insert(i)).collect();
+
+            pool.delete(&keys[1]);
+            pool.delete(&keys[3]);
+            pool.delete(&keys[4]);
+
+            assert_eq!(pool.free.len(), 3);
+            assert!(pool.free.iter(&pool.data).eq(vec![4, 3, 1]), "Expected the most recently freed slot on top.");
+        }
+
+        #[test]
+        fn retain_unlinks_from_anywhere_in_the_list()
+        {
+            let mut pool = ObjectPool::new(10);
+            let keys: Vec<_> = (0..5).map(|i| pool.insert(i)).collect();
+            for key in &keys { pool.delete(key); }
+
+            pool.free.retain(&mut pool.data, |index| index % 2 == 1);
+            assert!(pool.free.iter(&pool.data).eq(vec![3, 1]));
+
+            pool.free.retain(&mut pool.data, |index| index != 3);
+            assert!(pool.free.iter(&pool.data).eq(vec![1]));
+
+            assert_eq!(pool.free.pop(&pool.data), Some(1));
+            assert_eq!(pool.free.pop(&pool.data), None);
+        }
+
+        #[test]
+        fn retain_keeps_the_tail_linked()
+        {
+            let mut pool = ObjectPool::new(10).with_slot_reuse(SlotReuse::Fifo);
+            let keys: Vec<_> = (0..4).map(|i| pool.insert(i)).collect();
+            for key in &keys[..3] { pool.delete(key); }
+
+            pool.free.retain(&mut pool.data, |index| index != 2);
+            pool.delete(&keys[3]);
+
+            assert!(pool.free.iter(&pool.data).eq(vec![0, 1, 3]), "Expected a push after unlinking the tail to land after the new tail.");
+        }
+    }
 }
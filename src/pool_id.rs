@@ -0,0 +1,139 @@
+#[cfg(feature = "pool-id")]
+use core::sync::atomic::{ AtomicU64, Ordering };
+
+#[cfg(feature = "serde")]
+use serde::{ Serialize, Deserialize };
+
+/// Identifies the pool instance which issued a key.
+///
+/// Zero-sized unless the `pool-id` feature is enabled, in which case each pool draws a unique id at construction.
+/// Keys carrying [`NONE`], such as those unpacked by `PoolKey::from_u64`, are accepted by every pool.
+///
+/// Ids count up from a random seed, drawn once per process, so that an id deserialized from an earlier run
+/// is unlikely to collide with one drawn in this run. The seed comes from std's `RandomState`;
+/// without the `std` feature there's no source of randomness to draw on, so ids count up from a fixed seed,
+/// and ids from earlier runs may well collide with fresh ones.
+///
+/// [`NONE`]: #associatedconstant.NONE
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub(crate) struct PoolId
+{
+    #[cfg(feature = "pool-id")]
+    id: u64,
+}
+
+impl PoolId
+{
+    pub(crate) const NONE: PoolId = PoolId {
+        #[cfg(feature = "pool-id")]
+        id: 0,
+    };
+
+    /// Returns a new id, distinct from any other returned so far.
+    pub(crate) fn unique() -> PoolId
+    {
+        #[cfg(feature = "pool-id")]
+        {
+            // Zero until seeded, and again should the count ever wrap, after which it's seeded afresh.
+            static NEXT: AtomicU64 = AtomicU64::new(0);
+
+            loop
+            {
+                if NEXT.load(Ordering::Relaxed) == 0
+                {
+                    let _ = NEXT.compare_exchange(0, seed(), Ordering::Relaxed, Ordering::Relaxed);
+                }
+
+                let id = NEXT.fetch_add(1, Ordering::Relaxed);
+                if id != PoolId::NONE.id { return PoolId { id }; }
+            }
+        }
+
+        #[cfg(not(feature = "pool-id"))]
+        return PoolId::NONE;
+    }
+
+    /// Returns `true` if a key tagged with `key` may be used against the pool identified by `self`.
+    /// Always `true` unless the `pool-id` feature is enabled, or if either id is [`NONE`].
+    ///
+    /// [`NONE`]: #associatedconstant.NONE
+    pub(crate) fn admits(&self, key: PoolId) -> bool
+    {
+        return key == PoolId::NONE || *self == PoolId::NONE || key == *self;
+    }
+}
+
+/// Returns a random, non-zero seed for the id count.
+#[cfg(all(feature = "pool-id", feature = "std"))]
+fn seed() -> u64
+{
+    use std::collections::hash_map::RandomState;
+    use std::hash::{ BuildHasher, Hasher };
+
+    return RandomState::new().build_hasher().finish().max(1);
+}
+
+/// Returns a fixed seed for the id count, lacking any source of randomness without `std`.
+#[cfg(all(feature = "pool-id", not(feature = "std")))]
+fn seed() -> u64
+{
+    return 1;
+}
+
+#[cfg(feature = "rkyv")]
+impl ArchivedPoolId
+{
+    /// Returns `true` if a key tagged with `key` may be used against the archived pool identified by `self`.
+    pub(crate) fn admits(&self, key: PoolId) -> bool
+    {
+        let id = PoolId {
+            #[cfg(feature = "pool-id")]
+            id: self.id.to_native(),
+        };
+        return id.admits(key);
+    }
+}
+
+impl Default for PoolId
+{
+    fn default() -> Self
+    {
+        Self::unique()
+    }
+}
+
+
+#[cfg(all(test, feature = "pool-id"))]
+mod tests
+{
+    mod pool_id
+    {
+        use super::super::PoolId;
+
+        #[test]
+        fn unique_ids_differ()
+        {
+            assert_ne!(PoolId::unique(), PoolId::unique());
+            assert_ne!(PoolId::unique(), PoolId::NONE);
+        }
+
+        #[test]
+        #[cfg(feature = "std")]
+        fn ids_start_from_a_random_seed()
+        {
+            assert!(PoolId::unique().id > 1 << 16, "Expected the count not to start from a small fixed seed.");
+        }
+
+        #[test]
+        fn admits_own_and_untagged_keys_only()
+        {
+            let id = PoolId::unique();
+
+            assert!(id.admits(id));
+            assert!(id.admits(PoolId::NONE));
+            assert!(!id.admits(PoolId::unique()));
+        }
+    }
+}
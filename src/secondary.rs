@@ -0,0 +1,458 @@
+use alloc::vec::Vec;
+use core::hash::{ Hash, Hasher };
+use core::marker::PhantomData;
+use core::num::NonZeroU32;
+
+use crate::index::PoolIndex;
+use crate::pool::PoolKey;
+use crate::pool_id::PoolId;
+
+#[cfg(feature = "serde")]
+use serde::{ Serialize, Deserialize };
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct SecondaryEntry<V>
+{
+    generation: NonZeroU32,
+    #[cfg_attr(all(feature = "serde", not(feature = "pool-id")), serde(skip))]
+    pool_id: PoolId,
+    value: V,
+}
+
+// ===-===-===-===-===-===-===-===-===-===-===-===-=== //
+
+/// Associates additional data with the items of a [`Pool`], keyed by [`PoolKey`].
+///
+/// Like the pool itself, entries remember the generation of the key they were inserted with,
+/// so a stale key never resolves to data inserted for a newer occupant of the same slot.
+///
+/// [`Pool`]: trait.Pool.html
+/// [`PoolKey`]: struct.PoolKey.html
+///
+/// ```rust
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use spool::{ ObjectPool, Pool, SecondaryMap };
+///
+/// let mut pool = ObjectPool::new(10);
+/// let mut names = SecondaryMap::new();
+///
+/// let key = pool.insert(100);
+/// names.insert(&key, "One hundred");
+///
+/// assert_eq!(names.get(&key), Some(&"One hundred"));
+///
+/// pool.delete(&key);
+/// let new_key = pool.insert(200);
+///
+/// //Same slot, but the old key doesn't see data for the new occupant.
+/// names.insert(&new_key, "Two hundred");
+/// assert!(names.get(&key).is_none());
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SecondaryMap<V, I = usize>
+{
+    count: usize,
+    slots: Vec<Option<SecondaryEntry<V>>>,
+    index: PhantomData<I>,
+}
+
+// Implemented by hand, as derives would also bound the index type, which the map only holds as a marker.
+impl<V: Clone, I> Clone for SecondaryMap<V, I>
+{
+    fn clone(&self) -> Self
+    {
+        Self {
+            count: self.count,
+            slots: self.slots.clone(),
+            index: PhantomData,
+        }
+    }
+}
+
+impl<V: PartialEq, I> PartialEq for SecondaryMap<V, I>
+{
+    fn eq(&self, other: &Self) -> bool { self.count == other.count && self.slots == other.slots }
+}
+
+impl<V: Eq, I> Eq for SecondaryMap<V, I> {}
+
+impl<V: Hash, I> Hash for SecondaryMap<V, I>
+{
+    fn hash<H: Hasher>(&self, state: &mut H)
+    {
+        self.count.hash(state);
+        self.slots.hash(state);
+    }
+}
+
+impl<V, I: PoolIndex> Default for SecondaryMap<V, I>
+{
+    fn default() -> Self
+    {
+        Self {
+            count: 0,
+            slots: Vec::new(),
+            index: PhantomData,
+        }
+    }
+}
+
+impl<V> SecondaryMap<V>
+{
+    /// Returns a new, empty map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::SecondaryMap;
+    ///
+    /// let map: SecondaryMap<i32> = SecondaryMap::new();
+    /// assert!(map.is_empty());
+    /// ```
+    ///
+    /// For keys with a narrower index type, use `SecondaryMap::<V, I>::default()`.
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+}
+
+impl<V, I: PoolIndex> SecondaryMap<V, I>
+{
+    /// Returns the number of values held by the map.
+    pub fn len(&self) -> usize { self.count }
+
+    /// Returns `true` if the map holds no values.
+    pub fn is_empty(&self) -> bool { self.count == 0 }
+
+    // ====-====-====-====-====-==== //
+
+    /// Inserts a value for the [`PoolKey`] referenced, returning the previous value for that key, if any.
+    ///
+    /// A value held for an older generation of the same slot is replaced, and not returned.
+    /// If the key is older than the one the current value was inserted with, or is the [`null`] key, nothing is inserted and `None` is returned.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    /// [`null`]: struct.PoolKey.html#method.null
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool, SecondaryMap };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let mut map = SecondaryMap::new();
+    ///
+    /// let key = pool.insert(());
+    ///
+    /// assert_eq!(map.insert(&key, 1), None);
+    /// assert_eq!(map.insert(&key, 2), Some(1));
+    /// ```
+    pub fn insert(&mut self, key: &PoolKey<I>, value: V) -> Option<V>
+    {
+        if key.is_null() { return None; }
+
+        let index = key.index.into_usize();
+        if index >= self.slots.len()
+        {
+            self.slots.resize_with(index + 1, || None);
+        }
+
+        let slot = &mut self.slots[index];
+        match slot
+        {
+            Some(entry) if entry.generation > key.generation => { return None; }
+            Some(entry) if entry.generation == key.generation =>
+            {
+                return Some(core::mem::replace(&mut entry.value, value));
+            }
+            Some(entry) =>
+            {
+                *entry = SecondaryEntry { generation: key.generation, pool_id: key.pool_id, value };
+                return None;
+            }
+            None =>
+            {
+                *slot = Some(SecondaryEntry { generation: key.generation, pool_id: key.pool_id, value });
+                self.count += 1;
+                return None;
+            }
+        }
+    }
+
+    /// Retrieves an Option<&V> corresponding to the [`PoolKey`] referenced.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    pub fn get(&self, key: &PoolKey<I>) -> Option<&V>
+    {
+        match self.slots.get(key.index.into_usize())
+        {
+            Some(Some(entry)) if entry.generation == key.generation => Some(&entry.value),
+            _ => None,
+        }
+    }
+
+    /// Retrieves an Option<&mut V> corresponding to the [`PoolKey`] referenced.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    pub fn get_mut(&mut self, key: &PoolKey<I>) -> Option<&mut V>
+    {
+        match self.slots.get_mut(key.index.into_usize())
+        {
+            Some(Some(entry)) if entry.generation == key.generation => Some(&mut entry.value),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the map holds a value for the [`PoolKey`] referenced.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    pub fn contains_key(&self, key: &PoolKey<I>) -> bool
+    {
+        self.get(key).is_some()
+    }
+
+    /// Removes and returns the value corresponding to the [`PoolKey`] referenced.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use spool::{ ObjectPool, Pool, SecondaryMap };
+    ///
+    /// let mut pool = ObjectPool::new(10);
+    /// let mut map = SecondaryMap::new();
+    ///
+    /// let key = pool.insert(());
+    /// map.insert(&key, "Remove me!");
+    ///
+    /// assert_eq!(map.remove(&key), Some("Remove me!"));
+    /// assert!(map.get(&key).is_none());
+    /// ```
+    pub fn remove(&mut self, key: &PoolKey<I>) -> Option<V>
+    {
+        let slot = self.slots.get_mut(key.index.into_usize())?;
+        match slot
+        {
+            Some(entry) if entry.generation == key.generation =>
+            {
+                self.count -= 1;
+                slot.take().map(|entry| entry.value)
+            }
+            _ => None,
+        }
+    }
+
+    /// Removes all values.
+    pub fn clear(&mut self)
+    {
+        self.slots.clear();
+        self.count = 0;
+    }
+
+    // ====-====-====-====-====-==== //
+
+    /// Returns an iterator over the values in the map, along with their [`PoolKey`]s.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    pub fn iter(&self) -> impl Iterator<Item = (PoolKey<I>, &'_ V)>
+    {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            let e = slot.as_ref()?;
+            Some((PoolKey::tagged(I::from_usize(index)?, e.generation, e.pool_id), &e.value))
+        })
+    }
+
+    /// Returns an iterator over mutable references to the values in the map, along with their [`PoolKey`]s.
+    ///
+    /// [`PoolKey`]: struct.PoolKey.html
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (PoolKey<I>, &'_ mut V)>
+    {
+        self.slots.iter_mut().enumerate().filter_map(|(index, slot)| {
+            let e = slot.as_mut()?;
+            Some((PoolKey::tagged(I::from_usize(index)?, e.generation, e.pool_id), &mut e.value))
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    use crate::pool::{
+        Pool,
+        ObjectPool,
+    };
+
+    mod secondary_map
+    {
+        mod insert
+        {
+            use crate::pool::PoolKey;
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+            use super::super::super::SecondaryMap;
+
+            #[test]
+            fn ignores_null_key()
+            {
+                let mut map: SecondaryMap<i32> = SecondaryMap::new();
+
+                assert_eq!(map.insert(&PoolKey::null(), 100), None);
+                assert!(map.is_empty(), "Expected nothing to be inserted for the null key.");
+                assert!(map.get(&PoolKey::null()).is_none());
+            }
+
+            #[test]
+            fn returns_previous_value_for_same_key()
+            {
+                let mut pool: ObjectPool<()> = ObjectPool::new(10);
+                let mut map = SecondaryMap::new();
+                let key = pool.insert(());
+
+                assert_eq!(map.insert(&key, 100), None);
+                assert_eq!(map.insert(&key, 200), Some(100));
+                assert_eq!(map.get(&key), Some(&200));
+                assert_eq!(map.len(), 1);
+            }
+
+            #[test]
+            fn replaces_value_of_older_generation_without_returning_it()
+            {
+                let mut pool: ObjectPool<()> = ObjectPool::new(10);
+                let mut map = SecondaryMap::new();
+
+                let old_key = pool.insert(());
+                map.insert(&old_key, 100);
+
+                pool.delete(&old_key);
+                let new_key = pool.insert(());
+
+                assert_eq!(map.insert(&new_key, 200), None);
+                assert_eq!(map.get(&new_key), Some(&200));
+                assert_eq!(map.len(), 1, "Expected replaced value not to be counted twice.");
+            }
+
+            #[test]
+            fn ignores_keys_older_than_current_value()
+            {
+                let mut pool: ObjectPool<()> = ObjectPool::new(10);
+                let mut map = SecondaryMap::new();
+
+                let old_key = pool.insert(());
+                pool.delete(&old_key);
+                let new_key = pool.insert(());
+
+                map.insert(&new_key, 200);
+
+                assert_eq!(map.insert(&old_key, 100), None);
+                assert_eq!(map.get(&new_key), Some(&200), "Expected newer value to be kept.");
+                assert!(map.get(&old_key).is_none());
+            }
+        }
+
+        mod get
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+            use super::super::super::SecondaryMap;
+
+            #[test]
+            fn stale_key_does_not_read_value_of_reused_slot()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let mut map = SecondaryMap::new();
+
+                let old_key = pool.insert(1);
+                map.insert(&old_key, "Old");
+
+                pool.delete(&old_key);
+                let new_key = pool.insert(2);
+                assert_eq!(old_key.index, new_key.index, "Expected freed slot to be reused.");
+
+                map.insert(&new_key, "New");
+
+                assert!(map.get(&old_key).is_none(), "Expected stale key to resolve to None.");
+                assert!(map.get_mut(&old_key).is_none(), "Expected stale key to resolve to None.");
+                assert!(!map.contains_key(&old_key));
+                assert_eq!(map.get(&new_key), Some(&"New"));
+            }
+
+            #[test]
+            fn returns_none_for_keys_never_inserted()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let map: SecondaryMap<i32> = SecondaryMap::new();
+
+                let key = pool.insert(1);
+                assert!(map.get(&key).is_none());
+            }
+        }
+
+        mod remove
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+            use super::super::super::SecondaryMap;
+
+            #[test]
+            fn removes_value_for_matching_key_only()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let mut map = SecondaryMap::new();
+
+                let old_key = pool.insert(1);
+                pool.delete(&old_key);
+                let new_key = pool.insert(2);
+
+                map.insert(&new_key, "New");
+
+                assert_eq!(map.remove(&old_key), None, "Expected stale key not to remove newer value.");
+                assert_eq!(map.len(), 1);
+
+                assert_eq!(map.remove(&new_key), Some("New"));
+                assert_eq!(map.len(), 0);
+                assert!(map.get(&new_key).is_none());
+                assert_eq!(map.remove(&new_key), None);
+            }
+        }
+
+        mod iter
+        {
+            use super::super::{
+                Pool,
+                ObjectPool,
+            };
+            use super::super::super::SecondaryMap;
+
+            #[test]
+            fn yields_values_with_their_keys()
+            {
+                let mut pool: ObjectPool<i32> = ObjectPool::new(10);
+                let mut map = SecondaryMap::new();
+
+                let keys: Vec<_> = (0..5).map(|i| pool.insert(i)).collect();
+                for key in keys.iter().step_by(2) { map.insert(key, pool[*key] * 10); }
+
+                for (_, value) in map.iter_mut() { *value += 1; }
+
+                let data: Vec<_> = map.iter().collect();
+                assert_eq!(data, [(keys[0], &1), (keys[2], &21), (keys[4], &41)]);
+            }
+        }
+    }
+}
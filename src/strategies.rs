@@ -0,0 +1,121 @@
+//! [`proptest`] strategies for pools and keys, enabled by the `proptest` feature.
+//!
+//! [`proptest`]: https://docs.rs/proptest
+
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use proptest::prelude::*;
+use proptest::sample::{ select, Index };
+
+use crate::pool::{ Pool, PoolKey, ObjectPool };
+
+/// Returns a strategy yielding pools of up to `max_capacity` slots, holding items from `item`,
+/// along with the keys of every item in the pool. Each key is valid for its pool by construction.
+///
+/// Pools are built by replaying a random sequence of inserts and deletes, so they come with freed and reused slots.
+///
+/// # Examples
+///
+/// ```
+/// use proptest::prelude::*;
+/// use spool::Pool;
+/// use spool::strategies::pool_strategy;
+///
+/// proptest!(|((pool, keys) in pool_strategy(any::<i32>(), 16))| {
+///     for key in &keys
+///     {
+///         prop_assert!(pool.get(key).is_some());
+///     }
+/// });
+/// ```
+pub fn pool_strategy<T, S>(item: S, max_capacity: usize) -> impl Strategy<Value = (ObjectPool<T>, Vec<PoolKey>)>
+where
+    T: Debug,
+    S: Strategy<Value = T>,
+{
+    let ops = proptest::collection::vec((any::<bool>(), any::<Index>(), item), 0..=max_capacity.saturating_mul(2));
+
+    (0..=max_capacity, ops).prop_map(|(capacity, ops)| {
+        let mut pool = ObjectPool::new(capacity);
+        let mut keys = Vec::new();
+
+        for (delete, index, item) in ops
+        {
+            if delete && !keys.is_empty()
+            {
+                let key = keys.swap_remove(index.index(keys.len()));
+                pool.delete(&key);
+            }
+            else if let Ok(key) = pool.try_insert(item)
+            {
+                keys.push(key);
+            }
+        }
+
+        return (pool, keys);
+    })
+}
+
+/// Returns a strategy yielding the keys of the items in `pool`, each valid for it.
+///
+/// # Panics
+///
+/// This function panics if the pool is empty, as there are no valid keys to yield.
+///
+/// # Examples
+///
+/// ```
+/// use proptest::prelude::*;
+/// use spool::{ ObjectPool, Pool };
+/// use spool::strategies::valid_key_strategy;
+///
+/// let pool: ObjectPool<i32> = (0..10).collect();
+///
+/// proptest!(|(key in valid_key_strategy(&pool))| {
+///     prop_assert!(pool.contains_key(&key));
+/// });
+/// ```
+pub fn valid_key_strategy<T>(pool: &ObjectPool<T>) -> impl Strategy<Value = PoolKey>
+{
+    select(pool.keys().collect::<Vec<_>>())
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    mod strategies
+    {
+        use proptest::prelude::*;
+
+        use crate::pool::Pool;
+        use super::super::{ pool_strategy, valid_key_strategy };
+
+        proptest!
+        {
+            #[test]
+            fn yielded_keys_resolve((pool, keys) in pool_strategy(any::<u8>(), 32))
+            {
+                prop_assert_eq!(keys.len(), pool.len(), "Expected a key for every item.");
+                for key in &keys
+                {
+                    prop_assert!(pool.get(key).is_some());
+                }
+            }
+
+            #[test]
+            fn valid_keys_resolve(
+                (pool, key) in pool_strategy(any::<u8>(), 32)
+                    .prop_filter("pool has no keys", |(pool, _)| !pool.is_empty())
+                    .prop_flat_map(|(pool, _)| {
+                        let key = valid_key_strategy(&pool);
+                        (Just(pool), key)
+                    })
+            )
+            {
+                prop_assert!(pool.get(&key).is_some());
+            }
+        }
+    }
+}
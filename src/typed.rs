@@ -0,0 +1,279 @@
+use core::fmt;
+use core::hash::{ Hash, Hasher };
+use core::marker::PhantomData;
+
+use crate::error::PoolFull;
+use crate::index::PoolIndex;
+use crate::pool::{ Pool, PoolKey, ObjectPool, Iter, IterMut };
+
+/// A [`PoolKey`] tagged with the type of item it refers to, issued by a [`TypedPool`].
+///
+/// The compiler rejects using a key for one item type against a pool of another.
+/// Converts to and from the untyped [`PoolKey`] for low-level use.
+///
+/// [`PoolKey`]: struct.PoolKey.html
+/// [`TypedPool`]: struct.TypedPool.html
+pub struct TypedPoolKey<T, I = usize>
+{
+    key: PoolKey<I>,
+    item: PhantomData<fn() -> T>,
+}
+
+impl<T, I: PoolIndex> TypedPoolKey<T, I>
+{
+    /// Tags an untyped key with an item type.
+    /// The key is only meaningful against the pool which issued it, same as the untyped key.
+    pub fn from_untyped(key: PoolKey<I>) -> Self
+    {
+        Self { key, item: PhantomData }
+    }
+
+    /// Returns the underlying untyped key.
+    pub fn untyped(&self) -> PoolKey<I> { self.key }
+}
+
+// Implemented by hand, so the item type needn't implement these traits itself.
+impl<T, I: Copy> Copy for TypedPoolKey<T, I> {}
+
+impl<T, I: Copy> Clone for TypedPoolKey<T, I>
+{
+    fn clone(&self) -> Self { *self }
+}
+
+impl<T, I: fmt::Debug> fmt::Debug for TypedPoolKey<T, I>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        f.debug_tuple("TypedPoolKey").field(&self.key).finish()
+    }
+}
+
+impl<T, I: PartialEq> PartialEq for TypedPoolKey<T, I>
+{
+    fn eq(&self, other: &Self) -> bool { self.key == other.key }
+}
+
+impl<T, I: Eq> Eq for TypedPoolKey<T, I> {}
+
+impl<T, I: Hash> Hash for TypedPoolKey<T, I>
+{
+    fn hash<H: Hasher>(&self, state: &mut H) { self.key.hash(state); }
+}
+
+impl<T, I> From<TypedPoolKey<T, I>> for PoolKey<I>
+{
+    fn from(key: TypedPoolKey<T, I>) -> Self { key.key }
+}
+
+// ===-===-===-===-===-===-===-===-===-===-===-===-=== //
+
+/// An [`ObjectPool`] issuing [`TypedPoolKey`]s, so keys can't be mixed up between pools of different item types.
+///
+/// [`ObjectPool`]: struct.ObjectPool.html
+/// [`TypedPoolKey`]: struct.TypedPoolKey.html
+///
+/// ```rust
+/// use spool::TypedPool;
+///
+/// let mut names = TypedPool::new(10);
+/// let mut ages = TypedPool::new(10);
+///
+/// let name = names.insert("Bob");
+/// let age = ages.insert(42);
+///
+/// assert_eq!(names.get(&name), Some(&"Bob"));
+///
+/// //Doesn't compile, the key is for a `&str`!
+/// //ages.get(&name);
+/// ```
+pub struct TypedPool<T, I = usize>
+{
+    pool: ObjectPool<T, I>,
+}
+
+// The traits below are implemented by hand, as derives would also bound the index type, and `T` by `Default` for `Default`.
+impl<T: Clone, I> Clone for TypedPool<T, I>
+{
+    fn clone(&self) -> Self { Self { pool: self.pool.clone() } }
+}
+
+impl<T: PartialEq, I> PartialEq for TypedPool<T, I>
+{
+    fn eq(&self, other: &Self) -> bool { self.pool == other.pool }
+}
+
+impl<T: Eq, I> Eq for TypedPool<T, I> {}
+
+impl<T: Hash, I> Hash for TypedPool<T, I>
+{
+    fn hash<H: Hasher>(&self, state: &mut H) { self.pool.hash(state); }
+}
+
+impl<T, I> Default for TypedPool<T, I>
+{
+    fn default() -> Self
+    {
+        Self { pool: ObjectPool::default() }
+    }
+}
+
+// Implemented by hand, as the pool's own `Debug` needs the index type to be a `PoolIndex`.
+impl<T: fmt::Debug, I: PoolIndex + fmt::Debug> fmt::Debug for TypedPool<T, I>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        f.debug_struct("TypedPool").field("pool", &self.pool).finish()
+    }
+}
+
+impl<T> TypedPool<T>
+{
+    /// Returns a new, empty pool. Preallocated with specified capacity.
+    ///
+    /// See [`ObjectPool::new`].
+    ///
+    /// [`ObjectPool::new`]: struct.ObjectPool.html#method.new
+    pub fn new(capacity: usize) -> Self
+    {
+        Self { pool: ObjectPool::new(capacity) }
+    }
+}
+
+impl<T, I: PoolIndex> TypedPool<T, I>
+{
+    /// Wraps an existing pool. Its items become reachable through typed keys.
+    pub fn from_untyped(pool: ObjectPool<T, I>) -> Self
+    {
+        Self { pool }
+    }
+
+    /// Returns the underlying pool, for use with untyped keys.
+    pub fn untyped(&self) -> &ObjectPool<T, I> { &self.pool }
+
+    /// Returns the underlying pool mutably, for use with untyped keys.
+    pub fn untyped_mut(&mut self) -> &mut ObjectPool<T, I> { &mut self.pool }
+
+    /// Consumes the wrapper, returning the underlying pool.
+    pub fn into_untyped(self) -> ObjectPool<T, I> { self.pool }
+
+    // ====-====-====-====-====-==== //
+
+    pub fn capacity(&self) -> usize { self.pool.capacity() }
+
+    pub fn len(&self) -> usize { self.pool.len() }
+
+    pub fn is_empty(&self) -> bool { self.pool.is_empty() }
+
+    // ====-====-====-====-====-==== //
+
+    /// See [`Pool::insert`].
+    ///
+    /// [`Pool::insert`]: trait.Pool.html#tymethod.insert
+    pub fn insert(&mut self, value: T) -> TypedPoolKey<T, I>
+    {
+        TypedPoolKey::from_untyped(self.pool.insert(value))
+    }
+
+    /// See [`Pool::try_insert`].
+    ///
+    /// [`Pool::try_insert`]: trait.Pool.html#tymethod.try_insert
+    pub fn try_insert(&mut self, value: T) -> Result<TypedPoolKey<T, I>, PoolFull<T>>
+    {
+        self.pool.try_insert(value).map(TypedPoolKey::from_untyped)
+    }
+
+    pub fn get(&self, key: &TypedPoolKey<T, I>) -> Option<&T>
+    {
+        self.pool.get(&key.key)
+    }
+
+    pub fn get_mut(&mut self, key: &TypedPoolKey<T, I>) -> Option<&mut T>
+    {
+        self.pool.get_mut(&key.key)
+    }
+
+    pub fn contains_key(&self, key: &TypedPoolKey<T, I>) -> bool
+    {
+        self.pool.contains_key(&key.key)
+    }
+
+    pub fn take(&mut self, key: &TypedPoolKey<T, I>) -> Option<T>
+    {
+        self.pool.take(&key.key)
+    }
+
+    pub fn delete(&mut self, key: &TypedPoolKey<T, I>) -> bool
+    {
+        self.pool.delete(&key.key)
+    }
+
+    pub fn clear(&mut self)
+    {
+        self.pool.clear()
+    }
+
+    // ====-====-====-====-====-==== //
+
+    pub fn iter(&self) -> Iter<'_, T> { self.pool.iter() }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> { self.pool.iter_mut() }
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    mod typed_pool
+    {
+        use crate::pool::{
+            Pool,
+            PoolKey,
+        };
+        use super::super::{
+            TypedPool,
+            TypedPoolKey,
+        };
+
+        #[test]
+        fn behaves_like_untyped_pool()
+        {
+            let mut pool = TypedPool::new(2);
+
+            let key1 = pool.insert(1);
+            let key2 = pool.insert(2);
+            assert!(pool.try_insert(3).is_err());
+
+            assert_eq!(pool.get(&key1), Some(&1));
+            assert_eq!(pool.take(&key2), Some(2));
+            assert!(!pool.contains_key(&key2), "Expected taken key to be invalid.");
+
+            pool.clear();
+            assert!(pool.get(&key1).is_none());
+            assert!(pool.is_empty());
+        }
+
+        #[test]
+        fn keys_convert_to_and_from_untyped()
+        {
+            let mut pool = TypedPool::new(10);
+            let key = pool.insert("Howdy!");
+
+            let untyped: PoolKey = key.into();
+            assert_eq!(pool.untyped().get(&untyped), Some(&"Howdy!"));
+            assert_eq!(TypedPoolKey::from_untyped(untyped), key);
+        }
+
+        #[test]
+        fn keys_need_no_bounds_on_item_type()
+        {
+            struct Opaque;
+
+            let mut pool = TypedPool::new(10);
+            let key = pool.insert(Opaque);
+            let copy = key;
+
+            assert_eq!(key, copy);
+            assert!(pool.get(&copy).is_some());
+        }
+    }
+}
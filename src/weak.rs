@@ -0,0 +1,97 @@
+use crate::index::PoolIndex;
+use crate::pool::{ Pool, PoolKey, ObjectPool };
+
+#[cfg(feature = "serde")]
+use serde::{ Serialize, Deserialize };
+
+/// A handle to an item which doesn't claim it is still there, upgraded to a [`PoolKey`] only once checked against the pool.
+///
+/// A [`PoolKey`] already stops resolving once its item is removed, even if the slot is reused,
+/// so this is the same key with the check made explicit: holders of a `WeakPoolKey` can't use it without [`upgrade`]-ing first.
+/// Suits observers, which must expect their subject to be gone.
+///
+/// [`PoolKey`]: struct.PoolKey.html
+/// [`upgrade`]: #method.upgrade
+///
+/// # Examples
+///
+/// ```
+/// use spool::{ ObjectPool, Pool, WeakPoolKey };
+///
+/// let mut pool = ObjectPool::new(10);
+/// let key = pool.insert("Subject");
+/// let weak = key.downgrade();
+///
+/// assert_eq!(weak.upgrade(&pool), Some(key));
+///
+/// pool.delete(&key);
+/// assert_eq!(weak.upgrade(&pool), None);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WeakPoolKey<I = usize>
+{
+    key: PoolKey<I>,
+}
+
+impl<I: PoolIndex> WeakPoolKey<I>
+{
+    /// Returns a weak handle to the item of `key`.
+    pub fn new(key: PoolKey<I>) -> Self
+    {
+        Self { key }
+    }
+
+    /// Returns the key, if it still refers to an item in the pool.
+    /// Returns `None` once the item has been removed, whether or not its slot has been reused since.
+    pub fn upgrade<T>(&self, pool: &ObjectPool<T, I>) -> Option<PoolKey<I>>
+    {
+        if pool.contains_key(&self.key) { Some(self.key) }
+        else                            { None }
+    }
+}
+
+impl<I: PoolIndex> From<PoolKey<I>> for WeakPoolKey<I>
+{
+    fn from(key: PoolKey<I>) -> Self { Self::new(key) }
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    mod weak_pool_key
+    {
+        use crate::pool::{
+            Pool,
+            ObjectPool,
+        };
+        use super::super::WeakPoolKey;
+
+        #[test]
+        fn upgrades_while_the_item_is_live()
+        {
+            let mut pool = ObjectPool::new(10);
+            let key = pool.insert(1);
+            let weak = WeakPoolKey::from(key);
+
+            assert_eq!(weak.upgrade(&pool), Some(key));
+            assert_eq!(pool.get(&weak.upgrade(&pool).unwrap()), Some(&1));
+        }
+
+        #[test]
+        fn fails_to_upgrade_after_the_slot_is_reused()
+        {
+            let mut pool = ObjectPool::new(10);
+            let key = pool.insert(1);
+            let weak = key.downgrade();
+
+            pool.delete(&key);
+            let new_key = pool.insert(2);
+            assert_eq!(new_key.index, key.index, "Expected the slot to be reused.");
+
+            assert_eq!(weak.upgrade(&pool), None, "Expected the weak key to see through the reuse.");
+            assert_eq!(new_key.downgrade().upgrade(&pool), Some(new_key));
+        }
+    }
+}
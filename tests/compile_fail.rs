@@ -0,0 +1,6 @@
+#[test]
+fn compile_fail()
+{
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
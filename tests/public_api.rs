@@ -0,0 +1,36 @@
+use spool::{
+    create_default_pool,
+    Pool,
+    PoolKey,
+    ObjectPool,
+};
+
+#[test]
+fn reexported_names_are_usable()
+{
+    let mut pool: ObjectPool<i32> = create_default_pool(10);
+    assert_eq!(pool.capacity(), 10);
+
+    let key: PoolKey = pool.insert(100);
+    assert_eq!(pool.get(&key), Some(&100));
+
+    pool.delete(&key);
+    assert!(pool.get(&key).is_none());
+}
+
+#[test]
+fn default_pool_is_usable_through_pool_trait()
+{
+    fn fill<P: Pool<i32>>(pool: &mut P) -> Vec<PoolKey>
+    {
+        (0..pool.capacity() as i32).map(|i| pool.insert(i)).collect()
+    }
+
+    let mut pool = create_default_pool(4);
+    let keys = fill(&mut pool);
+
+    for (i, key) in keys.iter().enumerate()
+    {
+        assert_eq!(pool.get(key), Some(&(i as i32)));
+    }
+}
@@ -0,0 +1,13 @@
+use spool::TypedPool;
+
+struct Foo;
+struct Bar;
+
+fn main()
+{
+    let mut foos = TypedPool::new(10);
+    let bars: TypedPool<Bar> = TypedPool::new(10);
+
+    let foo_key = foos.insert(Foo);
+    bars.get(&foo_key);
+}